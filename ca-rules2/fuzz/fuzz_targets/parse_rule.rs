@@ -0,0 +1,14 @@
+//! `parse_rule` must return an error instead of panicking on any input.
+//!
+//! Run with `cargo fuzz run parse_rule` in the `ca-rules2` directory.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let rule_string = String::from_utf8_lossy(data);
+    if let Ok(rule) = ca_rules2::parse_rule(&rule_string) {
+        let _ = rule.to_string();
+    }
+});
@@ -1,3 +1,4 @@
+use crate::NeighborhoodType;
 use thiserror::Error;
 
 /// An error that can occur when generating a list of neighbors.
@@ -6,6 +7,16 @@ pub enum NeighborError {
     /// The radius is too large.
     #[error("The radius is too large")]
     RadiusTooLarge,
+    /// The radius is too large for a non-totalistic neighborhood of the given type.
+    ///
+    /// A non-totalistic neighborhood can have at most 64 neighbors.
+    #[error("{neighborhood_type:?} non-totalistic radius must be at most {max_radius}")]
+    NonTotalisticRadiusTooLarge {
+        /// The type of the neighborhood.
+        neighborhood_type: NeighborhoodType,
+        /// The maximum radius allowed for this type.
+        max_radius: u32,
+    },
     /// The neighborhood size is too large.
     #[error("The neighborhood size is too large")]
     NeighborhoodTooLarge,
@@ -20,9 +31,29 @@ pub enum ParseRuleError {
     /// The birth or survival condition is invalid.
     #[error("The birth or survival condition is invalid")]
     InvalidCondition,
+    /// A birth or survival condition in a Life-like or Generations rule string
+    /// has a `0` after another digit.
+    ///
+    /// Each digit is a separate number of neighbors in these notations, so something like
+    /// `B10` is most likely a mistake for a multi-digit number, rather than `B01`.
+    #[error("The birth or survival condition contains a multi-digit number")]
+    AmbiguousCondition,
     /// The number of states is smaller than 2.
     #[error("The number of states is smaller than 2")]
     TooFewStates,
+    /// The rule string looks like a Generations rule string,
+    /// but the number of states is missing.
+    #[error("The number of states is missing")]
+    MissingStates,
+    /// The custom neighborhood is invalid, e.g., it is empty, a neighbor appears twice,
+    /// or only some of the neighbors have weights.
+    #[error("The custom neighborhood is invalid")]
+    InvalidNeighborhood,
+    /// The ranges of birth or survival conditions contain too many conditions.
+    ///
+    /// See [`MAX_CONDITIONS`](crate::MAX_CONDITIONS).
+    #[error("The rule has too many birth or survival conditions")]
+    TooManyConditions,
     /// Integer overflow occurred.
     #[error("Integer overflow occurred")]
     IntegerOverflow,
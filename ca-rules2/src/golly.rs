@@ -0,0 +1,294 @@
+use crate::{Neighborhood, Rule};
+use std::fmt::Write;
+
+/// The neighborhoods supported by Golly's rule tables,
+/// with the coordinates of the neighbors in the order used by the table.
+///
+/// The coordinates are relative to the center cell, with the y axis pointing down.
+const GOLLY_NEIGHBORHOODS: [(&str, &[(i32, i32)]); 3] = [
+    (
+        "Moore",
+        &[
+            (0, -1),
+            (1, -1),
+            (1, 0),
+            (1, 1),
+            (0, 1),
+            (-1, 1),
+            (-1, 0),
+            (-1, -1),
+        ],
+    ),
+    ("vonNeumann", &[(0, -1), (1, 0), (0, 1), (-1, 0)]),
+    (
+        "hexagonal",
+        &[(0, -1), (1, 0), (1, 1), (0, 1), (-1, 0), (-1, -1)],
+    ),
+];
+
+/// The maximum number of states in a Golly rule table.
+const GOLLY_MAX_STATES: u64 = 256;
+
+impl Rule {
+    /// Converts the rule to a rule file in [Golly's rule table format](https://golly.sourceforge.io/Help/formats.html#rule),
+    /// so that it can be simulated in Golly.
+    ///
+    /// The output contains a `@RULE` section, whose name is the canonical rule string with
+    /// characters other than letters, digits, `-` and `_` replaced by `_`, and a `@TABLE` section.
+    ///
+    /// Rule tables only support neighborhoods of radius 1, so the set of neighbors must be
+    /// the Moore, von Neumann, or hexagonal neighborhood of radius 1, in any order.
+    /// In particular, the cross and hash neighborhoods of radius 1 are the same as the von Neumann
+    /// and Moore neighborhoods, respectively. The number of states must be at most 256.
+    /// Otherwise, [`None`] is returned.
+    ///
+    /// Totalistic rules use the `permute` symmetry, with one transition for each number of live
+    /// neighbors. Other rules list the transitions for all combinations of live neighbors.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ca_rules2::Rule;
+    /// let rule: Rule = "B3/S23".parse().unwrap();
+    /// let table = rule.to_golly_table().unwrap();
+    /// assert!(table.starts_with("@RULE B3_S23\n\n@TABLE\nn_states:2\nneighborhood:Moore\n"));
+    /// assert!(table.contains("\n0,1,1,1,0,0,0,0,0,1\n"));
+    /// ```
+    pub fn to_golly_table(&self) -> Option<String> {
+        if self.states > GOLLY_MAX_STATES {
+            return None;
+        }
+
+        let mut coords = self.neighbor_coords();
+        coords.sort_unstable();
+        let (name, positions) = GOLLY_NEIGHBORHOODS.into_iter().find(|(_, positions)| {
+            let mut positions = positions.to_vec();
+            positions.sort_unstable();
+            positions == coords
+        })?;
+
+        let neighbors = self.neighborhood.neighbors().ok()?;
+        let size = positions.len();
+
+        // The state of a live cell that does not survive.
+        let death = if self.states > 2 { 2 } else { 0 };
+
+        // The entry for the `i`-th neighbor when it is not alive.
+        // In a Generations rule, dying cells are not counted as alive.
+        let not_alive = |i: usize| {
+            if self.states > 2 {
+                format!("n{i}")
+            } else {
+                "0".to_string()
+            }
+        };
+
+        let is_totalistic = matches!(
+            self.neighborhood,
+            Neighborhood::Totalistic(_, _) | Neighborhood::CustomTotalistic(_)
+        );
+
+        let rule_name = self
+            .to_string()
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                    c
+                } else {
+                    '_'
+                }
+            })
+            .collect::<String>();
+
+        let mut table = String::new();
+
+        writeln!(table, "@RULE {rule_name}").ok()?;
+        writeln!(table).ok()?;
+        writeln!(table, "@TABLE").ok()?;
+        writeln!(table, "n_states:{}", self.states).ok()?;
+        writeln!(table, "neighborhood:{name}").ok()?;
+        writeln!(
+            table,
+            "symmetries:{}",
+            if is_totalistic { "permute" } else { "none" }
+        )
+        .ok()?;
+
+        if self.states > 2 {
+            // Each neighbor needs its own variable, since a variable that appears
+            // more than once must take the same value each time.
+            let not_alive_states = (0..self.states)
+                .filter(|&state| state != 1)
+                .map(|state| state.to_string())
+                .collect::<Vec<_>>()
+                .join(",");
+            let all_states = (0..self.states)
+                .map(|state| state.to_string())
+                .collect::<Vec<_>>()
+                .join(",");
+
+            for i in 0..size {
+                writeln!(table, "var n{i}={{{not_alive_states}}}").ok()?;
+            }
+            for i in 0..size {
+                writeln!(table, "var a{i}={{{all_states}}}").ok()?;
+            }
+        }
+
+        let mut write_transition = |center: u64, alive: &[bool], next: u64| {
+            let entries = alive
+                .iter()
+                .enumerate()
+                .map(
+                    |(i, &alive)| {
+                        if alive {
+                            "1".to_string()
+                        } else {
+                            not_alive(i)
+                        }
+                    },
+                )
+                .collect::<Vec<_>>()
+                .join(",");
+            writeln!(table, "{center},{entries},{next}")
+        };
+
+        if is_totalistic {
+            for count in 0..=size {
+                let alive = (0..size).map(|i| i < count).collect::<Vec<_>>();
+
+                if self.birth.contains(&(count as u64)) {
+                    write_transition(0, &alive, 1).ok()?;
+                }
+                if !self.survival.contains(&(count as u64)) {
+                    write_transition(1, &alive, death).ok()?;
+                }
+            }
+        } else {
+            for mask in 0..1usize << size {
+                let alive = (0..size).map(|i| mask >> i & 1 == 1).collect::<Vec<_>>();
+
+                let sum = neighbors
+                    .iter()
+                    .filter(|neighbor| {
+                        positions
+                            .iter()
+                            .position(|&coord| coord == neighbor.coord)
+                            .is_some_and(|i| alive[i])
+                    })
+                    .map(|neighbor| neighbor.weight)
+                    .sum::<u64>();
+
+                if self.birth.contains(&sum) {
+                    write_transition(0, &alive, 1).ok()?;
+                }
+                if !self.survival.contains(&sum) {
+                    write_transition(1, &alive, death).ok()?;
+                }
+            }
+        }
+
+        // Dying cells always move on to the next state, whatever their neighbors are.
+        for state in 2..self.states {
+            let entries = (0..size)
+                .map(|i| format!("a{i}"))
+                .collect::<Vec<_>>()
+                .join(",");
+            writeln!(table, "{state},{entries},{}", (state + 1) % self.states).ok()?;
+        }
+
+        Some(table)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Neighbor, NeighborhoodType};
+
+    #[test]
+    fn test_golly_table_life() {
+        let rule: Rule = "B3/S23".parse().unwrap();
+        let table = rule.to_golly_table().unwrap();
+
+        assert_eq!(
+            table,
+            "@RULE B3_S23\n\
+             \n\
+             @TABLE\n\
+             n_states:2\n\
+             neighborhood:Moore\n\
+             symmetries:permute\n\
+             1,0,0,0,0,0,0,0,0,0\n\
+             1,1,0,0,0,0,0,0,0,0\n\
+             0,1,1,1,0,0,0,0,0,1\n\
+             1,1,1,1,1,0,0,0,0,0\n\
+             1,1,1,1,1,1,0,0,0,0\n\
+             1,1,1,1,1,1,1,0,0,0\n\
+             1,1,1,1,1,1,1,1,0,0\n\
+             1,1,1,1,1,1,1,1,1,0\n"
+        );
+
+        // The hash neighborhood of radius 1 is the Moore neighborhood.
+        let hash: Rule = "B3/S23#".parse().unwrap();
+        assert!(hash
+            .to_golly_table()
+            .unwrap()
+            .contains("neighborhood:Moore\n"));
+    }
+
+    #[test]
+    fn test_golly_table_generations() {
+        // Brian's Brain.
+        let rule: Rule = "B2/S/3".parse().unwrap();
+        let table = rule.to_golly_table().unwrap();
+
+        assert!(table.contains("n_states:3\n"));
+        assert!(table.contains("var n0={0,2}\n"));
+        assert!(table.contains("var a7={0,1,2}\n"));
+        assert!(table.contains("\n0,1,1,n2,n3,n4,n5,n6,n7,1\n"));
+        assert!(table.contains("\n1,n0,n1,n2,n3,n4,n5,n6,n7,2\n"));
+        assert!(table.contains("\n2,a0,a1,a2,a3,a4,a5,a6,a7,0\n"));
+    }
+
+    #[test]
+    fn test_golly_table_non_totalistic() {
+        // A cell is born if only its east neighbor is alive,
+        // and survives if only its north neighbor is alive.
+        let rule = Rule {
+            states: 2,
+            neighborhood: Neighborhood::CustomWeighted(vec![
+                Neighbor::new((0, -1), 1),
+                Neighbor::new((1, 0), 2),
+                Neighbor::new((0, 1), 4),
+                Neighbor::new((-1, 0), 8),
+            ]),
+            birth: vec![2],
+            survival: vec![1],
+        };
+        let table = rule.to_golly_table().unwrap();
+
+        assert!(table.contains("neighborhood:vonNeumann\nsymmetries:none\n"));
+        assert!(table.contains("\n0,0,1,0,0,1\n"));
+        assert!(!table.contains("\n1,1,0,0,0,0\n"));
+        assert!(table.contains("\n1,0,0,0,0,0\n"));
+        assert_eq!(
+            table.lines().filter(|line| line.starts_with("1,")).count(),
+            15
+        );
+    }
+
+    #[test]
+    fn test_golly_table_unsupported() {
+        let rule: Rule = "R2,C2,S2-3,B3,NM".parse().unwrap();
+        assert_eq!(rule.to_golly_table(), None);
+
+        let rule = Rule {
+            states: 2,
+            neighborhood: Neighborhood::Totalistic(NeighborhoodType::Moore, 1),
+            birth: vec![3],
+            survival: vec![2, 3],
+        };
+        assert!(rule.to_golly_table().is_some());
+    }
+}
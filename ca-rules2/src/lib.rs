@@ -6,9 +6,10 @@
 #![warn(clippy::nursery)]
 
 mod error;
+mod golly;
 mod parse;
 mod rule;
 
 pub use error::{NeighborError, ParseRuleError};
-pub use parse::{parse_generations, parse_hrot, parse_life_like, parse_rule};
+pub use parse::{parse_generations, parse_hrot, parse_life_like, parse_rule, MAX_CONDITIONS};
 pub use rule::{Neighbor, Neighborhood, NeighborhoodType, Rule};
@@ -1,10 +1,13 @@
-use crate::{Neighborhood, NeighborhoodType, ParseRuleError, Rule};
+use crate::{Neighbor, Neighborhood, NeighborhoodType, ParseRuleError, Rule};
 use std::{
     num::ParseIntError,
     ops::{Range, RangeInclusive},
     str,
 };
 
+/// A neighbor in a custom neighborhood, with an optional weight.
+type CustomNeighbor = ((i32, i32), Option<u64>);
+
 /// A pattern for matching a single character represented as a byte.
 trait CharPattern {
     /// Returns `true` if the given character matches this pattern.
@@ -50,6 +53,59 @@ where
     }
 }
 
+/// Whether a sequence of digits has a `0` right after another digit.
+///
+/// A `0` is always written first in a Life-like or Generations condition,
+/// so a `0` after another digit, as in `B10`, can only be part of a multi-digit number.
+fn has_multi_digit_number(digits: &[u64]) -> bool {
+    digits.windows(2).any(|pair| pair[0] > 0 && pair[1] == 0)
+}
+
+/// Check that the birth and survival conditions of a Life-like or Generations rule string
+/// do not contain a multi-digit number, which would otherwise be read as several digits.
+fn check_digits(birth: &[u64], survival: &[u64]) -> Result<(), ParseRuleError> {
+    if has_multi_digit_number(birth) || has_multi_digit_number(survival) {
+        Err(ParseRuleError::AmbiguousCondition)
+    } else {
+        Ok(())
+    }
+}
+
+/// The maximum number of birth or survival conditions that the ranges in a HROT rule string
+/// can expand to.
+///
+/// A range such as `S0-4000000000` is valid for a large neighborhood, but listing all its
+/// conditions would exhaust the memory. So such a rule string is rejected with
+/// [`TooManyConditions`](ParseRuleError::TooManyConditions) instead.
+pub const MAX_CONDITIONS: u64 = 1 << 20;
+
+/// Expand a list of ranges of birth or survival conditions into a list of conditions.
+///
+/// The ranges are checked before they are expanded: a nonempty range must not exceed
+/// `max_condition`, and the ranges must not expand to more than [`MAX_CONDITIONS`] conditions.
+fn collect_conditions(
+    ranges: impl IntoIterator<Item = RangeInclusive<u64>>,
+    max_condition: u64,
+) -> Result<Vec<u64>, ParseRuleError> {
+    let ranges = ranges
+        .into_iter()
+        .filter(|range| !range.is_empty())
+        .collect::<Vec<_>>();
+
+    if ranges.iter().any(|range| *range.end() > max_condition) {
+        return Err(ParseRuleError::InvalidCondition);
+    }
+
+    let count = ranges.iter().fold(0u64, |count, range| {
+        count.saturating_add((range.end() - range.start()).saturating_add(1))
+    });
+    if count > MAX_CONDITIONS {
+        return Err(ParseRuleError::TooManyConditions);
+    }
+
+    Ok(ranges.into_iter().flatten().collect())
+}
+
 /// A helper struct for parsing rule strings.
 ///
 /// Inspired by the parser for [`IpAddr`](std::net::IpAddr) in Rust's standard
@@ -112,7 +168,7 @@ impl<'a> Parser<'a> {
     }
 
     /// Peek at the next character without consuming it.
-    fn peek(&self) -> Option<u8> {
+    const fn peek(&self) -> Option<u8> {
         self.input.first().copied()
     }
 
@@ -172,11 +228,25 @@ impl<'a> Parser<'a> {
         (!digits.is_empty()).then(|| str::from_utf8(digits).unwrap().parse())
     }
 
+    /// The result of parsing a B/S/C or S/B/C rule string
+    /// when there is no number of states after the last slash.
+    ///
+    /// If the rule string ends right after the slash, it is most likely a Generations
+    /// rule string with the number of states omitted, rather than a Life-like rule string
+    /// with a trailing slash. Report this instead of a generic syntax error.
+    fn missing_states(&self) -> Option<Result<Rule, ParseRuleError>> {
+        self.peek()
+            .is_none()
+            .then_some(Err(ParseRuleError::MissingStates))
+    }
+
     /// Parse a neighborhood type for a Life-like rule string.
     fn parse_neighborhood_type_life_like(&mut self) -> Option<NeighborhoodType> {
         match self.read() {
             Some(b'V' | b'v') => Some(NeighborhoodType::VonNeumann),
             Some(b'H' | b'h') => Some(NeighborhoodType::Hexagonal),
+            Some(b'+') => Some(NeighborhoodType::Cross),
+            Some(b'#') => Some(NeighborhoodType::Hash),
             None => Some(NeighborhoodType::Moore),
             _ => None,
         }
@@ -194,6 +264,93 @@ impl<'a> Parser<'a> {
         }
     }
 
+    /// Try to read an optional minus sign followed by one or more digits,
+    /// and parse them as an `i32`.
+    fn parse_signed_number(&mut self) -> Option<Result<i32, ParseIntError>> {
+        let input = self.input;
+        self.read_matches(b'-');
+        let digits = self.read_matches_many(b'0'..=b'9');
+        (!digits.is_empty()).then(|| {
+            let len = input.len() - self.input.len();
+            str::from_utf8(&input[..len]).unwrap().parse()
+        })
+    }
+
+    /// Parse a neighbor in a custom neighborhood, in the form `({x},{y})` or `({x},{y})*{weight}`.
+    ///
+    /// The coordinates may be negative.
+    fn parse_neighbor(&mut self) -> Option<Result<CustomNeighbor, ParseIntError>> {
+        self.read_matches(b'(')?;
+        let x = self.parse_signed_number()?;
+        self.read_matches(b',')?;
+        let y = self.parse_signed_number()?;
+        self.read_matches(b')')?;
+        let weight = self.try_parse(|parser| {
+            parser.read_matches(b'*')?;
+            parser.parse_number()
+        });
+
+        Some(match (x, y, weight.transpose()) {
+            (Ok(x), Ok(y), Ok(weight)) => Ok(((x, y), weight)),
+            (Err(err), _, _) | (_, Err(err), _) | (_, _, Err(err)) => Err(err),
+        })
+    }
+
+    /// Parse a custom neighborhood for a HROT rule string, in the form `[{neighbors}]`
+    /// or `![{neighbors}]`.
+    ///
+    /// See [`parse_hrot`] for more details.
+    fn parse_neighborhood_custom(&mut self) -> Option<Result<Neighborhood, ParseRuleError>> {
+        let is_totalistic = self.read_matches(b'!').is_none();
+        self.read_matches(b'[')?;
+        let neighbors = self.parse_many_sep(b',', Parser::parse_neighbor);
+        self.read_matches(b']')?;
+
+        let mut coords = Vec::new();
+        let mut weights = Vec::new();
+        for neighbor in neighbors {
+            match neighbor {
+                Ok((coord, weight)) => {
+                    coords.push(coord);
+                    weights.push(weight);
+                }
+                Err(_) => return Some(Err(ParseRuleError::IntegerOverflow)),
+            }
+        }
+
+        if coords.is_empty() {
+            return Some(Err(ParseRuleError::InvalidNeighborhood));
+        }
+
+        for (i, coord) in coords.iter().enumerate() {
+            if coords[..i].contains(coord) {
+                return Some(Err(ParseRuleError::InvalidNeighborhood));
+            }
+        }
+
+        let neighborhood = if weights.iter().all(Option::is_none) {
+            if is_totalistic {
+                Neighborhood::CustomTotalistic(coords)
+            } else if coords.len() > 64 {
+                return Some(Err(ParseRuleError::InvalidNeighborhood));
+            } else {
+                Neighborhood::CustomNontotalistic(coords)
+            }
+        } else if is_totalistic && weights.iter().all(Option::is_some) {
+            Neighborhood::CustomWeighted(
+                coords
+                    .into_iter()
+                    .zip(weights)
+                    .map(|(coord, weight)| Neighbor::new(coord, weight.unwrap()))
+                    .collect(),
+            )
+        } else {
+            return Some(Err(ParseRuleError::InvalidNeighborhood));
+        };
+
+        Some(Ok(neighborhood))
+    }
+
     /// Parse a single number or a range in the form `{min}-{max}`.
     ///
     /// If it is a single number, it is converted to a range with the same
@@ -241,6 +398,11 @@ impl<'a> Parser<'a> {
             return None;
         }
 
+        // Check that a multi-digit number is not silently read as several digits.
+        if let Err(e) = check_digits(&birth, &survival) {
+            return Some(Err(e));
+        }
+
         // Check that the birth and survival conditions are valid.
         let rule = Rule {
             states: 2,
@@ -279,6 +441,11 @@ impl<'a> Parser<'a> {
             return None;
         }
 
+        // Check that a multi-digit number is not silently read as several digits.
+        if let Err(e) = check_digits(&birth, &survival) {
+            return Some(Err(e));
+        }
+
         // Check that the birth and survival conditions are valid.
         let rule = Rule {
             states: 2,
@@ -328,7 +495,9 @@ impl<'a> Parser<'a> {
         self.read_matches(b'/')?;
 
         // Parse the number of states.
-        let states = self.parse_number()?;
+        let Some(states) = self.parse_number() else {
+            return self.missing_states();
+        };
 
         // Parse the neighborhood type.
         let neighborhood_type = self.parse_neighborhood_type_life_like()?;
@@ -339,6 +508,11 @@ impl<'a> Parser<'a> {
             return None;
         }
 
+        // Check that a multi-digit number is not silently read as several digits.
+        if let Err(e) = check_digits(&birth, &survival) {
+            return Some(Err(e));
+        }
+
         // Check that the number of states is valid.
         if states.is_err() {
             return Some(Err(ParseRuleError::IntegerOverflow));
@@ -383,7 +557,9 @@ impl<'a> Parser<'a> {
         self.read_matches(b'/')?;
 
         // Parse the number of states.
-        let states = self.parse_number()?;
+        let Some(states) = self.parse_number() else {
+            return self.missing_states();
+        };
 
         // Parse the neighborhood type.
         let neighborhood_type = self.parse_neighborhood_type_life_like()?;
@@ -394,6 +570,11 @@ impl<'a> Parser<'a> {
             return None;
         }
 
+        // Check that a multi-digit number is not silently read as several digits.
+        if let Err(e) = check_digits(&birth, &survival) {
+            return Some(Err(e));
+        }
+
         // Check that the number of states is valid.
         if states.is_err() {
             return Some(Err(ParseRuleError::IntegerOverflow));
@@ -446,6 +627,11 @@ impl<'a> Parser<'a> {
             return None;
         }
 
+        // Check that a multi-digit number is not silently read as several digits.
+        if let Err(e) = check_digits(&birth, &survival) {
+            return Some(Err(e));
+        }
+
         // Check that the number of states is valid.
         if states.is_err() {
             return Some(Err(ParseRuleError::IntegerOverflow));
@@ -572,8 +758,15 @@ impl<'a> Parser<'a> {
             smax -= 1;
         }
 
-        let survival = (smin..=smax).collect();
-        let birth = (bmin..=bmax).collect();
+        let max_condition = neighborhood.max_condition();
+        let survival = match collect_conditions([smin..=smax], max_condition) {
+            Ok(survival) => survival,
+            Err(err) => return Some(Err(err)),
+        };
+        let birth = match collect_conditions([bmin..=bmax], max_condition) {
+            Ok(birth) => birth,
+            Err(err) => return Some(Err(err)),
+        };
         let rule = Rule {
             states,
             neighborhood,
@@ -645,8 +838,15 @@ impl<'a> Parser<'a> {
         smin -= 1;
         smax -= 1;
 
-        let survival = (smin..=smax).collect();
-        let birth = (bmin..=bmax).collect();
+        let max_condition = neighborhood.max_condition();
+        let survival = match collect_conditions([smin..=smax], max_condition) {
+            Ok(survival) => survival,
+            Err(err) => return Some(Err(err)),
+        };
+        let birth = match collect_conditions([bmin..=bmax], max_condition) {
+            Ok(birth) => birth,
+            Err(err) => return Some(Err(err)),
+        };
         let rule = Rule {
             states: 2,
             neighborhood,
@@ -693,12 +893,16 @@ impl<'a> Parser<'a> {
         self.read_matches(b"Bb")?;
         let birth_list = self.parse_many_sep(b',', Parser::parse_range);
 
-        // Parse the comma and the neighborhood type. This is optional.
-        let neighborhood_type = if self.read_matches(b",").is_some() {
+        // Parse the comma and the neighborhood, which is either a neighborhood type
+        // or a list of neighbors. This is optional.
+        let (neighborhood_type, custom) = if self.read_matches(b",").is_some() {
             self.read_matches(b"Nn")?;
-            self.parse_neighborhood_type_hrot()?
+            match self.try_parse(Parser::parse_neighborhood_custom) {
+                Some(custom) => (NeighborhoodType::Moore, Some(custom)),
+                None => (self.parse_neighborhood_type_hrot()?, None),
+            }
         } else {
-            NeighborhoodType::Moore
+            (NeighborhoodType::Moore, None)
         };
 
         // Check that there is no more input.
@@ -715,7 +919,12 @@ impl<'a> Parser<'a> {
             return Some(Err(ParseRuleError::IntegerOverflow));
         }
 
-        let neighborhood = Neighborhood::Totalistic(neighborhood_type, radius as u32);
+        // The radius is ignored for custom neighborhoods.
+        let neighborhood = match custom {
+            Some(Ok(neighborhood)) => neighborhood,
+            Some(Err(err)) => return Some(Err(err)),
+            None => Neighborhood::Totalistic(neighborhood_type, radius as u32),
+        };
 
         // Check that the number of states is valid.
         if states.is_err() {
@@ -724,21 +933,23 @@ impl<'a> Parser<'a> {
         let states = states.unwrap().max(2);
 
         // Check that the birth and survival conditions are valid.
-        let mut survival = Vec::new();
-        for range in survival_list {
-            match range {
-                Ok(range) => survival.extend(range),
-                Err(_) => return Some(Err(ParseRuleError::IntegerOverflow)),
-            }
-        }
+        let max_condition = neighborhood.max_condition();
+        let collect = |list: Vec<Result<RangeInclusive<u64>, ParseIntError>>| {
+            let ranges = list
+                .into_iter()
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|_| ParseRuleError::IntegerOverflow)?;
+            collect_conditions(ranges, max_condition)
+        };
 
-        let mut birth = Vec::new();
-        for range in birth_list {
-            match range {
-                Ok(range) => birth.extend(range),
-                Err(_) => return Some(Err(ParseRuleError::IntegerOverflow)),
-            }
-        }
+        let survival = match collect(survival_list) {
+            Ok(survival) => survival,
+            Err(err) => return Some(Err(err)),
+        };
+        let birth = match collect(birth_list) {
+            Ok(birth) => birth,
+            Err(err) => return Some(Err(err)),
+        };
 
         let rule = Rule {
             states,
@@ -795,7 +1006,9 @@ impl<'a> Parser<'a> {
 /// - `{survival}` is a sequence of digits. These are the numbers of neighbors
 ///   that cause a live cell to survive.
 ///
-/// These sequences may be empty.
+/// These sequences may be empty. They must not contain a `0` after another digit.
+/// Otherwise an [`AmbiguousCondition`](ParseRuleError::AmbiguousCondition)
+/// error is returned, so that e.g. `B10/S23` is not silently read as `B01/S23`.
 ///
 /// # S/B notation
 ///
@@ -814,10 +1027,11 @@ impl<'a> Parser<'a> {
 ///
 /// # Suffixes
 ///
-/// The rule string may optionally have a suffix `V` or `H` to indicate the
-/// neighborhood type. `V` means the von Neumann neighborhood, and `H` means
-/// the hexagonal neighborhood. If there is no suffix, the Moore neighborhood is
-/// assumed. All three neighborhood types have a radius of 1.
+/// The rule string may optionally have a suffix `V`, `H`, `+` or `#` to indicate
+/// the neighborhood type. `V` means the von Neumann neighborhood, `H` means
+/// the hexagonal neighborhood, `+` means the cross neighborhood, and `#` means
+/// the hash neighborhood. If there is no suffix, the Moore neighborhood is
+/// assumed. All these neighborhood types have a radius of 1.
 ///
 /// See [`NeighborhoodType`](crate::NeighborhoodType) for more information.
 pub fn parse_life_like(rule_string: &str) -> Result<Rule, ParseRuleError> {
@@ -848,6 +1062,9 @@ pub fn parse_life_like(rule_string: &str) -> Result<Rule, ParseRuleError> {
 /// - `{states}` is the number of states in the cellular automaton. It must be
 ///   greater than 1.
 ///
+/// As in [`parse_life_like`], the sequences of digits must not contain a `0`
+/// after another digit.
+///
 /// # S/B/C notation
 ///
 /// The rule string is in the form `{survival}/{birth}/{states}`, where
@@ -864,10 +1081,11 @@ pub fn parse_life_like(rule_string: &str) -> Result<Rule, ParseRuleError> {
 ///
 /// # Suffixes
 ///
-/// The rule string may optionally have a suffix `V` or `H` to indicate the
-/// neighborhood type. `V` means the von Neumann neighborhood, and `H` means
-/// the hexagonal neighborhood. If there is no suffix, the Moore neighborhood is
-/// assumed. All three neighborhood types have a radius of 1.
+/// The rule string may optionally have a suffix `V`, `H`, `+` or `#` to indicate
+/// the neighborhood type, the same as for [`parse_life_like`]. `V` means the von Neumann
+/// neighborhood, `H` means the hexagonal neighborhood, `+` means the cross neighborhood,
+/// and `#` means the hash neighborhood. If there is no suffix, the Moore neighborhood is
+/// assumed. All these neighborhood types have a radius of 1.
 ///
 /// See [`NeighborhoodType`](crate::NeighborhoodType) for more information.
 pub fn parse_generations(rule_string: &str) -> Result<Rule, ParseRuleError> {
@@ -932,6 +1150,32 @@ pub fn parse_generations(rule_string: &str) -> Result<Rule, ParseRuleError> {
 ///   is either a single number, or a range in the form `{min}-{max}`.
 /// - `{neighborhood}` is the same as in the LtL notation, except that it may
 ///   be omitted. If it is omitted, the Moore neighborhood is assumed.
+///   It may also be a custom neighborhood, see below.
+///
+/// # Custom neighborhoods
+///
+/// In the HROT notation, `{neighborhood}` may be a list of neighbors in square brackets,
+/// separated by commas. Each neighbor is given by its coordinates `({x},{y})` relative to
+/// the center cell, which may be negative. The radius is then ignored. This is the same
+/// format as the one used by the [`Display`](std::fmt::Display) implementation of
+/// [`Rule`](crate::Rule). Every rule it writes can be parsed back, but a rule with a
+/// [`Nontotalistic`](Neighborhood::Nontotalistic) neighborhood is parsed back as a
+/// [`CustomNontotalistic`](Neighborhood::CustomNontotalistic) one with the same neighbors.
+///
+/// - `[({x},{y}),...]` is a custom totalistic neighborhood,
+///   see [`CustomTotalistic`](Neighborhood::CustomTotalistic).
+/// - `![({x},{y}),...]` is a custom non-totalistic neighborhood,
+///   see [`CustomNontotalistic`](Neighborhood::CustomNontotalistic).
+///   It can have at most 64 neighbors.
+/// - `[({x},{y})*{weight},...]` is a custom weighted neighborhood,
+///   see [`CustomWeighted`](Neighborhood::CustomWeighted).
+///   Either all the neighbors or none of them must have a weight.
+///
+/// A neighbor must not appear twice. Otherwise an
+/// [`InvalidNeighborhood`](ParseRuleError::InvalidNeighborhood) error is returned.
+///
+/// For example, `R2,C2,S2,B3,N[(-2,0),(-1,0),(1,0),(2,0),(0,-2),(0,-1),(0,1),(0,2)]`
+/// is the same rule as `R2,C2,S2,B3,N+`.
 pub fn parse_hrot(rule_string: &str) -> Result<Rule, ParseRuleError> {
     let mut parser = Parser::new(rule_string);
 
@@ -950,9 +1194,19 @@ pub fn parse_hrot(rule_string: &str) -> Result<Rule, ParseRuleError> {
 ///
 /// See the documentation of each function for more details.
 ///
+/// Surrounding ASCII whitespace is ignored, and the rule string may be enclosed
+/// in a single pair of square brackets, e.g. `[B3/S23]`. This makes it easier to
+/// parse rule strings that are copied and pasted from elsewhere.
+///
 /// This function is also used in the [`FromStr`](std::str::FromStr) implementation
 /// for [`Rule`](crate::Rule).
 pub fn parse_rule(rule_string: &str) -> Result<Rule, ParseRuleError> {
+    let rule_string = rule_string.trim_ascii();
+    let rule_string = rule_string
+        .strip_prefix('[')
+        .and_then(|s| s.strip_suffix(']'))
+        .map_or(rule_string, str::trim_ascii);
+
     let mut parser = Parser::new(rule_string);
 
     parser
@@ -1015,6 +1269,26 @@ mod tests {
                 survival: vec![3],
             }
         );
+
+        assert_eq!(
+            parse_life_like("B2/S+").unwrap(),
+            Rule {
+                states: 2,
+                neighborhood: Neighborhood::Totalistic(NeighborhoodType::Cross, 1),
+                birth: vec![2],
+                survival: vec![],
+            }
+        );
+
+        assert_eq!(
+            parse_life_like("B3/S23#").unwrap(),
+            Rule {
+                states: 2,
+                neighborhood: Neighborhood::Totalistic(NeighborhoodType::Hash, 1),
+                birth: vec![3],
+                survival: vec![2, 3],
+            }
+        );
     }
 
     #[test]
@@ -1174,6 +1448,26 @@ mod tests {
                 survival: vec![3],
             }
         );
+
+        assert_eq!(
+            parse_generations("B2/S34/3+").unwrap(),
+            Rule {
+                states: 3,
+                neighborhood: Neighborhood::Totalistic(NeighborhoodType::Cross, 1),
+                birth: vec![2],
+                survival: vec![3, 4],
+            }
+        );
+
+        assert_eq!(
+            parse_generations("B3/S23/4#").unwrap(),
+            Rule {
+                states: 4,
+                neighborhood: Neighborhood::Totalistic(NeighborhoodType::Hash, 1),
+                birth: vec![3],
+                survival: vec![2, 3],
+            }
+        );
     }
 
     #[test]
@@ -1227,6 +1521,44 @@ mod tests {
                 survival: vec![3],
             }
         );
+
+        assert_eq!(
+            parse_generations("34/2/3+").unwrap(),
+            Rule {
+                states: 3,
+                neighborhood: Neighborhood::Totalistic(NeighborhoodType::Cross, 1),
+                birth: vec![2],
+                survival: vec![3, 4],
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_rule_slash_disambiguation() {
+        // One slash: Life-like.
+        assert_eq!(parse_rule("23/3").unwrap().states, 2);
+        assert_eq!(parse_rule("B3/S23").unwrap().states, 2);
+
+        // Two slashes: Generations.
+        assert_eq!(parse_rule("23/3/2").unwrap(), parse_rule("23/3").unwrap());
+        assert_eq!(parse_rule("23/3/4").unwrap().states, 4);
+        assert_eq!(parse_rule("B3/S23/4").unwrap().states, 4);
+
+        // A trailing slash: Generations with the number of states omitted.
+        assert!(matches!(
+            parse_rule("23/3/"),
+            Err(ParseRuleError::MissingStates)
+        ));
+        assert!(matches!(
+            parse_rule("B3/S23/"),
+            Err(ParseRuleError::MissingStates)
+        ));
+
+        // Something else after the second slash.
+        assert!(matches!(
+            parse_rule("23/3/x"),
+            Err(ParseRuleError::InvalidSyntax)
+        ));
     }
 
     #[test]
@@ -1280,6 +1612,16 @@ mod tests {
                 survival: vec![3],
             }
         );
+
+        assert_eq!(
+            parse_generations("g3b2s34#").unwrap(),
+            Rule {
+                states: 3,
+                neighborhood: Neighborhood::Totalistic(NeighborhoodType::Hash, 1),
+                birth: vec![2],
+                survival: vec![3, 4],
+            }
+        );
     }
 
     #[test]
@@ -1439,4 +1781,173 @@ mod tests {
             }
         );
     }
+
+    #[test]
+    fn test_parse_hrot_custom() {
+        let rule = parse_rule("R2,C2,S2,B3,N[(-2,0),(-1,0),(1,0),(2,0),(0,-2),(0,-1),(0,1),(0,2)]")
+            .unwrap();
+        assert_eq!(
+            rule.neighborhood,
+            Neighborhood::CustomTotalistic(vec![
+                (-2, 0),
+                (-1, 0),
+                (1, 0),
+                (2, 0),
+                (0, -2),
+                (0, -1),
+                (0, 1),
+                (0, 2),
+            ])
+        );
+        assert_eq!(rule.birth, vec![3]);
+        assert_eq!(rule.survival, vec![2]);
+
+        let rule = parse_rule("R1,C2,S1,B2-3,N![(0,-1),(1,0)]").unwrap();
+        assert_eq!(
+            rule.neighborhood,
+            Neighborhood::CustomNontotalistic(vec![(0, -1), (1, 0)])
+        );
+
+        let rule = parse_rule("R1,C3,S1,B2,n[(0,-1)*1,(1,0)*2]").unwrap();
+        assert_eq!(
+            rule.neighborhood,
+            Neighborhood::CustomWeighted(vec![Neighbor::new((0, -1), 1), Neighbor::new((1, 0), 2)])
+        );
+        assert_eq!(rule.states, 3);
+
+        // The display format can be parsed back.
+        for rule in [
+            "R3,C2,S2,B3,N[(-3,0),(3,0),(0,-3),(0,3)]",
+            "R1,C2,S1,B2,N[(0,-1)*1,(1,0)*2]",
+        ] {
+            let rule = parse_rule(rule).unwrap();
+            assert_eq!(parse_rule(&rule.to_string()).unwrap(), rule);
+        }
+
+        // No neighbors, duplicate neighbors, mixed weights, and too many non-totalistic neighbors.
+        for rule in [
+            "R1,C2,S,B,N[]",
+            "R1,C2,S,B,N![]",
+            "R1,C2,S2,B3,N[(0,1),(0,1)]",
+            "R1,C2,S2,B3,N[(0,1)*1,(1,0)]",
+            "R1,C2,S2,B3,N![(0,1)*1]",
+        ] {
+            assert!(matches!(
+                parse_rule(rule),
+                Err(ParseRuleError::InvalidNeighborhood)
+            ));
+        }
+        let many = (0..65)
+            .map(|x| format!("({x},1)"))
+            .collect::<Vec<_>>()
+            .join(",");
+        assert!(matches!(
+            parse_rule(&format!("R1,C2,S1,B1,N![{many}]")),
+            Err(ParseRuleError::InvalidNeighborhood)
+        ));
+
+        assert!(matches!(
+            parse_rule("R1,C2,S2,B3,N[(0,99999999999)]"),
+            Err(ParseRuleError::IntegerOverflow)
+        ));
+        assert!(matches!(
+            parse_rule("R1,C2,S2,B3,N[(0,1)"),
+            Err(ParseRuleError::InvalidSyntax)
+        ));
+    }
+
+    #[test]
+    fn test_parse_ambiguous_condition() {
+        // `B10` must not be read as `B01`, which would be a B0 rule.
+        for rule_string in [
+            "B10/S23",
+            "B3/S20",
+            "23/10",
+            "b10s23",
+            "B10/S23/3",
+            "g3b10s23",
+        ] {
+            assert!(
+                matches!(
+                    parse_rule(rule_string),
+                    Err(ParseRuleError::AmbiguousCondition)
+                ),
+                "{rule_string:?}"
+            );
+        }
+
+        assert!(parse_rule("B01/S23").unwrap().contains_b0());
+
+        // Other unordered digits are not ambiguous.
+        for rule_string in ["B3/S32", "B33/S23", "32/3"] {
+            assert_eq!(parse_rule(rule_string).unwrap().to_string(), "B3/S23");
+        }
+    }
+
+    #[test]
+    fn test_parse_rule_trim() {
+        let rule = parse_rule("B3/S23").unwrap();
+
+        for rule_string in [
+            " B3/S23 ",
+            "B3/S23\n",
+            "\tB3/S23\r\n",
+            "[B3/S23]",
+            " [ B3/S23 ]\n",
+        ] {
+            assert_eq!(parse_rule(rule_string).unwrap(), rule, "{rule_string:?}");
+        }
+
+        assert_eq!(
+            parse_rule("[R3,C2,S2,B3,N+]").unwrap(),
+            parse_rule("R3,C2,S2,B3,N+").unwrap()
+        );
+
+        // Only a single pair of brackets is stripped, and whitespace inside the rule is not allowed.
+        assert!(parse_rule("[[B3/S23]]").is_err());
+        assert!(parse_rule("[B3/S23").is_err());
+        assert!(parse_rule("B3 /S23").is_err());
+    }
+
+    #[test]
+    fn test_parse_rule_no_panic() {
+        // A radius whose neighborhood size does not fit in a `u32`.
+        let rule = parse_rule("R100000,C0,M0,S1..1,B1..1,NM").unwrap();
+        assert_eq!(rule.neighborhood_size(), 40_000_400_000);
+
+        // A range that is valid, but too large to list.
+        assert!(matches!(
+            parse_rule("R70000,C2,S1,B3-2147483648,NM"),
+            Err(ParseRuleError::TooManyConditions)
+        ));
+        assert!(matches!(
+            parse_rule("R5,C0,M0,S0..18446744073709551615,B1..2,NM"),
+            Err(ParseRuleError::InvalidCondition)
+        ));
+
+        // Extreme coordinates and weights.
+        let rule = parse_rule("R1,C2,S1,B1,N![(-2147483648,0)]").unwrap();
+        assert_eq!(rule.radius(), 2_147_483_648);
+        let rule = parse_rule("R1,C2,S1,B1,N[(0,1)*18446744073709551615,(1,0)*1]").unwrap();
+        assert_eq!(rule.max_condition(), u64::MAX);
+
+        // A non-totalistic neighborhood with 64 neighbors.
+        let neighbors = (0..64)
+            .map(|i| format!("({},{})", i % 8 + 1, i / 8))
+            .collect::<Vec<_>>()
+            .join(",");
+        let rule = parse_rule(&format!("R1,C2,S1,B1,N![{neighbors}]")).unwrap();
+        assert_eq!(rule.max_condition(), u64::MAX);
+
+        for rule_string in [
+            "R,C,S..",
+            "R1,C2,S1-,B3",
+            "B3/S23/",
+            "g",
+            "R1,C2,S1,B1,N[(",
+            "Bé/S23",
+        ] {
+            assert!(parse_rule(rule_string).is_err(), "{rule_string:?}");
+        }
+    }
 }
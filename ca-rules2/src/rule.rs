@@ -1,5 +1,9 @@
 use crate::{parse_rule, NeighborError, ParseRuleError};
-use std::str::FromStr;
+use std::{
+    collections::HashSet,
+    fmt::{self, Display, Formatter},
+    str::FromStr,
+};
 
 /// The coordinates of a neighbor and its weight.
 ///
@@ -67,6 +71,7 @@ impl Neighbor {
     /// This means that the maximum radius allowed is `3` for [`Moore`](NeighborhoodType::Moore),
     /// `5` for [`VonNeumann`](NeighborhoodType::VonNeumann),
     /// `16` for [`Cross`](NeighborhoodType::Cross),
+    /// `8` for [`Hash`](NeighborhoodType::Hash),
     /// and `4` for [`Hexagonal`](NeighborhoodType::Hexagonal).
     ///
     /// When `is_totalistic` is `true`, the radius should be at most [`i32::MAX`].
@@ -163,14 +168,38 @@ impl NeighborhoodType {
     /// - `4 * radius` for [`Cross`](NeighborhoodType::Cross),
     /// - `8 * radius` for [`Hash`](NeighborhoodType::Hash),
     /// - `3 * radius * (radius + 1)` for [`Hexagonal`](NeighborhoodType::Hexagonal).
+    ///
+    /// The result saturates at [`usize::MAX`] instead of overflowing, so that a huge radius
+    /// from a rule string is reported as an invalid condition instead of a panic.
     pub const fn size(self, radius: u32) -> usize {
-        (match self {
-            Self::Moore => 4 * radius * (radius + 1),
-            Self::VonNeumann => 2 * radius * (radius + 1),
-            Self::Cross => 4 * radius,
-            Self::Hash => 8 * radius,
-            Self::Hexagonal => 3 * radius * (radius + 1),
-        }) as usize
+        let radius = radius as usize;
+        let (factor, area) = match self {
+            Self::Moore => (4, radius.saturating_mul(radius + 1)),
+            Self::VonNeumann => (2, radius.saturating_mul(radius + 1)),
+            Self::Cross => (4, radius),
+            Self::Hash => (8, radius),
+            Self::Hexagonal => (3, radius.saturating_mul(radius + 1)),
+        };
+        area.saturating_mul(factor)
+    }
+
+    /// Gets the maximum radius allowed for a non-totalistic neighborhood of this type,
+    /// i.e., the largest radius such that the neighborhood has at most 64 neighbors.
+    ///
+    /// The maximum radius is:
+    /// - `3` for [`Moore`](NeighborhoodType::Moore),
+    /// - `5` for [`VonNeumann`](NeighborhoodType::VonNeumann),
+    /// - `16` for [`Cross`](NeighborhoodType::Cross),
+    /// - `8` for [`Hash`](NeighborhoodType::Hash),
+    /// - `4` for [`Hexagonal`](NeighborhoodType::Hexagonal).
+    pub const fn max_non_totalistic_radius(self) -> u32 {
+        match self {
+            Self::Moore => 3,
+            Self::VonNeumann => 5,
+            Self::Cross => 16,
+            Self::Hash => 8,
+            Self::Hexagonal => 4,
+        }
     }
 
     /// Gets a list of coordinates from a neighborhood type and a radius.
@@ -253,6 +282,7 @@ impl NeighborhoodType {
     /// This means that the maximum radius allowed is `3` for [`Moore`](NeighborhoodType::Moore),
     /// `5` for [`VonNeumann`](NeighborhoodType::VonNeumann),
     /// `16` for [`Cross`](NeighborhoodType::Cross),
+    /// `8` for [`Hash`](NeighborhoodType::Hash),
     /// and `4` for [`Hexagonal`](NeighborhoodType::Hexagonal).
     ///
     /// When `is_totalistic` is `true`, the radius should be at most [`i32::MAX`].
@@ -265,10 +295,13 @@ impl NeighborhoodType {
             return Err(NeighborError::RadiusTooLarge);
         }
 
-        let size = self.size(radius);
+        let max_radius = self.max_non_totalistic_radius();
 
-        if !is_totalistic && size > 64 {
-            return Err(NeighborError::RadiusTooLarge);
+        if !is_totalistic && radius > max_radius {
+            return Err(NeighborError::NonTotalisticRadiusTooLarge {
+                neighborhood_type: self,
+                max_radius,
+            });
         }
 
         let coords = self.neighbor_coords(radius);
@@ -350,6 +383,7 @@ impl Neighborhood {
     /// This means that the maximum radius allowed is `3` for [`Moore`](NeighborhoodType::Moore),
     /// `5` for [`VonNeumann`](NeighborhoodType::VonNeumann),
     /// `16` for [`Cross`](NeighborhoodType::Cross),
+    /// `8` for [`Hash`](NeighborhoodType::Hash),
     /// and `4` for [`Hexagonal`](NeighborhoodType::Hexagonal).
     ///
     /// When the neighborhood is totalistic, the radius should be at most [`i32::MAX`].
@@ -402,8 +436,54 @@ impl Neighborhood {
         )
     }
 
+    /// Whether the neighborhood is weighted.
+    pub const fn is_weighted(&self) -> bool {
+        matches!(self, Self::CustomWeighted(_))
+    }
+
+    /// Whether the neighborhood is outer-totalistic, i.e., totalistic,
+    /// and the center cell is not one of its own neighbors.
+    ///
+    /// Predefined totalistic neighborhoods are always outer-totalistic.
+    pub fn is_outer_totalistic(&self) -> bool {
+        match self {
+            Self::Totalistic(_, _) => true,
+            Self::CustomTotalistic(coords) => !coords.contains(&(0, 0)),
+            _ => false,
+        }
+    }
+
+    /// Whether the neighborhood is invariant under all the rotations and reflections
+    /// of the square grid.
+    ///
+    /// For weighted neighborhoods, each neighbor must be mapped to a neighbor with the same weight.
+    /// For other neighborhoods, only the set of neighbors is checked. In particular,
+    /// for non-totalistic neighborhoods, this does not check the birth and survival conditions.
+    ///
+    /// The hexagonal neighborhood is not isotropic in this sense,
+    /// since it is only symmetric on the hexagonal grid.
+    pub fn is_isotropic(&self) -> bool {
+        let neighbors = match self {
+            Self::CustomWeighted(neighbors) => neighbors
+                .iter()
+                .map(|neighbor| (neighbor.coord, neighbor.weight))
+                .collect::<HashSet<_>>(),
+            _ => self
+                .neighbor_coords()
+                .into_iter()
+                .map(|coord| (coord, 1))
+                .collect(),
+        };
+
+        // The reflection along the y axis and the reflection along the diagonal
+        // generate all the rotations and reflections.
+        neighbors.iter().all(|&((x, y), weight)| {
+            neighbors.contains(&((-x, y), weight)) && neighbors.contains(&((y, x), weight))
+        })
+    }
+
     /// Number of neighbors.
-    pub fn size(&self) -> usize {
+    pub const fn size(&self) -> usize {
         match self {
             Self::Totalistic(neighborhood_type, radius)
             | Self::Nontotalistic(neighborhood_type, radius) => neighborhood_type.size(*radius),
@@ -422,17 +502,56 @@ impl Neighborhood {
             Self::Totalistic(_, radius) | Self::Nontotalistic(_, radius) => *radius,
             Self::CustomTotalistic(coords) | Self::CustomNontotalistic(coords) => coords
                 .iter()
-                .map(|(x, y)| x.abs().max(y.abs()) as u32)
+                .map(|(x, y)| x.unsigned_abs().max(y.unsigned_abs()))
                 .max()
                 .unwrap_or(0),
             Self::CustomWeighted(neighbors) => neighbors
                 .iter()
-                .map(|neighbor| neighbor.coord.0.abs().max(neighbor.coord.1.abs()) as u32)
+                .map(|neighbor| {
+                    neighbor
+                        .coord
+                        .0
+                        .unsigned_abs()
+                        .max(neighbor.coord.1.unsigned_abs())
+                })
                 .max()
                 .unwrap_or(0),
         }
     }
 
+    /// The bounding box of the neighbors, as `(min_x, min_y, max_x, max_y)`.
+    ///
+    /// The coordinates are relative to the center cell. Unlike the [`radius`](Neighborhood::radius),
+    /// this gives the actual extent of the neighborhood in each direction, which differs
+    /// for asymmetric custom neighborhoods.
+    ///
+    /// If there are no neighbors, `(0, 0, 0, 0)` is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ca_rules2::{Neighborhood, NeighborhoodType};
+    /// let moore = Neighborhood::Totalistic(NeighborhoodType::Moore, 2);
+    /// assert_eq!(moore.bounding_box(), (-2, -2, 2, 2));
+    ///
+    /// let custom = Neighborhood::CustomTotalistic(vec![(1, 0), (2, -1), (0, 3)]);
+    /// assert_eq!(custom.bounding_box(), (0, -1, 2, 3));
+    /// ```
+    pub fn bounding_box(&self) -> (i32, i32, i32, i32) {
+        let coords = self.neighbor_coords();
+
+        if coords.is_empty() {
+            return (0, 0, 0, 0);
+        }
+
+        coords.into_iter().fold(
+            (i32::MAX, i32::MAX, i32::MIN, i32::MIN),
+            |(min_x, min_y, max_x, max_y), (x, y)| {
+                (min_x.min(x), min_y.min(y), max_x.max(x), max_y.max(y))
+            },
+        )
+    }
+
     /// Maximum possible value for a birth or survival condition.
     ///
     /// For totalistic neighborhoods, this is the number of neighbors.
@@ -440,13 +559,18 @@ impl Neighborhood {
     /// For non-totalistic neighborhoods, this is `2^n`, where `n` is the number of neighbors.
     ///
     /// For weighted neighborhoods, this is the sum of weights of neighbors.
+    ///
+    /// The result saturates at [`u64::MAX`] instead of overflowing.
     pub fn max_condition(&self) -> u64 {
         match self {
             Self::Totalistic(_, _) | Self::CustomTotalistic(_) => self.size() as u64,
-            Self::Nontotalistic(_, _) | Self::CustomNontotalistic(_) => 1 << self.size(),
-            Self::CustomWeighted(neighbors) => {
-                neighbors.iter().map(|neighbor| neighbor.weight).sum()
-            }
+            Self::Nontotalistic(_, _) | Self::CustomNontotalistic(_) => u32::try_from(self.size())
+                .ok()
+                .and_then(|size| 1u64.checked_shl(size))
+                .unwrap_or(u64::MAX),
+            Self::CustomWeighted(neighbors) => neighbors
+                .iter()
+                .fold(0u64, |sum, neighbor| sum.saturating_add(neighbor.weight)),
         }
     }
 }
@@ -473,7 +597,7 @@ impl Neighborhood {
 ///    - Remain in the "live" state if it satisfies the [`survival`](Rule::survival) conditions.
 ///    - Otherwise, transition to the next "dying" state, or the "dead" state if there are only 2 states.
 /// - A cell in a "dying" state will transition to the next "dying" state, or the "dead" state if it is
-///     already in the last "dying" state.
+///   already in the last "dying" state.
 ///
 /// When the number of states is 2, there are no "dying" states, and the rule is equivalent to a Life-like rule.
 ///
@@ -512,6 +636,19 @@ impl Neighborhood {
 /// Totalistic rules and non-totalistic rules can be seen as special cases of weighted rules. In a totalistic rule,
 /// all neighbors have weight 1. In a non-totalistic rule, the weight of the `i`-th neighbor is `2^i`.
 ///
+/// # Outer and Inner Totalistic Rules
+///
+/// The predefined neighborhoods never contain the center cell, so the sum only counts the neighbors,
+/// and the state of the cell itself is taken into account by choosing between the birth and survival
+/// conditions. Such rules are called outer-totalistic.
+///
+/// Some rules count the center cell as well, e.g. HROT rules with `M1` in LtL notation. These are called
+/// inner-totalistic. The parser converts them to outer-totalistic rules, by subtracting 1 from the survival
+/// conditions, since a live center cell always adds 1 to the sum.
+///
+/// A custom neighborhood may still contain the center cell `(0, 0)`, in which case the rule is inner-totalistic.
+/// See [`includes_center`](Rule::includes_center) and [`without_center`](Rule::without_center).
+///
 /// # Examples
 ///
 /// For example, Conway's Game of Life has the Moore neighborhood of radius 1. It is a totalistic rule, so
@@ -546,8 +683,27 @@ impl Rule {
         self.neighborhood.is_totalistic()
     }
 
+    /// Whether the rule is outer-totalistic.
+    ///
+    /// See [`Neighborhood::is_outer_totalistic`] for more information.
+    pub fn is_outer_totalistic(&self) -> bool {
+        self.neighborhood.is_outer_totalistic()
+    }
+
+    /// Whether the neighborhood of the rule is isotropic.
+    ///
+    /// See [`Neighborhood::is_isotropic`] for more information.
+    pub fn is_isotropic(&self) -> bool {
+        self.neighborhood.is_isotropic()
+    }
+
+    /// Whether the rule is weighted.
+    pub const fn is_weighted(&self) -> bool {
+        self.neighborhood.is_weighted()
+    }
+
     /// Number of neighbors.
-    pub fn neighborhood_size(&self) -> usize {
+    pub const fn neighborhood_size(&self) -> usize {
         self.neighborhood.size()
     }
 
@@ -563,6 +719,47 @@ impl Rule {
         self.neighborhood.neighbor_coords()
     }
 
+    /// The bounding box of the neighbors, as `(min_x, min_y, max_x, max_y)`.
+    ///
+    /// See [`Neighborhood::bounding_box`] for more information.
+    pub fn bounding_box(&self) -> (i32, i32, i32, i32) {
+        self.neighborhood.bounding_box()
+    }
+
+    /// The list of [`Neighbor`]s, i.e., the coordinates of the neighbors and their weights.
+    ///
+    /// See [`Neighborhood::neighbors`] for more information.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the radius is too large, or if the neighborhood is non-totalistic
+    /// and has more than 64 neighbors.
+    pub fn neighbors(&self) -> Result<Vec<Neighbor>, NeighborError> {
+        self.neighborhood.neighbors()
+    }
+
+    /// The list of weights of the neighbors.
+    ///
+    /// The weights are in the same order as the coordinates in [`neighbor_coords`](Rule::neighbor_coords).
+    ///
+    /// # Errors
+    ///
+    /// See [`neighbors`](Rule::neighbors).
+    pub fn neighbor_weights(&self) -> Result<Vec<u64>, NeighborError> {
+        Ok(self
+            .neighbors()?
+            .into_iter()
+            .map(|neighbor| neighbor.weight)
+            .collect())
+    }
+
+    /// Maximum possible value for a birth or survival condition.
+    ///
+    /// See [`Neighborhood::max_condition`] for more information.
+    pub fn max_condition(&self) -> u64 {
+        self.neighborhood.max_condition()
+    }
+
     /// Whether the birth conditions contain 0.
     ///
     /// In this case, a dead cell can be born even if it has no live neighbors.
@@ -581,13 +778,104 @@ impl Rule {
     ///
     /// See the documentation of the [`Neighborhood::max_condition`] for more information.
     pub fn check_conditions(&self) -> bool {
-        let max_condition = self.neighborhood.max_condition();
+        let max_condition = self.max_condition();
 
         self.birth.iter().all(|&n| n <= max_condition)
             && self.survival.iter().all(|&n| n <= max_condition)
     }
 }
 
+impl Rule {
+    /// Whether the center cell `(0, 0)` is one of its own neighbors,
+    /// i.e., whether the rule is inner-totalistic.
+    ///
+    /// This is never the case for predefined neighborhoods, or for rules returned by the parser.
+    /// See the documentation of [`Rule`] for more information.
+    pub fn includes_center(&self) -> bool {
+        self.neighbor_coords().contains(&(0, 0))
+    }
+
+    /// Converts the rule to an equivalent outer-totalistic rule, whose neighborhood
+    /// does not include the center cell.
+    ///
+    /// The center cell is removed from the neighborhood. If it has weight `w`,
+    /// `w` is subtracted from each survival condition, and the conditions smaller than `w`
+    /// are removed, since a live center cell always adds `w` to the sum.
+    /// The birth conditions are unchanged, since the center cell is not alive in that case.
+    ///
+    /// A custom totalistic neighborhood stays totalistic. If the remaining neighbors are
+    /// exactly a predefined neighborhood, the predefined one is used instead.
+    /// Other custom neighborhoods become weighted, keeping the weights of the remaining neighbors.
+    ///
+    /// If the rule does not [include the center](Rule::includes_center), it is returned unchanged.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the list of neighbors cannot be generated.
+    /// See [`Neighborhood::neighbors`] for more information.
+    pub fn without_center(&self) -> Result<Self, NeighborError> {
+        if !self.includes_center() {
+            return Ok(self.clone());
+        }
+
+        let (center, neighbors): (Vec<_>, Vec<_>) = self
+            .neighborhood
+            .neighbors()?
+            .into_iter()
+            .partition(|neighbor| neighbor.coord == (0, 0));
+        let center_weight = center.iter().map(|neighbor| neighbor.weight).sum::<u64>();
+
+        let neighborhood = if let Neighborhood::CustomTotalistic(_) = self.neighborhood {
+            let coords = neighbors
+                .iter()
+                .map(|neighbor| neighbor.coord)
+                .collect::<Vec<_>>();
+            predefined_neighborhood(&coords).unwrap_or(Neighborhood::CustomTotalistic(coords))
+        } else {
+            Neighborhood::CustomWeighted(neighbors)
+        };
+
+        let survival = self
+            .survival
+            .iter()
+            .filter_map(|&n| n.checked_sub(center_weight))
+            .collect();
+
+        Ok(Self {
+            states: self.states,
+            neighborhood,
+            birth: self.birth.clone(),
+            survival,
+        })
+    }
+}
+
+/// Finds a predefined totalistic neighborhood with exactly the given neighbors, in any order.
+fn predefined_neighborhood(coords: &[(i32, i32)]) -> Option<Neighborhood> {
+    let radius = coords
+        .iter()
+        .map(|&(x, y)| x.unsigned_abs().max(y.unsigned_abs()))
+        .max()?;
+
+    let mut sorted = coords.to_vec();
+    sorted.sort_unstable();
+
+    [
+        NeighborhoodType::Moore,
+        NeighborhoodType::VonNeumann,
+        NeighborhoodType::Cross,
+        NeighborhoodType::Hash,
+        NeighborhoodType::Hexagonal,
+    ]
+    .into_iter()
+    .find(|neighborhood_type| {
+        let mut predefined = neighborhood_type.neighbor_coords(radius);
+        predefined.sort_unstable();
+        predefined == sorted
+    })
+    .map(|neighborhood_type| Neighborhood::Totalistic(neighborhood_type, radius))
+}
+
 impl FromStr for Rule {
     type Err = ParseRuleError;
 
@@ -596,6 +884,137 @@ impl FromStr for Rule {
     }
 }
 
+/// Writes the rule in a canonical rule string.
+///
+/// The birth and survival conditions are sorted and deduplicated, so two rules that
+/// only differ in the order of the conditions are written in the same way.
+///
+/// - Rules with a totalistic neighborhood of radius 1 are written in B/S notation,
+///   e.g. `B3/S23`, or in B/S/C notation if they have more than 2 states, e.g. `B2/S34/3`.
+///   The neighborhood type is written as a suffix, see [`parse_life_like`](crate::parse_life_like).
+/// - Other rules with a totalistic neighborhood are written in HROT notation,
+///   e.g. `R3,C2,S2,B3,N+`. Consecutive conditions are written as ranges.
+///
+/// These strings can be parsed back with [`FromStr`].
+///
+/// Rules with other neighborhoods have no standard notation. They are written in HROT notation,
+/// with the neighborhood written as a list of coordinates, e.g. `N[(-1,0),(1,0)]`.
+/// The list is prefixed with `!` for non-totalistic neighborhoods, and each coordinate
+/// is followed by `*{weight}` for weighted neighborhoods.
+///
+/// Rules with a [`Totalistic`](Neighborhood::Totalistic),
+/// [`CustomTotalistic`](Neighborhood::CustomTotalistic),
+/// [`CustomNontotalistic`](Neighborhood::CustomNontotalistic) or
+/// [`CustomWeighted`](Neighborhood::CustomWeighted) neighborhood are parsed back
+/// to an equal rule. A rule with a [`Nontotalistic`](Neighborhood::Nontotalistic)
+/// neighborhood is written as the list of its neighbors, so it is parsed back as
+/// a [`CustomNontotalistic`](Neighborhood::CustomNontotalistic) rule with the same
+/// neighbors in the same order, which has the same transitions but does not compare equal.
+impl Display for Rule {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let mut birth = self.birth.clone();
+        birth.sort_unstable();
+        birth.dedup();
+        let mut survival = self.survival.clone();
+        survival.sort_unstable();
+        survival.dedup();
+
+        if let Neighborhood::Totalistic(neighborhood_type, 1) = self.neighborhood {
+            let write_digits = |f: &mut Formatter<'_>, conditions: &[u64]| {
+                conditions.iter().try_for_each(|n| write!(f, "{n}"))
+            };
+
+            write!(f, "B")?;
+            write_digits(f, &birth)?;
+            write!(f, "/S")?;
+            write_digits(f, &survival)?;
+            if self.states > 2 {
+                write!(f, "/{}", self.states)?;
+            }
+
+            let suffix = match neighborhood_type {
+                NeighborhoodType::Moore => "",
+                NeighborhoodType::VonNeumann => "V",
+                NeighborhoodType::Cross => "+",
+                NeighborhoodType::Hash => "#",
+                NeighborhoodType::Hexagonal => "H",
+            };
+            return write!(f, "{suffix}");
+        }
+
+        let write_ranges = |f: &mut Formatter<'_>, conditions: &[u64]| {
+            let mut first = true;
+            let mut i = 0;
+            while i < conditions.len() {
+                let mut j = i;
+                while j + 1 < conditions.len() && conditions[j + 1] == conditions[j] + 1 {
+                    j += 1;
+                }
+
+                if !first {
+                    write!(f, ",")?;
+                }
+                first = false;
+
+                if i == j {
+                    write!(f, "{}", conditions[i])?;
+                } else {
+                    write!(f, "{}-{}", conditions[i], conditions[j])?;
+                }
+
+                i = j + 1;
+            }
+            Ok(())
+        };
+
+        write!(f, "R{},C{},S", self.radius(), self.states)?;
+        write_ranges(f, &survival)?;
+        write!(f, ",B")?;
+        write_ranges(f, &birth)?;
+        write!(f, ",N")?;
+
+        let write_coords = |f: &mut Formatter<'_>, coords: &[(i32, i32)]| {
+            write!(f, "[")?;
+            for (i, (x, y)) in coords.iter().enumerate() {
+                if i > 0 {
+                    write!(f, ",")?;
+                }
+                write!(f, "({x},{y})")?;
+            }
+            write!(f, "]")
+        };
+
+        match &self.neighborhood {
+            Neighborhood::Totalistic(neighborhood_type, _) => {
+                let letter = match neighborhood_type {
+                    NeighborhoodType::Moore => "M",
+                    NeighborhoodType::VonNeumann => "N",
+                    NeighborhoodType::Cross => "+",
+                    NeighborhoodType::Hash => "#",
+                    NeighborhoodType::Hexagonal => "H",
+                };
+                write!(f, "{letter}")
+            }
+            Neighborhood::Nontotalistic(_, _) | Neighborhood::CustomNontotalistic(_) => {
+                write!(f, "!")?;
+                write_coords(f, &self.neighbor_coords())
+            }
+            Neighborhood::CustomTotalistic(coords) => write_coords(f, coords),
+            Neighborhood::CustomWeighted(neighbors) => {
+                write!(f, "[")?;
+                for (i, neighbor) in neighbors.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ",")?;
+                    }
+                    let (x, y) = neighbor.coord;
+                    write!(f, "({x},{y})*{}", neighbor.weight)?;
+                }
+                write!(f, "]")
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -604,10 +1023,96 @@ mod tests {
         NeighborhoodType::Moore,
         NeighborhoodType::VonNeumann,
         NeighborhoodType::Cross,
-        NeighborhoodType::Hexagonal,
+        NeighborhoodType::Hash,
         NeighborhoodType::Hexagonal,
     ];
 
+    #[test]
+    fn test_max_non_totalistic_radius() {
+        for neighborhood_type in ALL_NEIGHBORHOOD_TYPES {
+            let max_radius = neighborhood_type.max_non_totalistic_radius();
+            assert!(neighborhood_type.size(max_radius) <= 64);
+            assert!(neighborhood_type.size(max_radius + 1) > 64);
+            assert!(neighborhood_type.neighbors(max_radius, false).is_ok());
+        }
+
+        let err = NeighborhoodType::Cross.neighbors(17, false).unwrap_err();
+        assert!(matches!(
+            err,
+            NeighborError::NonTotalisticRadiusTooLarge {
+                neighborhood_type: NeighborhoodType::Cross,
+                max_radius: 16,
+            }
+        ));
+        assert_eq!(
+            err.to_string(),
+            "Cross non-totalistic radius must be at most 16"
+        );
+        assert!(NeighborhoodType::Cross.neighbors(17, true).is_ok());
+    }
+
+    #[test]
+    fn test_without_center() {
+        // LtL notation with `M1` counts the center cell, and is converted by the parser.
+        let rule: Rule = "R1,C2,M1,S3..4,B3..3,NM".parse().unwrap();
+        assert!(!rule.includes_center());
+        assert_eq!(rule, "B3/S23".parse().unwrap());
+        assert_eq!(rule.without_center().unwrap(), rule);
+
+        // The same rule with a custom neighborhood including the center.
+        let mut coords = NeighborhoodType::Moore.neighbor_coords(1);
+        coords.insert(4, (0, 0));
+        let inner = Rule {
+            states: 2,
+            neighborhood: Neighborhood::CustomTotalistic(coords),
+            birth: vec![3],
+            survival: vec![0, 3, 4],
+        };
+        assert!(inner.includes_center());
+        assert_eq!(inner.without_center().unwrap(), rule);
+
+        // A custom neighborhood that is not predefined.
+        let inner = Rule {
+            states: 3,
+            neighborhood: Neighborhood::CustomTotalistic(vec![(0, 0), (1, 0), (0, 1)]),
+            birth: vec![1],
+            survival: vec![1, 2],
+        };
+        assert_eq!(
+            inner.without_center().unwrap(),
+            Rule {
+                states: 3,
+                neighborhood: Neighborhood::CustomTotalistic(vec![(1, 0), (0, 1)]),
+                birth: vec![1],
+                survival: vec![0, 1],
+            }
+        );
+
+        // A weighted neighborhood keeps the weights.
+        let inner = Rule {
+            states: 2,
+            neighborhood: Neighborhood::CustomWeighted(vec![
+                Neighbor::new((-1, 0), 1),
+                Neighbor::new((0, 0), 2),
+                Neighbor::new((1, 0), 1),
+            ]),
+            birth: vec![2],
+            survival: vec![1, 3],
+        };
+        assert_eq!(
+            inner.without_center().unwrap(),
+            Rule {
+                states: 2,
+                neighborhood: Neighborhood::CustomWeighted(vec![
+                    Neighbor::new((-1, 0), 1),
+                    Neighbor::new((1, 0), 1),
+                ]),
+                birth: vec![2],
+                survival: vec![1],
+            }
+        );
+    }
+
     #[test]
     fn test_neighborhood_type() {
         let moore = NeighborhoodType::Moore.neighbors(1, true).unwrap();
@@ -693,4 +1198,130 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_classification() {
+        let life: Rule = "B3/S23".parse().unwrap();
+        assert!(life.is_totalistic());
+        assert!(life.is_outer_totalistic());
+        assert!(life.is_isotropic());
+        assert!(!life.is_weighted());
+
+        for neighborhood_type in [NeighborhoodType::Moore, NeighborhoodType::VonNeumann] {
+            for radius in 1..4 {
+                let neighborhood = Neighborhood::Totalistic(neighborhood_type, radius);
+                assert!(
+                    neighborhood.is_isotropic(),
+                    "{neighborhood_type:?} {radius}"
+                );
+            }
+        }
+        assert!(!Neighborhood::Totalistic(NeighborhoodType::Hexagonal, 1).is_isotropic());
+
+        // An asymmetric custom neighborhood.
+        let knight = Neighborhood::CustomTotalistic(vec![(1, 2), (2, -1), (-1, -2), (-2, 1)]);
+        assert!(knight.is_outer_totalistic());
+        assert!(!knight.is_isotropic());
+
+        let inner = Neighborhood::CustomTotalistic(vec![(0, 0), (1, 0), (-1, 0), (0, 1), (0, -1)]);
+        assert!(inner.is_totalistic());
+        assert!(!inner.is_outer_totalistic());
+        assert!(inner.is_isotropic());
+
+        let mut neighbors = Neighbor::from_coords(NeighborhoodType::VonNeumann.neighbor_coords(1));
+        let weighted = Neighborhood::CustomWeighted(neighbors.clone());
+        assert!(weighted.is_weighted());
+        assert!(!weighted.is_outer_totalistic());
+        assert!(weighted.is_isotropic());
+        neighbors[0].weight = 2;
+        assert!(!Neighborhood::CustomWeighted(neighbors).is_isotropic());
+    }
+
+    #[test]
+    fn test_bounding_box() {
+        for neighborhood_type in ALL_NEIGHBORHOOD_TYPES {
+            let neighborhood = Neighborhood::Totalistic(neighborhood_type, 3);
+            let (min_x, min_y, max_x, max_y) = neighborhood.bounding_box();
+            assert_eq!((-min_x, -min_y), (max_x, max_y), "{neighborhood_type:?}");
+            assert_eq!(
+                max_x.max(max_y) as u32,
+                neighborhood.radius(),
+                "{neighborhood_type:?}"
+            );
+        }
+
+        let rule: Rule = "B3/S23".parse().unwrap();
+        assert_eq!(rule.bounding_box(), (-1, -1, 1, 1));
+
+        // An asymmetric custom neighborhood only extends to the right and below.
+        let custom = Neighborhood::CustomWeighted(vec![
+            Neighbor::new((1, 0), 1),
+            Neighbor::new((3, 1), 2),
+            Neighbor::new((0, 2), 1),
+        ]);
+        assert_eq!(custom.bounding_box(), (0, 0, 3, 2));
+        assert_eq!(custom.radius(), 3);
+
+        assert_eq!(
+            Neighborhood::CustomTotalistic(Vec::new()).bounding_box(),
+            (0, 0, 0, 0)
+        );
+    }
+
+    #[test]
+    fn test_display() {
+        let rules = [
+            "B3/S23",
+            "B2/S",
+            "B3/S12V",
+            "B2/S34/3",
+            "B2/S+",
+            "B3/S23#",
+            "B2/S34H",
+            "R3,C2,S2,B3,N+",
+            "R2,C2,S,B3-5,7,NM",
+            "R5,C3,S10-20,B12,N#",
+        ];
+
+        for rule_str in rules {
+            let rule = Rule::from_str(rule_str).unwrap();
+            assert_eq!(rule.to_string(), rule_str);
+            assert_eq!(Rule::from_str(&rule.to_string()).unwrap(), rule);
+        }
+
+        // The conditions are sorted and deduplicated.
+        let rule = Rule::from_str("B63/S3223").unwrap();
+        assert_eq!(rule.to_string(), "B36/S23");
+        assert_eq!(
+            Rule::from_str("R1,C2,S2,B3,N+").unwrap().to_string(),
+            "B3/S2+"
+        );
+
+        let rule = Rule {
+            states: 2,
+            neighborhood: Neighborhood::CustomWeighted(vec![
+                Neighbor::new((-1, 0), 2),
+                Neighbor::new((1, 0), 1),
+            ]),
+            birth: vec![1],
+            survival: vec![],
+        };
+        assert_eq!(rule.to_string(), "R1,C2,S,B1,N[(-1,0)*2,(1,0)*1]");
+        assert_eq!(Rule::from_str(&rule.to_string()).unwrap(), rule);
+
+        // A non-totalistic rule is parsed back with a custom neighborhood.
+        let rule = Rule {
+            states: 2,
+            neighborhood: Neighborhood::Nontotalistic(NeighborhoodType::VonNeumann, 1),
+            birth: vec![0b0011],
+            survival: vec![0b1000],
+        };
+        let parsed = Rule::from_str(&rule.to_string()).unwrap();
+        assert_eq!(
+            parsed.neighborhood,
+            Neighborhood::CustomNontotalistic(rule.neighbor_coords())
+        );
+        assert_eq!(parsed.birth, rule.birth);
+        assert_eq!(parsed.survival, rule.survival);
+    }
 }
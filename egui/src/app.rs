@@ -2,7 +2,7 @@ use crate::search::{Event, Message, SearchThread};
 use documented::{Documented, DocumentedFields};
 use eframe::{glow::Context as GlowContext, App as EframeApp, Frame};
 use egui::{text::LayoutJob, CentralPanel, Context, SidePanel, TopBottomPanel};
-use factoriosrc_lib::{Config, Status};
+use factoriosrc_lib::{CellState, Config, SearchOrder, Status};
 #[cfg(feature = "save")]
 use serde::{Deserialize, Serialize};
 #[cfg(feature = "save")]
@@ -30,12 +30,71 @@ pub struct AppConfig {
     ///
     /// When the world size is increased, the search will be restarted, and the current search
     /// status will be lost.
+    ///
+    /// If [`relax_on_no_solution`](factoriosrc_lib::Config::relax_on_no_solution) is set,
+    /// the configuration is relaxed as it says instead, even if this is not enabled.
     pub increase_world_size: bool,
 
     /// Do not stop the search when a solution is found.
     ///
     /// The search will continue until no more solutions exist, or paused by the user.
     pub no_stop: bool,
+
+    /// The colors and characters used to display the cells.
+    #[cfg_attr(feature = "save", serde(default))]
+    pub theme: Theme,
+}
+
+/// The colors and characters used to display the cells in the result view.
+///
+/// Colors are given as sRGB triples.
+#[derive(Debug, Clone, PartialEq, Eq, Documented, DocumentedFields)]
+#[cfg_attr(feature = "save", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "save", serde(default))]
+pub struct Theme {
+    /// The color of living cells.
+    pub alive_color: [u8; 3],
+
+    /// The color of dead cells.
+    pub dead_color: [u8; 3],
+
+    /// The color of unknown cells.
+    pub unknown_color: [u8; 3],
+
+    /// The character used to display living cells.
+    pub alive_glyph: char,
+
+    /// The character used to display dead cells.
+    ///
+    /// Use `b` to match the compact RLE format.
+    pub dead_glyph: char,
+
+    /// The character used to display unknown cells.
+    pub unknown_glyph: char,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self {
+            alive_color: [113, 140, 0],
+            dead_color: [200, 40, 41],
+            unknown_color: [137, 89, 168],
+            alive_glyph: CellState::Alive.glyph(),
+            dead_glyph: CellState::Dead.glyph(),
+            unknown_glyph: CellState::glyph_opt(None),
+        }
+    }
+}
+
+impl Theme {
+    /// The color and the character used to display a cell with the given state.
+    pub const fn style(&self, state: Option<CellState>) -> ([u8; 3], char) {
+        match state {
+            Some(CellState::Alive) => (self.alive_color, self.alive_glyph),
+            Some(CellState::Dead) => (self.dead_color, self.dead_glyph),
+            None => (self.unknown_color, self.unknown_glyph),
+        }
+    }
 }
 
 /// Application modes.
@@ -73,6 +132,8 @@ pub struct App {
     pub status: Status,
     /// Time elapsed since the start of the search.
     pub elapsed: Duration,
+    /// The search order used by the current search, which may have been chosen automatically.
+    pub search_order: Option<SearchOrder>,
     /// A path to save the search state.
     #[cfg(feature = "save")]
     pub save: Option<PathBuf>,
@@ -85,6 +146,7 @@ impl Default for App {
             step: 100_000,
             increase_world_size: false,
             no_stop: false,
+            theme: Theme::default(),
         };
         Self {
             config,
@@ -97,6 +159,7 @@ impl Default for App {
             error: None,
             status: Status::NotStarted,
             elapsed: Duration::default(),
+            search_order: None,
             #[cfg(feature = "save")]
             save: None,
         }
@@ -200,6 +263,7 @@ impl App {
         self.mode = Mode::Configuring;
         self.status = Status::NotStarted;
         self.generation = 0;
+        self.search_order = None;
     }
 
     /// Send an event to the search thread to save the current state.
@@ -220,16 +284,10 @@ impl App {
                 self.view = frame.view;
                 self.populations = frame.populations;
                 self.elapsed = frame.elapsed;
+                self.search_order = Some(frame.search_order);
                 if frame.status == Status::Solved {
                     // Choose the generation with the smallest population.
-                    let solution = self
-                        .view
-                        .iter()
-                        .zip(&self.populations)
-                        .min_by_key(|(_, &p)| p)
-                        .unwrap()
-                        .0
-                        .clone();
+                    let solution = self.view[frame.minimal_phase as usize].clone();
 
                     self.solutions.push(solution);
                 }
@@ -1,9 +1,9 @@
-use crate::app::AppConfig;
+use crate::app::{AppConfig, Theme};
 use egui::{
     text::{LayoutJob, TextFormat},
     Color32, FontId,
 };
-use factoriosrc_lib::{Status, World};
+use factoriosrc_lib::{SearchOrder, Status, World};
 #[cfg(feature = "save")]
 use serde::{Deserialize, Serialize};
 #[cfg(feature = "save")]
@@ -52,6 +52,10 @@ pub struct Frame {
     pub view: Vec<LayoutJob>,
     /// Populations of each generation of the current partial result.
     pub populations: Vec<usize>,
+    /// The generation with the smallest population.
+    pub minimal_phase: i32,
+    /// The search order that is actually used, even if it was chosen automatically.
+    pub search_order: SearchOrder,
 }
 
 impl From<Frame> for Message {
@@ -79,6 +83,9 @@ struct Search {
     increase_world_size: bool,
     /// Whether not to stop the search when a solution is found.
     no_stop: bool,
+    /// The colors and characters used to display the cells.
+    #[cfg_attr(feature = "save", serde(default))]
+    theme: Theme,
     /// Whether the search is running.
     #[cfg_attr(feature = "save", serde(skip))]
     running: bool,
@@ -102,6 +109,7 @@ impl Search {
             step: config.step,
             increase_world_size: config.increase_world_size,
             no_stop: config.no_stop,
+            theme: config.theme,
             running: false,
             should_quit: false,
             start: None,
@@ -143,10 +151,17 @@ impl Search {
     fn step(&mut self) {
         self.status = self.world.search(self.step);
 
-        if self.status == Status::NoSolution && self.increase_world_size {
-            log::info!("Increasing world size.");
-            self.world.increase_world_size();
-            self.status = Status::Running;
+        if self.status == Status::NoSolution
+            && (self.increase_world_size || self.world.config().relax_on_no_solution.is_some())
+        {
+            match self.world.grow_or_relax() {
+                Ok(true) => {
+                    log::info!("Relaxing the configuration.");
+                    self.status = Status::Running;
+                }
+                Ok(false) => {}
+                Err(e) => log::error!("Failed to relax the configuration: {e}"),
+            }
         }
 
         if self.status != Status::Running && !self.no_stop || self.status == Status::NoSolution {
@@ -182,41 +197,16 @@ impl Search {
             for y in 0..h {
                 for x in 0..w {
                     let state = self.world.get_cell_state((x, y, t));
-                    match state {
-                        Some(factoriosrc_lib::CellState::Alive) => {
-                            job.append(
-                                "o",
-                                0.0,
-                                TextFormat {
-                                    color: Color32::from_rgb(113, 140, 0),
-                                    font_id: FontId::monospace(14.0),
-                                    ..Default::default()
-                                },
-                            );
-                        }
-                        Some(factoriosrc_lib::CellState::Dead) => {
-                            job.append(
-                                ".",
-                                0.0,
-                                TextFormat {
-                                    color: Color32::from_rgb(200, 40, 41),
-                                    font_id: FontId::monospace(14.0),
-                                    ..Default::default()
-                                },
-                            );
-                        }
-                        None => {
-                            job.append(
-                                "?",
-                                0.0,
-                                TextFormat {
-                                    color: Color32::from_rgb(137, 89, 168),
-                                    font_id: FontId::monospace(14.0),
-                                    ..Default::default()
-                                },
-                            );
-                        }
-                    }
+                    let ([r, g, b], glyph) = self.theme.style(state);
+                    job.append(
+                        glyph.encode_utf8(&mut [0; 4]),
+                        0.0,
+                        TextFormat {
+                            color: Color32::from_rgb(r, g, b),
+                            font_id: FontId::monospace(14.0),
+                            ..Default::default()
+                        },
+                    );
                 }
                 job.append(
                     if y == h - 1 { "!\n" } else { "$\n" },
@@ -247,6 +237,8 @@ impl Search {
             elapsed: self.elapsed,
             view,
             populations,
+            minimal_phase: self.world.minimal_phase(),
+            search_order: self.world.resolved_search_order(),
         }
     }
 
@@ -342,6 +334,7 @@ impl SearchThread {
             step: search.step,
             increase_world_size: search.increase_world_size,
             no_stop: search.no_stop,
+            theme: search.theme.clone(),
         };
 
         let (tx, rx) = mpsc::channel();
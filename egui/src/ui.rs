@@ -1,12 +1,59 @@
-use crate::app::{App, AppConfig, Mode};
+use crate::app::{App, AppConfig, Mode, Theme};
 use documented::{Documented, DocumentedFields};
-use egui::{Color32, ComboBox, DragValue, Grid, Label, RichText, ScrollArea, Slider, Ui};
+use egui::{Color32, ComboBox, DragValue, Grid, Label, RichText, ScrollArea, Slider, TextEdit, Ui};
 use factoriosrc_lib::{
-    Config, NewState, SearchOrder, Status, Symmetry, Transformation, TranslationCondition,
+    neighborhood_offsets, Boundary, Config, NewState, RelaxStrategy, SearchOrder, Status, Symmetry,
+    Transformation, TranslationCondition,
 };
 #[cfg(feature = "save")]
 use rfd::FileDialog;
 
+/// Draw the shape of a neighborhood as text, with `O` for the center cell,
+/// `#` for the neighbors, and `.` for the other cells.
+fn neighborhood_shape(offsets: &[(i32, i32)]) -> String {
+    let r = offsets
+        .iter()
+        .map(|&(x, y)| x.abs().max(y.abs()))
+        .max()
+        .unwrap_or(0);
+
+    let mut shape = String::new();
+    for y in -r..=r {
+        for x in -r..=r {
+            let c = if (x, y) == (0, 0) {
+                'O'
+            } else if offsets.contains(&(x, y)) {
+                '#'
+            } else {
+                '.'
+            };
+            shape.push(c);
+            if x < r {
+                shape.push(' ');
+            }
+        }
+        if y < r {
+            shape.push('\n');
+        }
+    }
+    shape
+}
+
+/// A text field to edit a single character.
+///
+/// The character is unchanged if the field is cleared.
+fn glyph_edit(ui: &mut Ui, glyph: &mut char) {
+    let mut text = glyph.to_string();
+    ui.add(
+        TextEdit::singleline(&mut text)
+            .char_limit(1)
+            .desired_width(16.0),
+    );
+    if let Some(c) = text.chars().next() {
+        *glyph = c;
+    }
+}
+
 impl App {
     /// The configuration panel.
     pub fn config_panel(&mut self, ui: &mut Ui) {
@@ -22,10 +69,15 @@ impl App {
                     ui.label("rule")
                         .on_hover_text(Config::get_field_docs("rule_str").unwrap());
                     ui.horizontal(|ui| {
-                        match config.parse_rule() {
-                            Ok(_) => {
+                        match neighborhood_offsets(&config.rule_str) {
+                            Ok(offsets) => {
                                 ui.label(RichText::new("✔").color(Color32::GREEN))
-                                    .on_hover_text("The rule is valid.");
+                                    .on_hover_ui(|ui| {
+                                        ui.label("The rule is valid. Its neighborhood is:");
+                                        ui.label(
+                                            RichText::new(neighborhood_shape(&offsets)).monospace(),
+                                        );
+                                    });
                             }
                             Err(err) => {
                                 ui.label(RichText::new("🗙").color(Color32::RED))
@@ -80,6 +132,13 @@ impl App {
                     );
                     ui.end_row();
 
+                    ui.label("up to period")
+                        .on_hover_text(Config::get_field_docs("search_up_to_period").unwrap());
+                    let mut checked = config.search_up_to_period.is_some();
+                    ui.checkbox(&mut checked, "");
+                    config.search_up_to_period = checked.then_some(config.period);
+                    ui.end_row();
+
                     let translation_condition = config.symmetry.translation_condition();
                     match translation_condition {
                         TranslationCondition::Any
@@ -158,6 +217,7 @@ impl App {
                         ui.horizontal(|ui| {
                             let mut checked = config.diagonal_width.is_some();
                             ui.checkbox(&mut checked, "");
+                            let max_diagonal_width = config.width.max(1);
                             let mut dummy = 0;
                             let diagonal_width = if checked {
                                 config
@@ -168,18 +228,34 @@ impl App {
                                 &mut dummy
                             };
                             ui.add_enabled_ui(checked, |ui| {
-                                ui.add(
-                                    DragValue::new(diagonal_width).speed(0.1).range(if checked {
-                                        1..=u16::MAX
+                                ui.add(DragValue::new(diagonal_width).speed(0.1).range(
+                                    if checked {
+                                        1..=max_diagonal_width
                                     } else {
                                         0..=0
-                                    }),
-                                );
+                                    },
+                                ));
                             });
                         })
                     });
                     ui.end_row();
 
+                    ui.label("boundary")
+                        .on_hover_text(Config::get_field_docs("boundary").unwrap());
+                    ComboBox::from_id_source("boundary")
+                        .selected_text(config.boundary.to_string())
+                        .show_ui(ui, |ui| {
+                            for (i, boundary) in Boundary::iter().enumerate() {
+                                ui.selectable_value(
+                                    &mut config.boundary,
+                                    boundary,
+                                    boundary.to_string(),
+                                )
+                                .on_hover_text(Boundary::FIELD_DOCS[i].unwrap());
+                            }
+                        });
+                    ui.end_row();
+
                     ui.label("symmetry")
                         .on_hover_text(Config::get_field_docs("symmetry").unwrap());
                     ComboBox::from_id_source("symmetry")
@@ -215,11 +291,13 @@ impl App {
                     ui.label("search order")
                         .on_hover_text(Config::get_field_docs("search_order").unwrap());
                     ComboBox::from_id_source("search_order")
-                        .selected_text(
-                            config
-                                .search_order
-                                .map_or_else(|| "auto".to_owned(), |s| s.to_string()),
-                        )
+                        .selected_text(config.search_order.map_or_else(
+                            || {
+                                self.search_order
+                                    .map_or_else(|| "auto".to_owned(), |s| format!("auto ({s})"))
+                            },
+                            |s| s.to_string(),
+                        ))
                         .show_ui(ui, |ui| {
                             ui.selectable_value(&mut config.search_order, None, "auto")
                                 .on_hover_text("The search order is automatically determined.");
@@ -234,6 +312,16 @@ impl App {
                         });
                     ui.end_row();
 
+                    if let Some(suggested) = config.suggested_search_order() {
+                        ui.label("");
+                        ui.label(RichText::new("⚠").color(Color32::YELLOW))
+                            .on_hover_text(format!(
+                                "This search order disables the front reduction, \
+                                 so the search may be slow. Consider {suggested} instead."
+                            ));
+                        ui.end_row();
+                    }
+
                     ui.label("new state")
                         .on_hover_text(Config::get_field_docs("new_state").unwrap());
                     ComboBox::from_id_source("new_state")
@@ -268,6 +356,24 @@ impl App {
                     });
                     ui.end_row();
 
+                    ui.label("restart after")
+                        .on_hover_text(Config::get_field_docs("restart_after").unwrap());
+                    ui.horizontal(|ui| {
+                        let mut checked = config.restart_after.is_some();
+                        ui.checkbox(&mut checked, "");
+                        let mut dummy = 0;
+                        let restart_after = if checked {
+                            config.restart_after.get_or_insert(10_000)
+                        } else {
+                            config.restart_after = None;
+                            &mut dummy
+                        };
+                        ui.add_enabled_ui(checked, |ui| {
+                            ui.add(DragValue::new(restart_after).speed(10.0));
+                        });
+                    });
+                    ui.end_row();
+
                     ui.label("max population")
                         .on_hover_text(Config::get_field_docs("max_population").unwrap());
                     ui.horizontal(|ui| {
@@ -288,11 +394,131 @@ impl App {
                     });
                     ui.end_row();
 
+                    ui.label("min population")
+                        .on_hover_text(Config::get_field_docs("min_population").unwrap());
+                    ui.horizontal(|ui| {
+                        let mut checked = config.min_population.is_some();
+                        ui.checkbox(&mut checked, "");
+                        let mut dummy = 0;
+                        let min_population = if checked {
+                            config.min_population.get_or_insert(1)
+                        } else {
+                            config.min_population = None;
+                            &mut dummy
+                        };
+                        ui.add_enabled_ui(checked, |ui| {
+                            ui.add(DragValue::new(min_population).speed(0.1));
+                        });
+                    });
+                    ui.end_row();
+
+                    ui.label("exact population")
+                        .on_hover_text(Config::get_field_docs("exact_population").unwrap());
+                    ui.horizontal(|ui| {
+                        let mut checked = config.exact_population.is_some();
+                        ui.checkbox(&mut checked, "");
+                        let mut dummy = 0;
+                        let exact_population = if checked {
+                            config.exact_population.get_or_insert(1)
+                        } else {
+                            config.exact_population = None;
+                            &mut dummy
+                        };
+                        ui.add_enabled_ui(checked, |ui| {
+                            ui.add(DragValue::new(exact_population).speed(0.1));
+                        });
+                    });
+                    ui.end_row();
+
                     ui.label("reduce max")
                         .on_hover_text(Config::get_field_docs("reduce_max_population").unwrap());
                     ui.checkbox(&mut config.reduce_max_population, "");
                     ui.end_row();
 
+                    ui.label("rotate gens").on_hover_text(
+                        Config::get_field_docs("allow_generation_rotation").unwrap(),
+                    );
+                    ui.checkbox(&mut config.allow_generation_rotation, "");
+                    ui.end_row();
+
+                    ui.label("front gen")
+                        .on_hover_text(Config::get_field_docs("front_generation").unwrap());
+                    ui.horizontal(|ui| {
+                        let mut checked = config.front_generation.is_some();
+                        ui.checkbox(&mut checked, "");
+                        let mut dummy = 0;
+                        let front_generation = if checked {
+                            config.front_generation.get_or_insert(0)
+                        } else {
+                            config.front_generation = None;
+                            &mut dummy
+                        };
+                        ui.add_enabled_ui(checked, |ui| {
+                            ui.add(
+                                DragValue::new(front_generation)
+                                    .speed(0.1)
+                                    .range(0..=config.period.saturating_sub(1)),
+                            );
+                        });
+                    });
+                    ui.end_row();
+
+                    ui.label("relax")
+                        .on_hover_text(Config::get_field_docs("relax_on_no_solution").unwrap());
+                    ui.horizontal(|ui| {
+                        let mut checked = config.relax_on_no_solution.is_some();
+                        ui.checkbox(&mut checked, "");
+                        let mut dummy = RelaxStrategy::Size(1);
+                        let strategy = if checked {
+                            config
+                                .relax_on_no_solution
+                                .get_or_insert(RelaxStrategy::Size(1))
+                        } else {
+                            config.relax_on_no_solution = None;
+                            &mut dummy
+                        };
+                        ui.add_enabled_ui(checked, |ui| {
+                            let amount = match *strategy {
+                                RelaxStrategy::MaxPopulation(n) => n as u32,
+                                RelaxStrategy::DiagonalWidth(n) | RelaxStrategy::Size(n) => n,
+                            };
+                            let candidates = [
+                                (
+                                    RelaxStrategy::MaxPopulation(amount as usize),
+                                    "max population",
+                                ),
+                                (RelaxStrategy::DiagonalWidth(amount), "diagonal width"),
+                                (RelaxStrategy::Size(amount), "size"),
+                            ];
+                            ComboBox::from_id_source("relax_on_no_solution")
+                                .selected_text(
+                                    candidates
+                                        .iter()
+                                        .find(|(candidate, _)| candidate == strategy)
+                                        .map_or("", |(_, name)| name),
+                                )
+                                .show_ui(ui, |ui| {
+                                    for (candidate, name) in candidates {
+                                        ui.selectable_value(strategy, candidate, name);
+                                    }
+                                });
+                            match strategy {
+                                RelaxStrategy::MaxPopulation(n) => {
+                                    ui.add(DragValue::new(n).speed(0.1).range(1..=usize::MAX));
+                                }
+                                RelaxStrategy::DiagonalWidth(n) | RelaxStrategy::Size(n) => {
+                                    ui.add(DragValue::new(n).speed(0.1).range(1..=u32::MAX));
+                                }
+                            }
+                        });
+                    });
+                    ui.end_row();
+
+                    ui.label("full box")
+                        .on_hover_text(Config::get_field_docs("full_bounding_box").unwrap());
+                    ui.checkbox(&mut config.full_bounding_box, "");
+                    ui.end_row();
+
                     ui.label("increase size")
                         .on_hover_text(AppConfig::get_field_docs("increase_world_size").unwrap());
                     ui.checkbox(&mut self.config.increase_world_size, "");
@@ -307,6 +533,37 @@ impl App {
                         .on_hover_text(AppConfig::get_field_docs("step").unwrap());
                     ui.add(DragValue::new(&mut self.config.step).speed(1.0));
                     ui.end_row();
+
+                    let theme = &mut self.config.theme;
+
+                    for (label, field, color, glyph) in [
+                        (
+                            "alive",
+                            "alive_color",
+                            &mut theme.alive_color,
+                            &mut theme.alive_glyph,
+                        ),
+                        (
+                            "dead",
+                            "dead_color",
+                            &mut theme.dead_color,
+                            &mut theme.dead_glyph,
+                        ),
+                        (
+                            "unknown",
+                            "unknown_color",
+                            &mut theme.unknown_color,
+                            &mut theme.unknown_glyph,
+                        ),
+                    ] {
+                        ui.label(label)
+                            .on_hover_text(Theme::get_field_docs(field).unwrap());
+                        ui.horizontal(|ui| {
+                            ui.color_edit_button_srgb(color);
+                            glyph_edit(ui, glyph);
+                        });
+                        ui.end_row();
+                    }
                 });
         });
     }
@@ -0,0 +1,15 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+use factoriosrc_lib::{Rule, RuleTable};
+use std::hint::black_box;
+
+fn bench_rule_table(c: &mut Criterion) {
+    // A radius-2 Moore rule, with 24 neighbors.
+    let rule = "R2,C2,S6-9,B7-8,NM".parse::<Rule>().unwrap();
+
+    c.bench_function("rule table R2 Moore", |b| {
+        b.iter(|| RuleTable::new(black_box(&rule)).unwrap());
+    });
+}
+
+criterion_group!(benches, bench_rule_table);
+criterion_main!(benches);
@@ -0,0 +1,28 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+use factoriosrc_lib::{Config, World};
+use std::hint::black_box;
+
+fn bench_search(c: &mut Criterion) {
+    // A c/4 diagonal search in a 32x32 world, limited to a fixed number of steps,
+    // so that the time mostly depends on the layout of the cells.
+    //
+    // To compare the layouts, save a baseline with the states inline in the cells,
+    // then run it again with the states packed into a separate array:
+    //
+    //     cargo bench --bench search -- --save-baseline inline
+    //     cargo bench --bench search --features packed-state -- --baseline inline
+    let config = Config::new("B3/S23", 32, 32, 4).with_translations(1, 1);
+
+    let mut group = c.benchmark_group("search");
+    group.sample_size(10);
+    group.bench_function("32x32 c/4 diagonal, 100000 steps", |b| {
+        b.iter(|| {
+            let mut world = World::new(black_box(config.clone())).unwrap();
+            world.search(100_000)
+        });
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_search);
+criterion_main!(benches);
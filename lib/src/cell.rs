@@ -21,27 +21,31 @@ pub(crate) struct LifeCell {
     /// The state of the cell.
     ///
     /// [`None`] means the cell is unknown.
+    ///
+    /// With the `packed-state` feature, the states are stored in the [`World`]
+    /// as [`PackedStates`] instead.
+    #[cfg(not(feature = "packed-state"))]
     pub(crate) state: Cell<Option<CellState>>,
 
     /// The neighborhood descriptor of the cell.
     pub(crate) descriptor: Cell<Descriptor>,
 
     /// The predecessor of the cell.
-    pub(crate) predecessor: *const LifeCell,
+    pub(crate) predecessor: *const Self,
 
     /// The successor of the cell.
-    pub(crate) successor: *const LifeCell,
+    pub(crate) successor: *const Self,
 
     /// The neighborhood of the cell.
-    pub(crate) neighborhood: [*const LifeCell; MAX_NEIGHBORHOOD_SIZE],
+    pub(crate) neighborhood: [*const Self; MAX_NEIGHBORHOOD_SIZE],
 
     /// Cells that are known to be equal to this cell because of the symmetry.
     ///
     /// The pointers in this vector should be non-null.
-    pub(crate) symmetry: Vec<*const LifeCell>,
+    pub(crate) symmetry: Vec<*const Self>,
 
     /// The next cell to be searched according to the search order.
-    pub(crate) next: *const LifeCell,
+    pub(crate) next: *const Self,
 
     /// Whether the cell is on the front, i.e. the first row or column, depending on the search order.
     ///
@@ -56,6 +60,7 @@ impl LifeCell {
     pub(crate) fn new(generation: i32) -> Self {
         Self {
             generation,
+            #[cfg(not(feature = "packed-state"))]
             state: Cell::new(None),
             descriptor: Cell::default(),
             predecessor: std::ptr::null(),
@@ -68,12 +73,13 @@ impl LifeCell {
     }
 
     /// Get the state of the cell.
-    pub(crate) fn state(&self) -> Option<CellState> {
+    #[cfg(not(feature = "packed-state"))]
+    pub(crate) const fn state(&self) -> Option<CellState> {
         self.state.get()
     }
 
     /// Get the neighborhood descriptor of the cell.
-    pub(crate) fn descriptor(&self) -> Descriptor {
+    pub(crate) const fn descriptor(&self) -> Descriptor {
         self.descriptor.get()
     }
 
@@ -129,3 +135,55 @@ impl LifeCell {
         self.descriptor.set(descriptor);
     }
 }
+
+/// The states of all the cells in a world, packed into 2 bits per cell.
+///
+/// With the `packed-state` feature, this replaces the `state` field of [`LifeCell`].
+/// The states are indexed by the position of the cell in the world, so that they take
+/// a contiguous block of memory, 32 cells per word, apart from the much larger cells.
+///
+/// A state is stored as its discriminant, and `0b00` means the cell is unknown.
+#[cfg(feature = "packed-state")]
+#[derive(Debug)]
+pub(crate) struct PackedStates {
+    /// The words containing the states.
+    words: Box<[Cell<u64>]>,
+}
+
+#[cfg(feature = "packed-state")]
+impl PackedStates {
+    /// The number of states in a word.
+    const STATES_PER_WORD: usize = u64::BITS as usize / 2;
+
+    /// Create the states of `len` cells, all unknown.
+    pub(crate) fn new(len: usize) -> Self {
+        Self {
+            words: (0..len.div_ceil(Self::STATES_PER_WORD))
+                .map(|_| Cell::new(0))
+                .collect(),
+        }
+    }
+
+    /// The number of bytes taken by the states of `len` cells.
+    pub(crate) const fn bytes(len: usize) -> usize {
+        len.div_ceil(Self::STATES_PER_WORD) * size_of::<u64>()
+    }
+
+    /// Get the state of the cell at the given index.
+    pub(crate) fn get(&self, index: usize) -> Option<CellState> {
+        let word = self.words[index / Self::STATES_PER_WORD].get();
+        match (word >> (index % Self::STATES_PER_WORD * 2)) & 0b11 {
+            0b01 => Some(CellState::Dead),
+            0b10 => Some(CellState::Alive),
+            _ => None,
+        }
+    }
+
+    /// Set the state of the cell at the given index.
+    pub(crate) fn set(&self, index: usize, state: Option<CellState>) {
+        let word = &self.words[index / Self::STATES_PER_WORD];
+        let shift = index % Self::STATES_PER_WORD * 2;
+        let bits = state.map_or(0, |state| state as u64);
+        word.set((word.get() & !(0b11 << shift)) | (bits << shift));
+    }
+}
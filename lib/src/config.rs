@@ -1,22 +1,31 @@
 use crate::{
-    error::ConfigError,
-    rule::MAX_NEIGHBORHOOD_SIZE,
+    error::{ConfigError, SquareReason},
+    rule::parse_supported_rule,
     symmetry::{Symmetry, Transformation},
+    world::Coord,
 };
-use ca_rules2::{Neighborhood, NeighborhoodType, Rule};
+use ca_rules2::Rule;
 #[cfg(feature = "clap")]
 use clap::{Args, ValueEnum};
 #[cfg(feature = "documented")]
 use documented::{Documented, DocumentedFields};
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
-use std::str::FromStr;
+use std::{
+    fmt::{self, Formatter},
+    str::FromStr,
+};
 use strum::{Display, EnumIter, EnumString, IntoEnumIterator};
 
 /// Search order.
 ///
 /// This is used to determine how we find the next unknown cell.
+///
+/// It can be parsed from a string with [`FromStr`], case-insensitively,
+/// using the same names and aliases as the command line: `row` or `r`,
+/// `column` or `c`, and `diagonal` or `d`.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Display, EnumIter, EnumString)]
+#[strum(ascii_case_insensitive)]
 #[cfg_attr(feature = "clap", derive(ValueEnum))]
 #[cfg_attr(feature = "documented", derive(Documented, DocumentedFields))]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -30,7 +39,7 @@ pub enum SearchOrder {
     /// ```
     #[cfg_attr(feature = "clap", value(name = "row", alias = "r"))]
     #[cfg_attr(feature = "serde", serde(rename = "row"))]
-    #[strum(serialize = "row")]
+    #[strum(to_string = "row", serialize = "r")]
     RowFirst,
 
     /// Search in column-major order.
@@ -42,7 +51,7 @@ pub enum SearchOrder {
     /// ```
     #[cfg_attr(feature = "clap", value(name = "column", alias = "c"))]
     #[cfg_attr(feature = "serde", serde(rename = "column"))]
-    #[strum(serialize = "column")]
+    #[strum(to_string = "column", serialize = "c")]
     ColumnFirst,
 
     /// Search in diagonal order.
@@ -58,7 +67,7 @@ pub enum SearchOrder {
     /// This requires the world to be square.
     #[cfg_attr(feature = "clap", value(name = "diagonal", alias = "d"))]
     #[cfg_attr(feature = "serde", serde(rename = "diagonal"))]
-    #[strum(serialize = "diagonal")]
+    #[strum(to_string = "diagonal", serialize = "d")]
     Diagonal,
 }
 
@@ -73,7 +82,12 @@ impl SearchOrder {
 /// How to guess the state of an unknown cell.
 ///
 /// The default is [`Dead`](NewState::Dead).
+///
+/// It can be parsed from a string with [`FromStr`], case-insensitively,
+/// using the same names and aliases as the command line: `alive` or `a`,
+/// `dead` or `d`, and `random` or `r`.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Display, EnumIter, EnumString)]
+#[strum(ascii_case_insensitive)]
 #[cfg_attr(feature = "clap", derive(ValueEnum))]
 #[cfg_attr(feature = "documented", derive(Documented, DocumentedFields))]
 #[cfg_attr(
@@ -84,17 +98,20 @@ impl SearchOrder {
 pub enum NewState {
     /// Guess that the cell is alive.
     #[cfg_attr(feature = "clap", value(alias = "a"))]
+    #[strum(to_string = "Alive", serialize = "a")]
     Alive,
 
     /// Guess that the cell is dead.
     #[default]
     #[cfg_attr(feature = "clap", value(alias = "d"))]
+    #[strum(to_string = "Dead", serialize = "d")]
     Dead,
 
     /// Make a random guess.
     ///
     /// The probability of each state is 50%.
     #[cfg_attr(feature = "clap", value(alias = "r"))]
+    #[strum(to_string = "Random", serialize = "r")]
     Random,
 }
 
@@ -106,6 +123,114 @@ impl NewState {
     }
 }
 
+/// What lies beyond the edges of the world.
+///
+/// The default is [`Dead`](Boundary::Dead).
+///
+/// It can be parsed from a string with [`FromStr`], case-insensitively:
+/// `dead` or `d`, and `torus` or `t`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Display, EnumIter, EnumString)]
+#[strum(ascii_case_insensitive)]
+#[cfg_attr(feature = "clap", derive(ValueEnum))]
+#[cfg_attr(feature = "documented", derive(Documented, DocumentedFields))]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(rename_all = "lowercase")
+)]
+pub enum Boundary {
+    /// The world is surrounded by dead cells.
+    #[default]
+    #[cfg_attr(feature = "clap", value(alias = "d"))]
+    #[strum(to_string = "Dead", serialize = "d")]
+    Dead,
+
+    /// The opposite edges of the world are glued together.
+    ///
+    /// A pattern on a torus tiles the plane periodically, so this searches for agars.
+    /// The world is the fundamental domain of the tiling, and there is no border.
+    #[cfg_attr(feature = "clap", value(alias = "t"))]
+    #[strum(to_string = "Torus", serialize = "t")]
+    Torus,
+}
+
+impl Boundary {
+    /// An iterator over all possible [`Boundary`]s.
+    #[inline]
+    pub fn iter() -> impl Iterator<Item = Self> {
+        <Self as IntoEnumIterator>::iter()
+    }
+}
+
+/// How to relax the configuration when the search finds no solution.
+///
+/// See [`relax_on_no_solution`](Config::relax_on_no_solution) for more details.
+///
+/// It can be parsed from a string with [`FromStr`], in the same format as its
+/// [`Display`](fmt::Display) implementation: `max-population:{n}`, `diagonal-width:{n}`,
+/// or `size:{n}`, where `{n}` is the amount to relax by. If `:{n}` is omitted, it is `1`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(rename_all = "snake_case")
+)]
+pub enum RelaxStrategy {
+    /// Increase the [`max_population`](Config::max_population) by the given amount.
+    MaxPopulation(usize),
+
+    /// Increase the [`diagonal_width`](Config::diagonal_width) by the given amount,
+    /// up to the width of the world.
+    DiagonalWidth(u32),
+
+    /// Increase the size of the world the given number of times, each time as in
+    /// [`World::increase_world_size`](crate::World::increase_world_size).
+    Size(u32),
+}
+
+impl RelaxStrategy {
+    /// The amount to relax by.
+    const fn amount(self) -> usize {
+        match self {
+            Self::MaxPopulation(n) => n,
+            Self::DiagonalWidth(n) | Self::Size(n) => n as usize,
+        }
+    }
+}
+
+impl fmt::Display for RelaxStrategy {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MaxPopulation(n) => write!(f, "max-population:{n}"),
+            Self::DiagonalWidth(n) => write!(f, "diagonal-width:{n}"),
+            Self::Size(n) => write!(f, "size:{n}"),
+        }
+    }
+}
+
+impl FromStr for RelaxStrategy {
+    type Err = ConfigError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (name, amount) = s.split_once(':').unwrap_or((s, "1"));
+        let amount = amount
+            .trim()
+            .parse()
+            .map_err(|_| ConfigError::InvalidRelaxStrategy)?;
+
+        match name.trim().to_ascii_lowercase().as_str() {
+            "max-population" => Ok(Self::MaxPopulation(amount)),
+            "diagonal-width" => Ok(Self::DiagonalWidth(
+                u32::try_from(amount).map_err(|_| ConfigError::InvalidRelaxStrategy)?,
+            )),
+            "size" => Ok(Self::Size(
+                u32::try_from(amount).map_err(|_| ConfigError::InvalidRelaxStrategy)?,
+            )),
+            _ => Err(ConfigError::InvalidRelaxStrategy),
+        }
+    }
+}
+
 /// The configuration of the world.
 #[derive(Debug, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "clap", derive(Args))]
@@ -120,7 +245,9 @@ pub struct Config {
     ///   Both Moore and von Neumann neighborhoods are supported.
     ///
     /// - [Higher-range outer-totalistic Life-like rules](https://conwaylife.com/wiki/Higher-range_outer-totalistic_cellular_automaton).
-    ///   Currently, the program only supports Moore, von Neumann, and cross neighborhoods.
+    ///   Currently, the program only supports Moore, von Neumann, cross and hash neighborhoods,
+    ///   and custom totalistic neighborhoods (e.g. `N[(-2,0),(2,0),(0,-2),(0,2)]`)
+    ///   that are invariant under all the rotations and reflections of the square grid.
     ///   The size of the neighborhood must be at most 24.
     ///   Rules with more than 2 states are not supported.
     ///
@@ -140,6 +267,23 @@ pub struct Config {
     #[cfg_attr(feature = "clap", arg(default_value = "1"))]
     pub period: u32,
 
+    /// Search for patterns whose period divides this bound, instead of exactly
+    /// [`period`](Config::period).
+    ///
+    /// If this is not [`None`], it must be equal to [`period`](Config::period),
+    /// which is the period the world is built with. Otherwise the configuration is rejected
+    /// with [`ConflictingPeriod`](ConfigError::ConflictingPeriod).
+    /// A solution is then accepted whenever its [true period](crate::World::true_period)
+    /// divides the bound. For example, with a bound of 4, still lifes, period 2 and
+    /// period 4 oscillators are all found in a single search.
+    ///
+    /// As in a normal search, a pattern is found once for each of its phases
+    /// that can be the 0-th generation, so an oscillator of period `p'` may be
+    /// found `p'` times.
+    #[cfg_attr(feature = "clap", arg(long))]
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub search_up_to_period: Option<u32>,
+
     /// Horizontal translation of the world.
     ///
     /// The pattern is translated by `dx` cells to the left in each period.
@@ -175,11 +319,26 @@ pub struct Config {
     ///
     /// This is useful for finding diagonal spaceships.
     ///
-    /// If this is not [`None`], then the world must be square.
+    /// If this is not [`None`], then the world must be square, and the diagonal width
+    /// must be at most the width. Since `abs(x - y)` is at most `width - 1` in the world,
+    /// a diagonal width equal to the width does not constrain anything either,
+    /// but it is allowed, so that it can be increased along with the world size.
     #[cfg_attr(feature = "clap", arg(short, long))]
     #[cfg_attr(feature = "serde", serde(default))]
     pub diagonal_width: Option<u32>,
 
+    /// What lies beyond the edges of the world.
+    ///
+    /// If this is [`Torus`](Boundary::Torus), the world must be at least `2r+1` cells wide and high, where `r` is the radius
+    /// of the neighborhood, so that no cell is its own neighbor. A pattern that repeats
+    /// itself horizontally or vertically within the world, i.e., that is also a solution
+    /// on a smaller torus, is not accepted as a solution. A pattern that only repeats itself
+    /// along a diagonal shift is accepted, since it is not a solution on any smaller
+    /// rectangular torus. A torus cannot have a diagonal width.
+    #[cfg_attr(feature = "clap", arg(long, value_enum, default_value = "dead"))]
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub boundary: Boundary,
+
     /// Symmetry of the pattern.
     ///
     /// There are 10 possible symmetries, corresponding to the 10 subgroups of the
@@ -240,6 +399,24 @@ pub struct Config {
     #[cfg_attr(feature = "serde", serde(default))]
     pub seed: Option<u64>,
 
+    /// Restart the search if it makes no progress for this many steps.
+    ///
+    /// The search makes progress when the stack of known cells becomes deeper than ever
+    /// before since the last restart. If it stays shallower for this many consecutive steps,
+    /// the search is probably stuck in a bad branch. Then all guesses are undone, and the
+    /// search starts again from the known cells, with a different state of the random number
+    /// generator. The threshold is doubled after each restart, so that the search can
+    /// still finish and report that there are no more solutions.
+    ///
+    /// This only takes effect when [`new_state`](Config::new_state) is
+    /// [`Random`](NewState::Random), since otherwise the search would make the same guesses
+    /// again. When enumerating several solutions, a solution may be found more than once.
+    ///
+    /// If this is [`None`], the search never restarts.
+    #[cfg_attr(feature = "clap", arg(long))]
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub restart_after: Option<usize>,
+
     /// Upper bound of the population of the pattern.
     ///
     /// If the period is greater than 1, then this is the upper bound of the minimum population
@@ -250,6 +427,37 @@ pub struct Config {
     #[cfg_attr(feature = "serde", serde(default))]
     pub max_population: Option<usize>,
 
+    /// Lower bound of the population of the pattern.
+    ///
+    /// If the period is greater than 1, then this is the lower bound of the minimum population
+    /// among all the generations. In other words, every generation must have at least
+    /// this many living cells.
+    ///
+    /// During the search, a branch is pruned as soon as some generation can no longer
+    /// reach this bound, even if all its unknown cells become alive.
+    ///
+    /// If this is [`None`], then the population is not bounded from below.
+    #[cfg_attr(feature = "clap", arg(long))]
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub min_population: Option<usize>,
+
+    /// The exact population of the pattern.
+    ///
+    /// Unlike [`max_population`](Config::max_population) and
+    /// [`min_population`](Config::min_population), which bound the minimum population
+    /// among all the generations, this requires every generation to have exactly
+    /// this many living cells.
+    ///
+    /// During the search, a branch is pruned as soon as some generation has more living cells,
+    /// or can no longer reach this number even if all its unknown cells become alive.
+    /// A pattern is only accepted as a solution if the population of each generation
+    /// is exactly this number.
+    ///
+    /// If this is [`None`], then the population is not required to be exact.
+    #[cfg_attr(feature = "clap", arg(long))]
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub exact_population: Option<usize>,
+
     /// Whether to reduce the upper bound of the population when a solution is found.
     ///
     /// If this is [`true`], when a solution with population `p` is found, then
@@ -259,10 +467,106 @@ pub struct Config {
     #[cfg_attr(feature = "clap", arg(long))]
     #[cfg_attr(feature = "serde", serde(default))]
     pub reduce_max_population: bool,
+
+    /// Whether to rotate the generations to reduce the search space.
+    ///
+    /// When the translation along the search order is zero, a pattern is still valid if
+    /// we rotate its generations, i.e. the first generation becomes the last, the second
+    /// becomes the first, and so on. So by default, only the first generation is used
+    /// as the front, which excludes such rotated copies of a pattern.
+    ///
+    /// If this is [`false`], this optimization is disabled, and the front is the first row
+    /// or column of every generation. This does not lose any solution, but the search is
+    /// slower, and the same pattern may be found several times in different phases.
+    /// This is mostly useful for debugging.
+    #[cfg_attr(
+        feature = "clap",
+        arg(long = "no-generation-rotation", action = clap::ArgAction::SetFalse)
+    )]
+    #[cfg_attr(
+        feature = "serde",
+        serde(default = "Config::default_allow_generation_rotation")
+    )]
+    pub allow_generation_rotation: bool,
+
+    /// The generation that contains the front, when only one generation is used as the front.
+    ///
+    /// By [rotating the generations](Config::allow_generation_rotation), any generation can be
+    /// moved to the first one, so the front can be placed on the first row or column of any
+    /// generation, instead of the first generation. Choosing the generation may help searches
+    /// with a symmetry or a transformation where the pattern is narrower in some phases.
+    /// When the front reduction does not apply, the whole pattern at this generation
+    /// is used as the front instead.
+    ///
+    /// If this is [`None`], the first generation is used. Otherwise, it must be smaller than
+    /// the period, and the generation rotation must be allowed.
+    #[cfg_attr(feature = "clap", arg(long))]
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub front_generation: Option<u32>,
+
+    /// Whether the pattern must touch all four edges of the world.
+    ///
+    /// If this is [`true`], a solution is only accepted if each of the first row, the last row,
+    /// the first column, and the last column has a living cell in some generation.
+    /// The edges may be touched in different generations.
+    ///
+    /// This excludes patterns that also fit in a smaller bounding box,
+    /// so that the same pattern is not found again with some empty padding.
+    #[cfg_attr(feature = "clap", arg(long))]
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub full_bounding_box: bool,
+
+    /// How to relax the configuration when the search finds no solution.
+    ///
+    /// Front-ends call [`World::grow_or_relax`](crate::World::grow_or_relax) when the search
+    /// finds no solution, which loosens the field named by the strategy, and starts a new search.
+    /// The strategy is written as `max-population:{n}`, `diagonal-width:{n}` or `size:{n}`,
+    /// see [`RelaxStrategy`].
+    ///
+    /// The amount must not be zero. To relax the population or the diagonal width,
+    /// [`max_population`](Config::max_population) or [`diagonal_width`](Config::diagonal_width)
+    /// must be set.
+    ///
+    /// The population or the diagonal width stops being relaxed once it no longer constrains
+    /// anything, i.e., once the population bound reaches the number of cells in a generation,
+    /// or the diagonal width reaches the width. Then the search simply finds no solution.
+    ///
+    /// If this is [`None`], the world size may still be increased, as in
+    /// [`World::increase_world_size`](crate::World::increase_world_size).
+    #[cfg_attr(feature = "clap", arg(long))]
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub relax_on_no_solution: Option<RelaxStrategy>,
+
+    /// Cells that must be alive in a solution.
+    ///
+    /// Unlike setting a cell with [`try_set_known`](crate::World::try_set_known),
+    /// this does not constrain the search. A complete pattern in which any of these cells
+    /// is dead is rejected, and the search backtracks as if it were invalid.
+    /// This is useful when fixing a cell from the start constrains the search too early.
+    ///
+    /// Each cell is given as `x,y,t` on the command line,
+    /// and must be inside the world.
+    #[cfg_attr(feature = "clap", arg(long, value_parser = parse_coord))]
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub require_alive: Vec<Coord>,
+}
+
+/// Parse a cell coordinate in the form `x,y,t` for the command line.
+#[cfg(feature = "clap")]
+fn parse_coord(s: &str) -> Result<Coord, String> {
+    let mut parts = s.split(',').map(|part| part.trim().parse::<i32>());
+    match (parts.next(), parts.next(), parts.next(), parts.next()) {
+        (Some(Ok(x)), Some(Ok(y)), Some(Ok(t)), None) => Ok((x, y, t)),
+        _ => Err(format!("expected `x,y,t`, found `{s}`")),
+    }
 }
 
 impl Config {
     /// Create a new configuration.
+    ///
+    /// Nothing is validated here. A width, height, or period of zero, and any other problem,
+    /// is only reported by [`check`](Config::check), which [`World::new`](crate::World::new)
+    /// calls. Use [`try_new`](Config::try_new) to reject a zero size right away.
     #[inline]
     pub fn new(rule_str: &str, width: u32, height: u32, period: u32) -> Self {
         Self {
@@ -270,19 +574,76 @@ impl Config {
             width,
             height,
             period,
+            search_up_to_period: None,
             dx: 0,
             dy: 0,
             diagonal_width: None,
+            boundary: Boundary::Dead,
             symmetry: Symmetry::C1,
             transformation: Transformation::R0,
             search_order: None,
             new_state: NewState::Dead,
             seed: None,
+            restart_after: None,
             max_population: None,
+            min_population: None,
+            exact_population: None,
             reduce_max_population: false,
+            allow_generation_rotation: true,
+            front_generation: None,
+            full_bounding_box: false,
+            relax_on_no_solution: None,
+            require_alive: Vec::new(),
         }
     }
 
+    /// Create a new configuration, and check that the width, height and period are nonzero.
+    ///
+    /// Return [`InvalidSize`](ConfigError::InvalidSize) if any of them is zero.
+    /// The other fields are not validated, so [`check`](Config::check) is still required,
+    /// e.g., for the rule string and the translations.
+    #[inline]
+    pub fn try_new(
+        rule_str: &str,
+        width: u32,
+        height: u32,
+        period: u32,
+    ) -> Result<Self, ConfigError> {
+        if width == 0 || height == 0 || period == 0 {
+            return Err(ConfigError::InvalidSize);
+        }
+
+        Ok(Self::new(rule_str, width, height, period))
+    }
+
+    /// Create a new configuration to search for still lifes.
+    ///
+    /// The period is 1, and [`full_bounding_box`](Config::full_bounding_box) is enabled,
+    /// so that each still life is only found in the smallest bounding box that contains it.
+    ///
+    /// Period 1 is a fast path of the search: with a single generation, the period of
+    /// a solution needs no checking, and the front is always on that generation,
+    /// whether or not the [generation rotation](Config::allow_generation_rotation) is allowed.
+    #[inline]
+    pub fn still_life(rule_str: &str, width: u32, height: u32) -> Self {
+        Self::new(rule_str, width, height, 1).with_full_bounding_box()
+    }
+
+    /// Set the rule from a parsed [`Rule`].
+    ///
+    /// The [`rule_str`](Config::rule_str) is set to the canonical string of the rule,
+    /// see the [`Display`](std::fmt::Display) implementation of [`Rule`].
+    ///
+    /// If the neighborhood of the rule includes the center cell, the rule is first converted
+    /// to an equivalent outer-totalistic rule with [`Rule::without_center`].
+    #[inline]
+    #[must_use]
+    pub fn with_rule(mut self, rule: Rule) -> Self {
+        let rule = rule.without_center().unwrap_or(rule);
+        self.rule_str = rule.to_string();
+        self
+    }
+
     /// Set horizontal and vertical translations.
     ///
     /// See [`dx`](Config::dx) and [`dy`](Config::dy) for more details.
@@ -304,6 +665,27 @@ impl Config {
         self
     }
 
+    /// Set what lies beyond the edges of the world.
+    ///
+    /// See [`boundary`](Config::boundary) for more details.
+    #[inline]
+    #[must_use]
+    pub const fn with_boundary(mut self, boundary: Boundary) -> Self {
+        self.boundary = boundary;
+        self
+    }
+
+    /// Require a cell to be alive in a solution.
+    ///
+    /// This can be called several times to require several cells.
+    /// See [`require_alive`](Config::require_alive) for more details.
+    #[inline]
+    #[must_use]
+    pub fn with_require_alive(mut self, coord: Coord) -> Self {
+        self.require_alive.push(coord);
+        self
+    }
+
     /// Set the symmetry.
     ///
     /// See [`symmetry`](Config::symmetry) for more details.
@@ -354,6 +736,16 @@ impl Config {
         self
     }
 
+    /// Restart the search if it makes no progress for the given number of steps.
+    ///
+    /// See [`restart_after`](Config::restart_after) for more details.
+    #[inline]
+    #[must_use]
+    pub const fn with_restart_after(mut self, restart_after: usize) -> Self {
+        self.restart_after = Some(restart_after);
+        self
+    }
+
     /// Set the upper bound of the population of the pattern.
     ///
     /// See [`max_population`](Config::max_population) for more details.
@@ -364,6 +756,26 @@ impl Config {
         self
     }
 
+    /// Set the lower bound of the population of the pattern.
+    ///
+    /// See [`min_population`](Config::min_population) for more details.
+    #[inline]
+    #[must_use]
+    pub const fn with_min_population(mut self, min_population: usize) -> Self {
+        self.min_population = Some(min_population);
+        self
+    }
+
+    /// Require every generation to have exactly the given number of living cells.
+    ///
+    /// See [`exact_population`](Config::exact_population) for more details.
+    #[inline]
+    #[must_use]
+    pub const fn with_exact_population(mut self, exact_population: usize) -> Self {
+        self.exact_population = Some(exact_population);
+        self
+    }
+
     /// Enable reducing the upper bound of the population when a solution is found.
     ///
     /// See [`reduce_max_population`](Config::reduce_max_population) for more details.
@@ -374,65 +786,265 @@ impl Config {
         self
     }
 
+    /// Disable the reduction of the search space by rotating the generations.
+    ///
+    /// See [`allow_generation_rotation`](Config::allow_generation_rotation) for more details.
+    #[inline]
+    #[must_use]
+    pub const fn without_generation_rotation(mut self) -> Self {
+        self.allow_generation_rotation = false;
+        self
+    }
+
+    /// Set the generation that contains the front.
+    ///
+    /// See [`front_generation`](Config::front_generation) for more details.
+    #[inline]
+    #[must_use]
+    pub const fn with_front_generation(mut self, front_generation: u32) -> Self {
+        self.front_generation = Some(front_generation);
+        self
+    }
+
+    /// Set how to relax the configuration when the search finds no solution.
+    ///
+    /// See [`relax_on_no_solution`](Config::relax_on_no_solution) for more details.
+    #[inline]
+    #[must_use]
+    pub const fn with_relax_on_no_solution(mut self, strategy: RelaxStrategy) -> Self {
+        self.relax_on_no_solution = Some(strategy);
+        self
+    }
+
+    /// Set the translations and the period from a velocity.
+    ///
+    /// The pattern moves by `dx` cells horizontally and `dy` cells vertically
+    /// every `period` generations. See [`dx`](Config::dx) and [`dy`](Config::dy)
+    /// for the directions.
+    #[inline]
+    #[must_use]
+    pub const fn with_velocity(mut self, dx: i32, dy: i32, period: u32) -> Self {
+        self.dx = dx;
+        self.dy = dy;
+        self.period = period;
+        self
+    }
+
+    /// Set the translations and the period from a velocity string.
+    ///
+    /// The following notations are supported:
+    ///
+    /// - `c/<period>` or `<n>c/<period>`: an orthogonal velocity,
+    ///   the pattern moves by `n` cells vertically in each period.
+    ///   An optional suffix `o` or ` orthogonal` is allowed.
+    /// - `c/<period>d` or `<n>c/<period> diagonal`: a diagonal velocity,
+    ///   the pattern moves by `n` cells both horizontally and vertically in each period.
+    /// - `(<dx>,<dy>)c/<period>`: an arbitrary velocity,
+    ///   the pattern moves by `dx` cells horizontally and `dy` cells vertically in each period.
+    ///
+    /// For example, `c/2o` is period 2 with `dy = 1`, `c/4d` is period 4 with `dx = dy = 1`,
+    /// and `(2,1)c/6` is period 6 with `dx = 2` and `dy = 1`.
+    ///
+    /// Returns [`ConfigError::InvalidVelocity`] if the string cannot be parsed,
+    /// and [`ConfigError::InvalidTranslation`] if the resulting translations
+    /// are not compatible with the [`symmetry`](Config::symmetry).
+    pub fn with_velocity_str(self, velocity: &str) -> Result<Self, ConfigError> {
+        let (dx, dy, period) = parse_velocity(velocity).ok_or(ConfigError::InvalidVelocity)?;
+        let config = self.with_velocity(dx, dy, period);
+
+        if !config.translation_is_valid() {
+            return Err(ConfigError::InvalidTranslation);
+        }
+
+        Ok(config)
+    }
+
+    /// Accept any pattern whose period divides the given bound.
+    ///
+    /// This also sets the [`period`](Config::period) to the bound.
+    ///
+    /// See [`search_up_to_period`](Config::search_up_to_period) for more details.
+    #[inline]
+    #[must_use]
+    pub const fn with_search_up_to_period(mut self, period: u32) -> Self {
+        self.search_up_to_period = Some(period);
+        self.period = period;
+        self
+    }
+
+    /// Require the pattern to touch all four edges of the world.
+    ///
+    /// See [`full_bounding_box`](Config::full_bounding_box) for more details.
+    #[inline]
+    #[must_use]
+    pub const fn with_full_bounding_box(mut self) -> Self {
+        self.full_bounding_box = true;
+        self
+    }
+
+    /// The default value of [`allow_generation_rotation`](Config::allow_generation_rotation)
+    /// when it is missing in a serialized configuration.
+    #[cfg(feature = "serde")]
+    const fn default_allow_generation_rotation() -> bool {
+        true
+    }
+
     /// Whether the configuration requires the world to be square.
     #[inline]
     pub const fn requires_square(&self) -> bool {
-        self.symmetry.requires_square()
-            || self.transformation.requires_square()
-            || self.diagonal_width.is_some()
-            || matches!(self.search_order, Some(SearchOrder::Diagonal))
+        self.square_reason().is_some()
+    }
+
+    /// The first requirement that makes the world square, if any.
+    ///
+    /// The requirements are checked in the following order: the symmetry,
+    /// the transformation, the diagonal width, and the search order.
+    #[inline]
+    pub const fn square_reason(&self) -> Option<SquareReason> {
+        if self.symmetry.requires_square() {
+            Some(SquareReason::Symmetry(self.symmetry))
+        } else if self.transformation.requires_square() {
+            Some(SquareReason::Transformation(self.transformation))
+        } else if self.diagonal_width.is_some() {
+            Some(SquareReason::DiagonalWidth)
+        } else if matches!(self.search_order, Some(SearchOrder::Diagonal)) {
+            Some(SquareReason::DiagonalSearchOrder)
+        } else {
+            None
+        }
     }
 
-    /// Whether the symmetry or the transformation requires the world to have no diagonal width.
+    /// Whether the symmetry, the transformation or the boundary requires the world
+    /// to have no diagonal width.
     #[inline]
     pub const fn requires_no_diagonal_width(&self) -> bool {
         self.symmetry.requires_no_diagonal_width()
             || self.transformation.requires_no_diagonal_width()
+            || matches!(self.boundary, Boundary::Torus)
     }
 
-    /// Whether the translation is compatible with the symmetry.
+    /// Whether the pattern should be kept centered when it is output in RLE format.
+    ///
+    /// This is the case when the configuration has a nontrivial symmetry,
+    /// so that the center of the symmetry stays at the center of the RLE bounding box.
     #[inline]
-    pub const fn translation_is_valid(&self) -> bool {
-        self.symmetry.translation_is_valid(self.dx, self.dy)
+    pub const fn center_symmetric(&self) -> bool {
+        !matches!(self.symmetry, Symmetry::C1)
     }
 
-    /// Try to parse the rule string, and check whether the rule is supported.
-    ///
-    /// Currently, the program supports the following rules:
-    /// - [Outer-totalistic Life-like rules](https://conwaylife.com/wiki/Life-like_cellular_automaton).
-    ///   Both Moore and von Neumann neighborhoods are supported.
-    /// - [Higher-range outer-totalistic Life-like rules](https://conwaylife.com/wiki/Higher-range_outer-totalistic_cellular_automaton).
-    ///   Currently, the program only supports Moore, von Neumann, and cross neighborhoods.
-    ///   The size of the neighborhood must be at most 24.
-    ///   Rules with more than 2 states are not supported.
+    /// Whether the front reduction applies with the given search order.
     ///
-    /// Rules whose birth conditions contain `0` are not supported.
+    /// The front reduction assumes that the first row or column, depending on the
+    /// search order, is not always empty, which greatly reduces the search space.
+    /// It only applies when the symmetry and the transformation preserve that row or column.
+    /// Otherwise, the whole first generation is used as the front instead.
     #[inline]
-    pub fn parse_rule(&self) -> Result<Rule, ConfigError> {
-        let rule = Rule::from_str(&self.rule_str).map_err(|_| ConfigError::InvalidRule)?;
-
-        if rule.contains_b0() || rule.states != 2 {
-            return Err(ConfigError::UnsupportedRule);
+    pub const fn front_reduction_applies(&self, search_order: SearchOrder) -> bool {
+        match search_order {
+            SearchOrder::RowFirst => {
+                self.symmetry.is_subgroup_of(Symmetry::D2H)
+                    && self.transformation.is_element_of(Symmetry::D2H)
+                    && self.diagonal_width.is_none()
+            }
+            SearchOrder::ColumnFirst => {
+                self.symmetry.is_subgroup_of(Symmetry::D2V)
+                    && self.transformation.is_element_of(Symmetry::D2V)
+                    && self.diagonal_width.is_none()
+            }
+            SearchOrder::Diagonal => {
+                self.symmetry.is_subgroup_of(Symmetry::D2D)
+                    && self.transformation.is_element_of(Symmetry::D2D)
+            }
         }
+    }
 
-        if !matches!(rule.neighborhood, Neighborhood::Totalistic(neighborhood_type, _) if neighborhood_type != NeighborhoodType::Hexagonal)
-        {
-            return Err(ConfigError::UnsupportedRule);
+    /// Suggest a better search order if the current one disables the front reduction.
+    ///
+    /// If the [`search_order`](Config::search_order) is set, but the
+    /// [front reduction](Config::front_reduction_applies) does not apply with it,
+    /// return another search order with which the reduction applies, if any.
+    /// The diagonal search order is only suggested for square worlds.
+    ///
+    /// This is only a diagnostic: the search is still correct with the current order,
+    /// but it may be much slower. Front-ends can use it to warn the user.
+    pub fn suggested_search_order(&self) -> Option<SearchOrder> {
+        let search_order = self.search_order?;
+
+        if self.front_reduction_applies(search_order) {
+            return None;
         }
 
-        let neighborhood_size = rule.neighborhood_size();
+        SearchOrder::iter().find(|&order| {
+            order != search_order
+                && (order != SearchOrder::Diagonal || self.width == self.height)
+                && self.front_reduction_applies(order)
+        })
+    }
+
+    /// Whether the translation is compatible with the symmetry.
+    #[inline]
+    pub const fn translation_is_valid(&self) -> bool {
+        self.symmetry.translation_is_valid(self.dx, self.dy)
+    }
+
+    /// The minimal period in which a pattern can move by the translation `(dx, dy)`.
+    ///
+    /// A pattern cannot move faster than the speed of light of the rule, i.e.,
+    /// the radius of the neighborhood per generation, so the period must satisfy
+    /// `radius * period >= max(|dx|, |dy|)`.
+    ///
+    /// Returns [`None`] if the translation cannot be achieved at all,
+    /// i.e., if it is nonzero and the radius is zero.
+    pub fn min_period(&self) -> Result<Option<u32>, ConfigError> {
+        let rule = self.parse_rule()?;
+        Ok(self.min_period_with_radius(rule.radius()))
+    }
+
+    /// The minimal period for the translation, given the radius of the neighborhood.
+    ///
+    /// See [`min_period`](Config::min_period).
+    const fn min_period_with_radius(&self, radius: u32) -> Option<u32> {
+        let distance = if self.dx.unsigned_abs() > self.dy.unsigned_abs() {
+            self.dx.unsigned_abs()
+        } else {
+            self.dy.unsigned_abs()
+        };
 
-        if neighborhood_size > MAX_NEIGHBORHOOD_SIZE {
-            return Err(ConfigError::UnsupportedRule);
+        if distance == 0 {
+            Some(1)
+        } else if radius == 0 {
+            None
+        } else {
+            Some(distance.div_ceil(radius))
         }
+    }
 
-        Ok(rule)
+    /// Try to parse the rule string, and check whether the rule is supported.
+    ///
+    /// See [`rule_str`](Config::rule_str) for the supported rules.
+    #[inline]
+    pub fn parse_rule(&self) -> Result<Rule, ConfigError> {
+        parse_supported_rule(&self.rule_str)
     }
 
     /// Check whether the configuration is valid,
     /// and find a search order if it is not specified.
+    ///
+    /// This is idempotent: checking a configuration that has already been checked
+    /// does not change it. In particular, a search order that is already set,
+    /// either by the user or by an earlier check, is kept, so [`World::new`](crate::World::new)
+    /// keeps the order of a configuration that a user interface has checked.
+    /// If the size or the symmetry is changed afterwards, reset the
+    /// [`search_order`](Config::search_order) to [`None`] to choose it again.
     pub fn check(&mut self) -> Result<(), ConfigError> {
-        self.parse_rule()?;
+        let rule = self.parse_rule()?;
+
+        if self
+            .search_up_to_period
+            .is_some_and(|period| period != self.period)
+        {
+            return Err(ConfigError::ConflictingPeriod);
+        }
 
         if self.width == 0
             || self.height == 0
@@ -446,69 +1058,719 @@ impl Config {
             return Err(ConfigError::InvalidMaxPopulation);
         }
 
-        if self.width != self.height && self.requires_square() {
-            return Err(ConfigError::NotSquare);
+        if self.restart_after.is_some_and(|n| n == 0) {
+            return Err(ConfigError::InvalidRestartAfter);
+        }
+
+        if self
+            .min_population
+            .zip(self.max_population)
+            .is_some_and(|(min, max)| min > max)
+        {
+            return Err(ConfigError::InvalidMinPopulation);
+        }
+
+        if self.exact_population.is_some_and(|exact| {
+            exact == 0
+                || self.min_population.is_some_and(|min| exact < min)
+                || self.max_population.is_some_and(|max| exact > max)
+        }) {
+            return Err(ConfigError::InvalidExactPopulation);
+        }
+
+        if self
+            .front_generation
+            .is_some_and(|t| t >= self.period || !self.allow_generation_rotation)
+        {
+            return Err(ConfigError::InvalidFrontGeneration);
+        }
+
+        if self.relax_on_no_solution.is_some_and(|strategy| {
+            strategy.amount() == 0
+                || matches!(strategy, RelaxStrategy::MaxPopulation(_))
+                    && self.max_population.is_none()
+                || matches!(strategy, RelaxStrategy::DiagonalWidth(_))
+                    && self.diagonal_width.is_none()
+        }) {
+            return Err(ConfigError::InvalidRelaxStrategy);
+        }
+
+        let min_size = 2 * rule.radius() + 1;
+        if self.boundary == Boundary::Torus && (self.width < min_size || self.height < min_size) {
+            return Err(ConfigError::TorusTooSmall { min_size });
+        }
+
+        if self.require_alive.iter().any(|&(x, y, t)| {
+            x < 0
+                || y < 0
+                || t < 0
+                || x as u32 >= self.width
+                || y as u32 >= self.height
+                || t as u32 >= self.period
+        }) {
+            return Err(ConfigError::InvalidRequireAlive);
+        }
+
+        if self.width != self.height {
+            if let Some(because) = self.square_reason() {
+                return Err(ConfigError::NotSquare { because });
+            }
         }
 
         if self.diagonal_width.is_some() && self.requires_no_diagonal_width() {
             return Err(ConfigError::HasDiagonalWidth);
         }
 
+        if let Some(diagonal_width) = self.diagonal_width.filter(|&d| d > self.width) {
+            return Err(ConfigError::DiagonalWidthTooLarge {
+                diagonal_width,
+                width: self.width,
+            });
+        }
+
         if !self.translation_is_valid() {
             return Err(ConfigError::InvalidTranslation);
         }
 
+        match self.min_period_with_radius(rule.radius()) {
+            Some(min_period) if self.period >= min_period => {}
+            min_period => {
+                return Err(ConfigError::VelocityTooFast {
+                    min_period: min_period.unwrap_or(0),
+                })
+            }
+        }
+
         // If the search order is not specified, determine it automatically.
         if self.search_order.is_none() {
-            // If the world is symmetric with respect to horizontal reflection,
-            // we only need to search the left half of the world.
-            let width = if self.transformation == Transformation::S2
-                || Transformation::S2.is_element_of(self.symmetry)
-            {
-                (self.width + 1) / 2
-            } else {
-                self.width
-            };
+            self.search_order = Some(self.auto_search_order(|_| {}));
+        }
 
-            // If the world is symmetric with respect to vertical reflection,
-            // we only need to search the upper half of the world.
-            let height = if self.transformation == Transformation::S0
-                || Transformation::S0.is_element_of(self.symmetry)
-            {
-                (self.height + 1) / 2
-            } else {
-                self.height
-            };
+        Ok(())
+    }
 
-            // If the world is symmetric with respect to diagonal reflection,
-            // we only need to search the lower triangle of the world.
-            let diagonal_width = if self.transformation == Transformation::S1
-                || Transformation::S1.is_element_of(self.symmetry)
-            {
-                self.diagonal_width.or(Some(self.width))
-            } else {
-                self.diagonal_width.map(|d| 2 * d + 1)
-            };
+    /// Determine the search order automatically, as [`check`](Config::check) does
+    /// when the [`search_order`](Config::search_order) is not specified.
+    ///
+    /// Each step of the decision is described to `note`, for
+    /// [`explain_search_order`](Config::explain_search_order).
+    fn auto_search_order(&self, mut note: impl FnMut(fmt::Arguments<'_>)) -> SearchOrder {
+        // If the world is symmetric with respect to horizontal reflection,
+        // we only need to search the left half of the world.
+        let width = if self.transformation == Transformation::S2
+            || Transformation::S2.is_element_of(self.symmetry)
+        {
+            let width = self.width.div_ceil(2);
+            note(format_args!(
+                "The pattern is symmetric under horizontal reflection, \
+                 so only the left half is searched: the effective width is {width}."
+            ));
+            width
+        } else {
+            note(format_args!("The effective width is {}.", self.width));
+            self.width
+        };
+
+        // If the world is symmetric with respect to vertical reflection,
+        // we only need to search the upper half of the world.
+        let height = if self.transformation == Transformation::S0
+            || Transformation::S0.is_element_of(self.symmetry)
+        {
+            let height = self.height.div_ceil(2);
+            note(format_args!(
+                "The pattern is symmetric under vertical reflection, \
+                 so only the upper half is searched: the effective height is {height}."
+            ));
+            height
+        } else {
+            note(format_args!("The effective height is {}.", self.height));
+            self.height
+        };
+
+        // If the world is symmetric with respect to diagonal reflection,
+        // we only need to search the lower triangle of the world.
+        let diagonal_width = if self.transformation == Transformation::S1
+            || Transformation::S1.is_element_of(self.symmetry)
+        {
+            let diagonal_width = self.diagonal_width.unwrap_or(self.width);
+            note(format_args!(
+                "The pattern is symmetric under diagonal reflection, \
+                 so only the lower triangle is searched: the effective diagonal width \
+                 is {diagonal_width}."
+            ));
+            Some(diagonal_width)
+        } else if let Some(d) = self.diagonal_width {
+            let diagonal_width = 2 * d + 1;
+            note(format_args!(
+                "The diagonal width {d} allows a band of {diagonal_width} cells \
+                 across the diagonal: the effective diagonal width is {diagonal_width}."
+            ));
+            Some(diagonal_width)
+        } else {
+            note(format_args!(
+                "There is no diagonal width, and no diagonal reflection, \
+                 so the diagonal order is not considered."
+            ));
+            None
+        };
+
+        // The shortest edge should be searched first.
+        if let Some(d) = diagonal_width.filter(|&d| d <= width && d <= height) {
+            note(format_args!(
+                "The effective diagonal width {d} is at most the effective width {width} \
+                 and height {height}, so the diagonal order is chosen."
+            ));
+            SearchOrder::Diagonal
+        } else {
+            if let Some(d) = diagonal_width {
+                note(format_args!(
+                    "The effective diagonal width {d} is larger than the effective width \
+                     {width} or height {height}, so the diagonal order is not chosen."
+                ));
+            }
 
-            // The shortest edge should be searched first.
-            let search_order = if diagonal_width.is_some_and(|d| d <= width && d <= height) {
-                SearchOrder::Diagonal
-            } else if width < height {
+            if width < height {
+                note(format_args!(
+                    "The effective width {width} is smaller than the effective height {height}, \
+                     so the rows are shorter, and the row-first order is chosen."
+                ));
                 SearchOrder::RowFirst
             } else if width > height {
+                note(format_args!(
+                    "The effective width {width} is larger than the effective height {height}, \
+                     so the columns are shorter, and the column-first order is chosen."
+                ));
                 SearchOrder::ColumnFirst
-            } else {
+            } else if self.dx.abs() < self.dy.abs() {
                 // If the world is square, check the translations.
-                if self.dx.abs() < self.dy.abs() {
-                    SearchOrder::RowFirst
-                } else {
-                    SearchOrder::ColumnFirst
-                }
+                note(format_args!(
+                    "The effective width and height are equal, and the pattern moves \
+                     more vertically than horizontally, so the row-first order is chosen."
+                ));
+                SearchOrder::RowFirst
+            } else {
+                note(format_args!(
+                    "The effective width and height are equal, and the pattern does not move \
+                     more vertically than horizontally, so the column-first order is chosen."
+                ));
+                SearchOrder::ColumnFirst
+            }
+        }
+    }
+
+    /// Explain how the search order is chosen automatically, in human-readable sentences,
+    /// one per line.
+    ///
+    /// This recomputes the same decision as [`check`](Config::check) does when the
+    /// [`search_order`](Config::search_order) is not specified: the effective width and height
+    /// are halved by reflective symmetries, the diagonal order is chosen if the effective
+    /// diagonal width is not larger than both of them, and otherwise the shorter edge is
+    /// searched first. This helps to understand an unexpected choice, e.g., of the
+    /// diagonal order, and why a search is slow.
+    ///
+    /// If the search order is already set, either explicitly or by a previous call to
+    /// [`check`](Config::check), the explanation starts with a line saying so,
+    /// and the automatic choice is still explained.
+    pub fn explain_search_order(&self) -> String {
+        let mut lines = Vec::new();
+        let search_order = self.auto_search_order(|note| lines.push(note.to_string()));
+
+        if let Some(current) = self.search_order {
+            let line = if current == search_order {
+                format!("The search order is set to {current}, the same as the automatic choice.")
+            } else {
+                format!(
+                    "The search order is set to {current}, \
+                     instead of the automatic choice {search_order}."
+                )
             };
+            lines.insert(0, line);
+        }
+
+        lines.join("\n")
+    }
+}
+
+/// Parse a velocity string into `(dx, dy, period)`.
+///
+/// See [`Config::with_velocity_str`] for the format.
+fn parse_velocity(velocity: &str) -> Option<(i32, i32, u32)> {
+    let velocity = velocity.trim();
+
+    let (speed, period) = velocity.split_once("c/")?;
 
-            self.search_order = Some(search_order);
+    // The period may be followed by a direction.
+    let digits = period
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(period.len());
+    let (period, direction) = period.split_at(digits);
+    let period: u32 = period.parse().ok()?;
+    let diagonal = match direction.trim() {
+        "" | "o" | "orthogonal" => false,
+        "d" | "diagonal" => true,
+        _ => return None,
+    };
+
+    let (dx, dy) = if let Some(translations) = speed.strip_prefix('(') {
+        // An explicit velocity does not take a direction.
+        if !direction.is_empty() {
+            return None;
+        }
+        let (dx, dy) = translations.strip_suffix(')')?.split_once(',')?;
+        (dx.trim().parse().ok()?, dy.trim().parse().ok()?)
+    } else {
+        let n = if speed.is_empty() {
+            1
+        } else {
+            speed.parse().ok()?
+        };
+        if diagonal {
+            (n, n)
+        } else {
+            (0, n)
+        }
+    };
+
+    Some((dx, dy, period))
+}
+
+/// A compact one-line representation of the configuration.
+///
+/// The format is `<rule> <width>x<height>p<period>`, followed by
+/// `dx=<dx>`, `dy=<dy>`, `sym=<symmetry>`, `trans=<transformation>`, `dw=<diagonal width>`
+/// and `bd=<boundary>`, each of which is omitted if it has the default value. For example:
+///
+/// ```plaintext
+/// B3/S23 16x16p3 dx=1 sym=D2|
+/// ```
+///
+/// Only the fields that define the shape of the search space are included.
+/// Other fields, such as the search order and the population bound, are not.
+/// The string can be parsed back with [`FromStr`].
+impl std::fmt::Display for Config {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} {}x{}p{}",
+            self.rule_str, self.width, self.height, self.period
+        )?;
+
+        if self.dx != 0 {
+            write!(f, " dx={}", self.dx)?;
+        }
+
+        if self.dy != 0 {
+            write!(f, " dy={}", self.dy)?;
+        }
+
+        if self.symmetry != Symmetry::C1 {
+            write!(f, " sym={}", self.symmetry)?;
+        }
+
+        if self.transformation != Transformation::R0 {
+            write!(f, " trans={}", self.transformation)?;
+        }
+
+        if let Some(diagonal_width) = self.diagonal_width {
+            write!(f, " dw={diagonal_width}")?;
+        }
+
+        if self.boundary != Boundary::Dead {
+            write!(f, " bd={}", self.boundary)?;
         }
 
         Ok(())
     }
 }
+
+/// Parse a configuration from its compact one-line representation.
+///
+/// See the [`Display`](std::fmt::Display) implementation for the format.
+/// Fields that are not included in the string have their default values.
+///
+/// The configuration is not [checked](Config::check).
+impl FromStr for Config {
+    type Err = ConfigError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let tokens: Vec<&str> = s.split_whitespace().collect();
+
+        // The rule string may contain whitespace,
+        // so it ends right before the first token that looks like a size.
+        let parse_size = |token: &str| -> Option<(u32, u32, u32)> {
+            let (width, rest) = token.split_once('x')?;
+            let (height, period) = rest.split_once('p')?;
+            Some((
+                width.parse().ok()?,
+                height.parse().ok()?,
+                period.parse().ok()?,
+            ))
+        };
+
+        let (index, (width, height, period)) = tokens
+            .iter()
+            .enumerate()
+            .skip(1)
+            .find_map(|(i, token)| parse_size(token).map(|size| (i, size)))
+            .ok_or(ConfigError::InvalidSpec)?;
+
+        let mut config = Self::new(&tokens[..index].join(" "), width, height, period);
+
+        for token in &tokens[index + 1..] {
+            let (key, value) = token.split_once('=').ok_or(ConfigError::InvalidSpec)?;
+
+            match key {
+                "dx" => config.dx = value.parse().map_err(|_| ConfigError::InvalidSpec)?,
+                "dy" => config.dy = value.parse().map_err(|_| ConfigError::InvalidSpec)?,
+                "sym" => {
+                    config.symmetry = value.parse().map_err(|_| ConfigError::InvalidSpec)?;
+                }
+                "trans" => {
+                    config.transformation = value.parse().map_err(|_| ConfigError::InvalidSpec)?;
+                }
+                "dw" => {
+                    config.diagonal_width =
+                        Some(value.parse().map_err(|_| ConfigError::InvalidSpec)?);
+                }
+                "bd" => {
+                    config.boundary = value.parse().map_err(|_| ConfigError::InvalidSpec)?;
+                }
+                _ => return Err(ConfigError::InvalidSpec),
+            }
+        }
+
+        Ok(config)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display_from_str() {
+        let configs = [
+            Config::new("B3/S23", 16, 16, 1),
+            Config::new("R3,C2,S2,B3,N+", 20, 10, 4).with_translations(1, 0),
+            Config::new("B3/S23", 8, 8, 2)
+                .with_symmetry(Symmetry::D2V)
+                .with_transformation(Transformation::S2)
+                .with_translations(0, -1),
+            Config::new("B36/S23", 12, 12, 4)
+                .with_translations(1, 1)
+                .with_symmetry(Symmetry::D2D)
+                .with_diagonal_width(5),
+            Config::new("B3/S23", 6, 6, 1).with_boundary(Boundary::Torus),
+        ];
+
+        for config in configs {
+            let spec = config.to_string();
+            assert_eq!(spec.parse::<Config>().unwrap(), config, "{spec}");
+        }
+
+        assert_eq!(
+            Config::new("B3/S23", 16, 16, 3)
+                .with_translations(1, 0)
+                .with_symmetry(Symmetry::D2H)
+                .to_string(),
+            "B3/S23 16x16p3 dx=1 sym=D2|"
+        );
+
+        assert!("B3/S23".parse::<Config>().is_err());
+        assert!("B3/S23 16x16p1 foo=1".parse::<Config>().is_err());
+        assert!("B3/S23 16x16p1 sym=D3".parse::<Config>().is_err());
+    }
+
+    #[test]
+    fn test_search_order_new_state_from_str() {
+        for (strings, search_order) in [
+            (["row", "r", "Row"], SearchOrder::RowFirst),
+            (["column", "c", "COLUMN"], SearchOrder::ColumnFirst),
+            (["diagonal", "d", "D"], SearchOrder::Diagonal),
+        ] {
+            for s in strings {
+                assert_eq!(s.parse::<SearchOrder>().unwrap(), search_order, "{s}");
+            }
+            assert_eq!(search_order.to_string().parse(), Ok(search_order));
+        }
+        assert!("x".parse::<SearchOrder>().is_err());
+
+        for (strings, new_state) in [
+            (["alive", "a", "Alive"], NewState::Alive),
+            (["dead", "d", "DEAD"], NewState::Dead),
+            (["random", "r", "R"], NewState::Random),
+        ] {
+            for s in strings {
+                assert_eq!(s.parse::<NewState>().unwrap(), new_state, "{s}");
+            }
+            assert_eq!(new_state.to_string().parse(), Ok(new_state));
+        }
+        assert!("x".parse::<NewState>().is_err());
+    }
+
+    #[cfg(feature = "clap")]
+    #[test]
+    fn test_from_str_matches_clap() {
+        for search_order in SearchOrder::iter() {
+            let value = search_order.to_possible_value().unwrap();
+            for name in value.get_name_and_aliases() {
+                assert_eq!(name.parse(), Ok(search_order), "{name}");
+            }
+        }
+        for new_state in NewState::iter() {
+            let value = new_state.to_possible_value().unwrap();
+            for name in value.get_name_and_aliases() {
+                assert_eq!(name.parse(), Ok(new_state), "{name}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_suggested_search_order() {
+        let config = Config::new("B3/S23", 16, 16, 1).with_symmetry(Symmetry::D2V);
+
+        // The search order is not set yet.
+        assert_eq!(config.suggested_search_order(), None);
+
+        let config = config.with_search_order(SearchOrder::RowFirst);
+        assert!(!config.front_reduction_applies(SearchOrder::RowFirst));
+        assert_eq!(
+            config.suggested_search_order(),
+            Some(SearchOrder::ColumnFirst)
+        );
+
+        let config = config.with_search_order(SearchOrder::ColumnFirst);
+        assert_eq!(config.suggested_search_order(), None);
+
+        // No search order can keep the reduction with a rotational symmetry.
+        let config = config.with_symmetry(Symmetry::C4);
+        assert_eq!(config.suggested_search_order(), None);
+    }
+
+    #[test]
+    fn test_diagonal_width_too_large() {
+        let mut config = Config::new("B3/S23", 8, 8, 4)
+            .with_translations(1, 1)
+            .with_diagonal_width(8);
+        assert!(config.clone().check().is_ok());
+
+        config.diagonal_width = Some(9);
+        let err = config.check().unwrap_err();
+        assert!(matches!(
+            err,
+            ConfigError::DiagonalWidthTooLarge {
+                diagonal_width: 9,
+                width: 8,
+            }
+        ));
+        assert_eq!(
+            err.to_string(),
+            "The diagonal width 9 is larger than the width 8 of the world"
+        );
+    }
+
+    #[test]
+    fn test_check_idempotent() {
+        let mut config = Config::new("B3/S23", 16, 8, 4)
+            .with_translations(0, 1)
+            .with_search_up_to_period(2);
+        config.check().unwrap();
+        assert_eq!(config.search_order, Some(SearchOrder::ColumnFirst));
+        assert_eq!(config.period, 2);
+
+        let checked = config.clone();
+        config.check().unwrap();
+        assert_eq!(config, checked);
+
+        // A resolved order is kept, even if it would be chosen differently now.
+        config.width = 4;
+        config.check().unwrap();
+        assert_eq!(config.search_order, Some(SearchOrder::ColumnFirst));
+
+        let world = crate::World::new(config.clone()).unwrap();
+        assert_eq!(world.config(), &config);
+    }
+
+    #[test]
+    fn test_conflicting_period() {
+        let mut config = Config::new("B3/S23", 4, 4, 2).with_search_up_to_period(4);
+        assert_eq!(config.period, 4);
+        config.check().unwrap();
+
+        // The period is not overwritten by the bound.
+        config.period = 2;
+        assert!(matches!(
+            config.check(),
+            Err(ConfigError::ConflictingPeriod)
+        ));
+        assert_eq!(config.period, 2);
+    }
+
+    #[test]
+    fn test_explain_search_order() {
+        let config = Config::new("B3/S23", 16, 16, 1).with_symmetry(Symmetry::D2D);
+        let explanation = config.explain_search_order();
+        assert!(explanation.contains("diagonal reflection"));
+        assert!(explanation.ends_with("so the diagonal order is chosen."));
+
+        let mut config = Config::new("B3/S23", 8, 16, 1);
+        assert!(config
+            .explain_search_order()
+            .ends_with("the row-first order is chosen."));
+
+        // The explanation agrees with `check`.
+        config.check().unwrap();
+        assert_eq!(config.search_order, Some(SearchOrder::RowFirst));
+        assert!(config
+            .explain_search_order()
+            .starts_with("The search order is set to row, the same as the automatic choice."));
+
+        config.search_order = Some(SearchOrder::ColumnFirst);
+        assert!(config.explain_search_order().starts_with(
+            "The search order is set to column, instead of the automatic choice row."
+        ));
+    }
+
+    #[test]
+    fn test_try_new() {
+        assert_eq!(
+            Config::try_new("B3/S23", 16, 8, 4).unwrap(),
+            Config::new("B3/S23", 16, 8, 4)
+        );
+
+        for (width, height, period) in [(0, 8, 4), (16, 0, 4), (16, 8, 0)] {
+            assert!(matches!(
+                Config::try_new("B3/S23", width, height, period),
+                Err(ConfigError::InvalidSize)
+            ));
+        }
+
+        // Only the size is validated.
+        assert!(Config::try_new("invalid", 16, 8, 4).is_ok());
+    }
+
+    #[test]
+    fn test_not_square() {
+        let mut config = Config::new("B3/S23", 16, 8, 1);
+        assert!(config.clone().check().is_ok());
+
+        config.symmetry = Symmetry::D8;
+        let err = config.clone().check().unwrap_err();
+        assert!(matches!(
+            err,
+            ConfigError::NotSquare {
+                because: SquareReason::Symmetry(Symmetry::D8)
+            }
+        ));
+        assert!(err.to_string().contains("the symmetry is D8"));
+
+        config.symmetry = Symmetry::C1;
+        config.transformation = Transformation::R1;
+        assert!(matches!(
+            config.clone().check(),
+            Err(ConfigError::NotSquare {
+                because: SquareReason::Transformation(Transformation::R1)
+            })
+        ));
+
+        config.transformation = Transformation::R0;
+        config.diagonal_width = Some(4);
+        assert!(matches!(
+            config.clone().check(),
+            Err(ConfigError::NotSquare {
+                because: SquareReason::DiagonalWidth
+            })
+        ));
+
+        config.diagonal_width = None;
+        config.search_order = Some(SearchOrder::Diagonal);
+        assert!(matches!(
+            config.check(),
+            Err(ConfigError::NotSquare {
+                because: SquareReason::DiagonalSearchOrder
+            })
+        ));
+    }
+
+    #[test]
+    fn test_with_rule() {
+        let rule = Rule::from_str("R3,C2,S2,B3,N+").unwrap();
+        let config = Config::new("B3/S23", 16, 16, 1).with_rule(rule.clone());
+        assert_eq!(config.rule_str, "R3,C2,S2,B3,N+");
+        assert_eq!(config.parse_rule().unwrap(), rule);
+
+        // The rule string is canonical.
+        let rule = Rule::from_str("b32s3").unwrap();
+        let config = config.with_rule(rule);
+        assert_eq!(config.rule_str, "B23/S3");
+    }
+
+    #[test]
+    fn test_velocity_too_fast() {
+        let config = Config::new("B3/S23", 16, 16, 1).with_translations(3, 0);
+        assert_eq!(config.min_period().unwrap(), Some(3));
+        assert!(matches!(
+            config.clone().check(),
+            Err(ConfigError::VelocityTooFast { min_period: 3 })
+        ));
+
+        let mut config = config;
+        config.period = 3;
+        assert!(config.check().is_ok());
+
+        // The default rule has a neighborhood of radius 3.
+        let mut config = Config::new("R3,C2,S2,B3,N+", 16, 16, 2).with_translations(-6, 5);
+        assert_eq!(config.min_period().unwrap(), Some(2));
+        assert!(config.check().is_ok());
+
+        let config = Config::new("B3/S23", 16, 16, 1);
+        assert_eq!(config.min_period().unwrap(), Some(1));
+    }
+
+    #[test]
+    fn test_velocity_str() {
+        let config = Config::new("B3/S23", 16, 16, 1);
+
+        let velocities = [
+            ("c/2", (0, 1, 2)),
+            ("c/3o", (0, 1, 3)),
+            ("2c/5 orthogonal", (0, 2, 5)),
+            ("c/4d", (1, 1, 4)),
+            ("c/12 diagonal", (1, 1, 12)),
+            ("(2,1)c/6", (2, 1, 6)),
+            ("(-1, 0)c/3", (-1, 0, 3)),
+        ];
+
+        for (velocity, (dx, dy, period)) in velocities {
+            let config = config.clone().with_velocity_str(velocity).unwrap();
+            assert_eq!((config.dx, config.dy, config.period), (dx, dy, period));
+        }
+
+        for velocity in [
+            "",
+            "c",
+            "c/",
+            "c/x",
+            "c/4x",
+            "(2,1)c/6d",
+            "(2c/6",
+            "x(1,1)c/6",
+        ] {
+            assert!(
+                matches!(
+                    config.clone().with_velocity_str(velocity),
+                    Err(ConfigError::InvalidVelocity)
+                ),
+                "{velocity}"
+            );
+        }
+
+        // Diagonal symmetry requires a diagonal velocity.
+        let config = config.with_symmetry(Symmetry::D2D);
+        assert!(config.clone().with_velocity_str("c/4d").is_ok());
+        assert!(matches!(
+            config.with_velocity_str("c/2"),
+            Err(ConfigError::InvalidTranslation)
+        ));
+    }
+}
@@ -1,3 +1,12 @@
+use crate::{
+    rule::CellState,
+    symmetry::{Symmetry, Transformation},
+    world::Coord,
+};
+use std::{
+    fmt::{self, Display, Formatter},
+    io,
+};
 use thiserror::Error;
 
 /// An error that can occur when initializing the search from a configuration.
@@ -11,25 +20,209 @@ pub enum ConfigError {
     #[error("The rule is not supported")]
     UnsupportedRule,
 
+    /// The rule table is built for another rule.
+    #[error("The rule table is built for another rule")]
+    RuleTableMismatch,
+
+    /// The birth or survival conditions contain a number larger than the neighborhood allows.
+    #[error("The birth or survival conditions are out of range")]
+    InvalidCondition,
+
+    /// The weighted sum of living neighbors can be too large to fit in a neighborhood descriptor.
+    #[error(
+        "The weighted sum of living neighbors can be up to {max_condition}, \
+         which does not fit in a neighborhood descriptor"
+    )]
+    SumTooLarge {
+        /// The maximum possible weighted sum, i.e., [`Rule::max_condition`](ca_rules2::Rule::max_condition).
+        max_condition: u64,
+    },
+
     /// The width, height, period, or diagonal width is zero.
     #[error("The width, height, period, or diagonal width is zero")]
     InvalidSize,
 
+    /// The bound of [`search_up_to_period`](crate::Config::search_up_to_period)
+    /// is not equal to the period.
+    #[error("The bound of the period to search up to is not equal to the period")]
+    ConflictingPeriod,
+
     /// The population upper bound is zero.
     #[error("The population upper bound is zero")]
     InvalidMaxPopulation,
 
+    /// The number of steps before restarting the search is zero.
+    #[error("The number of steps before restarting the search is zero")]
+    InvalidRestartAfter,
+
+    /// A cell that is required to be alive is outside the world.
+    #[error("A cell that is required to be alive is outside the world")]
+    InvalidRequireAlive,
+
+    /// The population lower bound is larger than the upper bound.
+    #[error("The population lower bound is larger than the upper bound")]
+    InvalidMinPopulation,
+
+    /// The exact population is zero, or outside the bounds of the population.
+    #[error("The exact population is zero, or outside the bounds of the population")]
+    InvalidExactPopulation,
+
+    /// The front generation is out of the range of the period,
+    /// or the generation rotation is disabled.
+    #[error("The front generation is out of range, or the generation rotation is disabled")]
+    InvalidFrontGeneration,
+
+    /// The strategy to relax the configuration is invalid, its amount is zero,
+    /// or the field it relaxes is not set.
+    #[error("The strategy to relax the configuration is invalid")]
+    InvalidRelaxStrategy,
+
     /// The world is not a square when it should be.
-    #[error("The world is not a square when it should be")]
-    NotSquare,
+    #[error("The world is not a square when it should be, because {because}")]
+    NotSquare {
+        /// The requirement that makes the world square.
+        because: SquareReason,
+    },
+
+    /// The world is too small to be a torus, so some cell would be its own neighbor.
+    #[error("The world must be at least {min_size}x{min_size} to be a torus")]
+    TorusTooSmall {
+        /// The minimum width and height of the world.
+        min_size: u32,
+    },
 
     /// The world has a diagonal width when it should not.
     #[error("The world has a diagonal width when it should not")]
     HasDiagonalWidth,
 
+    /// The diagonal width is larger than the width of the world, so it constrains nothing.
+    #[error("The diagonal width {diagonal_width} is larger than the width {width} of the world")]
+    DiagonalWidthTooLarge {
+        /// The diagonal width.
+        diagonal_width: u32,
+        /// The width of the world.
+        width: u32,
+    },
+
     /// The translations do not satisfy the symmetry.
     #[error("The translations do not satisfy the symmetry")]
     InvalidTranslation,
+
+    /// The translation cannot be achieved within the period, because patterns cannot move
+    /// faster than the speed of light of the rule, i.e., the radius of the neighborhood per generation.
+    #[error("The translation is too large: the period must be at least {min_period}")]
+    VelocityTooFast {
+        /// The minimal period for the translation, or `0` if it cannot be achieved at all.
+        min_period: u32,
+    },
+
+    /// The velocity string is invalid.
+    #[error("The velocity string is invalid")]
+    InvalidVelocity,
+
+    /// The world would use more memory than allowed.
+    #[error("The world is too large: it would use about {bytes} bytes of memory")]
+    WorldTooLarge {
+        /// The estimated number of bytes.
+        bytes: usize,
+    },
+
+    /// The compact string representation of the configuration is invalid.
+    #[error("The configuration string is invalid")]
+    InvalidSpec,
+}
+
+/// The reason why a configuration requires the world to be square.
+///
+/// See [`Config::square_reason`](crate::Config::square_reason).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SquareReason {
+    /// The symmetry maps rows to columns.
+    Symmetry(Symmetry),
+    /// The transformation maps rows to columns.
+    Transformation(Transformation),
+    /// A diagonal width is set.
+    DiagonalWidth,
+    /// The search order is diagonal.
+    DiagonalSearchOrder,
+}
+
+impl Display for SquareReason {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Symmetry(symmetry) => write!(f, "the symmetry is {symmetry}"),
+            Self::Transformation(transformation) => {
+                write!(f, "the transformation is {transformation}")
+            }
+            Self::DiagonalWidth => write!(f, "the diagonal width is set"),
+            Self::DiagonalSearchOrder => write!(f, "the search order is diagonal"),
+        }
+    }
+}
+
+/// An error that occurs when setting a cell to a state that conflicts with
+/// its current state.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Error)]
+#[error("The cell at {coord:?} is already {existing:?}, and cannot be set to {attempted:?}")]
+pub struct ConflictError {
+    /// The coordinates of the cell.
+    pub coord: Coord,
+
+    /// The current state of the cell.
+    pub existing: CellState,
+
+    /// The state that we tried to set the cell to.
+    pub attempted: CellState,
+}
+
+/// An error that can occur when creating a [`World`](crate::World) from a configuration
+/// and some known cells.
+#[derive(Clone, Copy, Debug, Error)]
+pub enum SeedError {
+    /// The configuration is invalid.
+    #[error("The configuration is invalid: {0}")]
+    InvalidConfig(#[from] ConfigError),
+
+    /// A known cell conflicts with another known cell.
+    #[error("A known cell conflicts with another known cell: {0}")]
+    Conflict(#[from] ConflictError),
+}
+
+/// An error that can occur when creating a [`World`](crate::World) from an RLE pattern.
+///
+/// See [`World::seed_from_rle_reader`](crate::World::seed_from_rle_reader).
+#[derive(Debug, Error)]
+pub enum RleError {
+    /// The configuration is invalid, or a cell conflicts with another known cell.
+    #[error(transparent)]
+    Seed(#[from] SeedError),
+
+    /// Failed to read the input.
+    #[error("Failed to read the RLE: {0}")]
+    Io(#[from] io::Error),
+
+    /// The RLE contains an invalid character.
+    #[error("Invalid character {char:?} in line {line} of the RLE")]
+    InvalidChar {
+        /// The line number, starting from 1.
+        line: usize,
+        /// The invalid character.
+        char: char,
+    },
+
+    /// A run count is too large.
+    #[error("A run count is too large in line {line} of the RLE")]
+    InvalidCount {
+        /// The line number, starting from 1.
+        line: usize,
+    },
+
+    /// A living cell is outside the world.
+    #[error("The living cell at {coord:?} is outside the world")]
+    OutOfBounds {
+        /// The coordinates of the cell.
+        coord: Coord,
+    },
 }
 
 /// An error that can occur when deserializing a [`World`].
@@ -48,3 +241,55 @@ pub enum SerdeError {
     #[error("The stack is invalid")]
     InvalidStack,
 }
+
+/// An error that can occur when rebuilding a [`World`](crate::World) from a shared find.
+///
+/// See [`World::from_find`](crate::World::from_find).
+#[cfg(feature = "serde")]
+#[derive(Debug, Error)]
+pub enum FindError {
+    /// The document is not valid JSON, or some field is missing.
+    #[error("The find is invalid: {0}")]
+    Json(#[from] serde_json::Error),
+
+    /// The configuration is invalid, or the RLE of the solution cannot be placed in the world.
+    #[error(transparent)]
+    Rle(#[from] RleError),
+
+    /// The pattern is not a solution of the configuration.
+    #[error("The pattern is not a solution of the configuration")]
+    NotASolution,
+}
+
+/// An error that can occur when loading a saved [`RuleTable`](crate::RuleTable).
+///
+/// See [`RuleTable::load`](crate::RuleTable::load).
+#[cfg(feature = "serde")]
+#[derive(Debug, Error)]
+pub enum RuleTableError {
+    /// The file cannot be read or written.
+    #[error("Failed to read or write the rule table: {0}")]
+    Io(#[from] io::Error),
+
+    /// The header is not valid JSON, or some field is missing.
+    #[error("The header of the rule table is invalid: {0}")]
+    Json(#[from] serde_json::Error),
+
+    /// The table was saved by another version of the format.
+    #[error("The rule table was saved in format version {version}, which is not supported")]
+    VersionMismatch {
+        /// The version recorded in the file.
+        version: u32,
+    },
+
+    /// The table was saved for another rule.
+    #[error("The rule table was saved for another rule {rule}")]
+    RuleMismatch {
+        /// The rule recorded in the file.
+        rule: String,
+    },
+
+    /// The table has the wrong size, or contains invalid entries.
+    #[error("The rule table is corrupted")]
+    Corrupted,
+}
@@ -11,13 +11,20 @@
 mod cell;
 mod config;
 mod error;
+mod rle;
 mod rule;
 mod search;
 mod symmetry;
 mod world;
 
-pub use config::{Config, NewState, SearchOrder};
-pub use error::ConfigError;
-pub use rule::{CellState, RuleTable};
+pub use ca_rules2::Rule;
+pub use config::{Boundary, Config, NewState, RelaxStrategy, SearchOrder};
+pub use error::{ConfigError, ConflictError, RleError, SeedError, SquareReason};
+#[cfg(feature = "serde")]
+pub use error::{FindError, RuleTableError};
+pub use rule::{neighborhood_offsets, CellState, ImplicationSet, RuleTable, RuleTableCache};
 pub use symmetry::{Symmetry, Transformation, TranslationCondition};
-pub use world::{Coord, Status, World};
+pub use world::{
+    coord, diff_rle, translate, ConfigTweaks, Coord, GrowAlignment, SearchOutcome, SearchPlan,
+    Status, World, DEFAULT_MEMORY_LIMIT,
+};
@@ -0,0 +1,185 @@
+use crate::{error::RleError, rule::CellState};
+use std::io::BufRead;
+
+/// A token in the body of an RLE string.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum RleToken {
+    /// A run of cells with the same state, or of unknown cells if the state is [`None`].
+    Cells(u32, Option<CellState>),
+    /// A run of line breaks.
+    NewLines(u32),
+    /// The end of the pattern.
+    End,
+}
+
+/// A tokenizer that reads an RLE string line by line, without loading it all at once.
+///
+/// The header line `x = ..., y = ..., rule = ...` and comment lines starting with `#`
+/// are skipped. In the body, `b` and `.` are dead cells, `o` and `A` are living cells,
+/// `?` is an unknown cell, `$` is a line break, and `!` is the end of the pattern.
+/// Each of them may be preceded by a run count, which may be split across lines.
+/// Whitespace is ignored.
+///
+/// After `!` or the end of the input, the tokenizer yields [`RleToken::End`] once,
+/// and then stops.
+#[derive(Debug)]
+pub(crate) struct RleTokenizer<R> {
+    /// The reader.
+    reader: R,
+    /// The current line.
+    line: String,
+    /// The position of the next character in the current line.
+    pos: usize,
+    /// The number of the current line, starting from 1.
+    line_number: usize,
+    /// The run count that has been read, but not yet applied to a token.
+    count: Option<u32>,
+    /// Whether the body has started, so that the header is no longer expected.
+    in_body: bool,
+    /// Whether [`RleToken::End`] has been yielded.
+    done: bool,
+}
+
+impl<R: BufRead> RleTokenizer<R> {
+    /// Create a tokenizer from a reader.
+    pub(crate) const fn new(reader: R) -> Self {
+        Self {
+            reader,
+            line: String::new(),
+            pos: 0,
+            line_number: 0,
+            count: None,
+            in_body: false,
+            done: false,
+        }
+    }
+
+    /// Read the next line that is not a comment or the header into the buffer.
+    ///
+    /// Return `false` at the end of the input.
+    fn next_line(&mut self) -> Result<bool, RleError> {
+        loop {
+            self.line.clear();
+            self.pos = 0;
+            self.line_number += 1;
+
+            if self.reader.read_line(&mut self.line)? == 0 {
+                return Ok(false);
+            }
+
+            let trimmed = self.line.trim_start();
+            if trimmed.starts_with('#') || !self.in_body && trimmed.starts_with('x') {
+                continue;
+            }
+
+            return Ok(true);
+        }
+    }
+
+    /// Read the next token.
+    fn next_token(&mut self) -> Result<RleToken, RleError> {
+        loop {
+            let Some(c) = self.line[self.pos..].chars().next() else {
+                if self.next_line()? {
+                    continue;
+                }
+                return Ok(RleToken::End);
+            };
+            self.pos += c.len_utf8();
+
+            if c.is_whitespace() {
+                continue;
+            }
+            self.in_body = true;
+
+            if let Some(digit) = c.to_digit(10) {
+                let count = self
+                    .count
+                    .unwrap_or(0)
+                    .checked_mul(10)
+                    .and_then(|count| count.checked_add(digit))
+                    .ok_or(RleError::InvalidCount {
+                        line: self.line_number,
+                    })?;
+                self.count = Some(count);
+                continue;
+            }
+
+            let count = self.count.take().unwrap_or(1);
+
+            return match c {
+                'b' | '.' => Ok(RleToken::Cells(count, Some(CellState::Dead))),
+                'o' | 'A' => Ok(RleToken::Cells(count, Some(CellState::Alive))),
+                '?' => Ok(RleToken::Cells(count, None)),
+                '$' => Ok(RleToken::NewLines(count)),
+                '!' => Ok(RleToken::End),
+                _ => Err(RleError::InvalidChar {
+                    line: self.line_number,
+                    char: c,
+                }),
+            };
+        }
+    }
+}
+
+impl<R: BufRead> Iterator for RleTokenizer<R> {
+    type Item = Result<RleToken, RleError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        let token = self.next_token();
+        if matches!(token, Ok(RleToken::End) | Err(_)) {
+            self.done = true;
+        }
+        Some(token)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tokens(rle: &str) -> Result<Vec<RleToken>, RleError> {
+        RleTokenizer::new(rle.as_bytes()).collect()
+    }
+
+    #[test]
+    fn test_tokenizer() {
+        let rle = "#N Glider\nx = 3, y = 3, rule = B3/S23\nbo$2bo$3o!\n";
+        assert_eq!(
+            tokens(rle).unwrap(),
+            [
+                RleToken::Cells(1, Some(CellState::Dead)),
+                RleToken::Cells(1, Some(CellState::Alive)),
+                RleToken::NewLines(1),
+                RleToken::Cells(2, Some(CellState::Dead)),
+                RleToken::Cells(1, Some(CellState::Alive)),
+                RleToken::NewLines(1),
+                RleToken::Cells(3, Some(CellState::Alive)),
+                RleToken::End,
+            ]
+        );
+
+        // A run count split across lines, unknown cells, and a missing `!`.
+        assert_eq!(
+            tokens("1\n2?\n 3$").unwrap(),
+            [
+                RleToken::Cells(12, None),
+                RleToken::NewLines(3),
+                RleToken::End,
+            ]
+        );
+
+        assert!(matches!(
+            tokens("bo$\nbz!"),
+            Err(RleError::InvalidChar { line: 2, char: 'z' })
+        ));
+        assert!(matches!(
+            tokens("99999999999o!"),
+            Err(RleError::InvalidCount { line: 1 })
+        ));
+    }
+}
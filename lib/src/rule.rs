@@ -1,15 +1,28 @@
 use crate::error::ConfigError;
+#[cfg(feature = "serde")]
+use crate::error::RuleTableError;
 use ca_rules2::{Neighborhood, NeighborhoodType, Rule};
 use enumflags2::{bitflags, BitFlags};
 use rand::{
     distributions::{Distribution, Standard},
     Rng,
 };
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 use std::{
+    collections::HashMap,
     fmt::{self, Debug, Formatter},
-    ops::Not,
+    ops::{Not, Range},
+    str::FromStr,
+    sync::{Arc, Mutex},
+};
+#[cfg(feature = "serde")]
+use std::{
+    fs::File,
+    io::{BufRead, BufReader, BufWriter, Read, Write},
+    path::Path,
 };
 
 /// The state of a known cell.
@@ -25,6 +38,36 @@ pub enum CellState {
     Alive = 0b10,
 }
 
+impl CellState {
+    /// Iterate over all the states, in the order `Dead`, `Alive`.
+    #[inline]
+    pub fn iter() -> impl Iterator<Item = Self> {
+        [Self::Dead, Self::Alive].into_iter()
+    }
+
+    /// The character used to display the state, e.g. in a non-compact [RLE](crate::World::rle).
+    ///
+    /// A dead cell is displayed as `.`, and a living cell as `o`.
+    #[inline]
+    pub const fn glyph(self) -> char {
+        match self {
+            Self::Dead => '.',
+            Self::Alive => 'o',
+        }
+    }
+
+    /// The character used to display a cell whose state may be unknown.
+    ///
+    /// This is the same as [`glyph`](CellState::glyph), but an unknown cell is displayed as `?`.
+    #[inline]
+    pub const fn glyph_opt(state: Option<Self>) -> char {
+        match state {
+            Some(state) => state.glyph(),
+            None => '?',
+        }
+    }
+}
+
 impl Not for CellState {
     type Output = Self;
 
@@ -77,6 +120,9 @@ impl Descriptor {
     /// A bit mask for the number of living or dead neighbors.
     const NEIGHBOR_COUNT_MASK: u16 = (1 << Self::NEIGHBOR_COUNT_BITS) - 1;
 
+    /// The largest number of living or dead neighbors that fits in a descriptor.
+    pub(crate) const MAX_COUNT: u64 = Self::NEIGHBOR_COUNT_MASK as u64;
+
     /// The number of bits used to represent the state of the successor cell.
     const SUCCESSOR_BITS: usize = 2;
 
@@ -195,6 +241,7 @@ impl Descriptor {
 
 /// Possible implications of a neighborhood descriptor.
 #[bitflags]
+#[allow(clippy::use_self)]
 #[repr(u8)]
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub(crate) enum Implication {
@@ -220,6 +267,48 @@ pub(crate) enum Implication {
     NeighborhoodDead,
 }
 
+/// The implications of a neighborhood, as returned by [`RuleTable::implications`].
+///
+/// Each field tells whether the corresponding deduction is made. This is a stable
+/// representation for tests and tools, independent of the internal layout of the table.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct ImplicationSet {
+    /// The known cells are inconsistent with the rule.
+    pub conflict: bool,
+
+    /// The successor cell should be alive.
+    pub successor_alive: bool,
+
+    /// The successor cell should be dead.
+    pub successor_dead: bool,
+
+    /// The current cell should be alive.
+    pub current_alive: bool,
+
+    /// The current cell should be dead.
+    pub current_dead: bool,
+
+    /// All unknown cells in the neighborhood should be alive.
+    pub neighborhood_alive: bool,
+
+    /// All unknown cells in the neighborhood should be dead.
+    pub neighborhood_dead: bool,
+}
+
+impl From<BitFlags<Implication>> for ImplicationSet {
+    fn from(flags: BitFlags<Implication>) -> Self {
+        Self {
+            conflict: flags.contains(Implication::Conflict),
+            successor_alive: flags.contains(Implication::SuccessorAlive),
+            successor_dead: flags.contains(Implication::SuccessorDead),
+            current_alive: flags.contains(Implication::CurrentAlive),
+            current_dead: flags.contains(Implication::CurrentDead),
+            neighborhood_alive: flags.contains(Implication::NeighborhoodAlive),
+            neighborhood_dead: flags.contains(Implication::NeighborhoodDead),
+        }
+    }
+}
+
 /// The lookup table and other information of a totalistic rule.
 ///
 /// In a totalistic rule, the state of a cell is determined by the state of itself and
@@ -229,10 +318,20 @@ pub(crate) enum Implication {
 /// in the neighborhood descriptor. So the neighborhood size is limited to 24.
 #[derive(Clone)]
 pub struct RuleTable {
+    /// The rule the table is built from, as written by its [`Display`](fmt::Display)
+    /// implementation, which is recorded when the table is [saved](RuleTable::save).
+    pub(crate) rule: String,
+
     /// The size of the neighborhood.
     pub(crate) neighborhood_size: usize,
 
     /// The offsets of the neighbors.
+    ///
+    /// The `i`-th neighbor of a cell is stored in the `i`-th slot of its neighborhood,
+    /// so this order must be stable. It is the order of [`Rule::neighbor_coords`].
+    /// For example, for the cross neighborhood of radius `r`, it is:
+    /// the left arm from `(-r, 0)` to `(-1, 0)`, the vertical arm from `(0, -r)` to `(0, r)`
+    /// skipping the center, and the right arm from `(1, 0)` to `(r, 0)`.
     pub(crate) offsets: Vec<(i32, i32)>,
 
     /// The radius of the neighborhood.
@@ -245,6 +344,7 @@ pub struct RuleTable {
 impl Debug for RuleTable {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         f.debug_struct("Rule")
+            .field("rule", &self.rule)
             .field("neighborhood_size", &self.neighborhood_size)
             .field("offsets", &self.offsets)
             .field("radius", &self.radius)
@@ -253,14 +353,46 @@ impl Debug for RuleTable {
 }
 
 impl RuleTable {
+    /// The offsets of the neighbors relative to the center cell,
+    /// in the order they are stored in the neighborhood of a cell.
+    #[inline]
+    pub fn offsets(&self) -> &[(i32, i32)] {
+        &self.offsets
+    }
+
+    /// The size of the lookup table in bytes.
+    #[inline]
+    pub const fn table_bytes(&self) -> usize {
+        self.table.len() * size_of::<BitFlags<Implication>>()
+    }
+
     /// Create and initialize a rule table from a [`Rule`].
+    ///
+    /// The rule is checked again here, since it may be constructed directly instead of
+    /// being parsed. In particular, if its birth or survival conditions contain a number
+    /// larger than [`max_condition`](Rule::max_condition), an
+    /// [`InvalidCondition`](ConfigError::InvalidCondition) error is returned.
+    ///
+    /// The maximum weighted sum of living neighbors must also fit in the count field of
+    /// a [`Descriptor`]. Otherwise a [`SumTooLarge`](ConfigError::SumTooLarge) error is returned,
+    /// even if the rule is not supported for other reasons, since a weighted neighborhood
+    /// with a few neighbors of large weights can exceed it.
+    ///
+    /// If the neighborhood includes the center cell, i.e., the rule is inner-totalistic,
+    /// it is first converted to an equivalent outer-totalistic rule with [`Rule::without_center`].
     pub fn new(rule: &Rule) -> Result<Self, ConfigError> {
-        if rule.contains_b0() {
+        let rule_string = rule.to_string();
+        let rule = &rule
+            .without_center()
+            .map_err(|_| ConfigError::UnsupportedRule)?;
+
+        check_weighted_sum(&rule.neighborhood)?;
+
+        if rule.contains_b0() || rule.states != 2 {
             return Err(ConfigError::UnsupportedRule);
         }
 
-        if !matches!(rule.neighborhood, Neighborhood::Totalistic(neighborhood_type, _) if neighborhood_type != NeighborhoodType::Hexagonal)
-        {
+        if !is_supported_neighborhood(&rule.neighborhood) {
             return Err(ConfigError::UnsupportedRule);
         }
 
@@ -270,11 +402,16 @@ impl RuleTable {
             return Err(ConfigError::UnsupportedRule);
         }
 
+        if !rule.check_conditions() {
+            return Err(ConfigError::InvalidCondition);
+        }
+
         let offsets = rule.neighbor_coords();
         let radius = rule.radius();
 
         let table = vec![BitFlags::empty(); 1 << Descriptor::BITS];
         let mut rule_table = Self {
+            rule: rule_string,
             neighborhood_size,
             offsets,
             radius,
@@ -292,116 +429,189 @@ impl RuleTable {
         self.deduce_neighborhood();
     }
 
+    /// Compute some entries of the lookup table for each number of dead neighbors in a range,
+    /// and add the implications to the table.
+    ///
+    /// `f` returns the descriptors and their implications for a given number of dead neighbors.
+    /// It may read the table, but the entries it reads must not be changed by this call.
+    ///
+    /// With the `rayon` feature, the numbers of dead neighbors are processed in parallel,
+    /// and the table is only updated after all of them are processed.
+    /// Since the entries that are read are not changed, the result is the same.
+    /// For the neighborhood sizes supported now, the overhead of scheduling the tasks
+    /// may outweigh the gain; see the `rule_table` benchmark.
+    fn update(
+        &mut self,
+        dead_range: Range<usize>,
+        f: impl Fn(&Self, usize) -> Vec<(Descriptor, BitFlags<Implication>)> + Sync,
+    ) {
+        #[cfg(feature = "rayon")]
+        {
+            let updates = dead_range
+                .into_par_iter()
+                .flat_map_iter(|dead| f(self, dead))
+                .collect::<Vec<_>>();
+            for (descriptor, implication) in updates {
+                self.table[descriptor.0 as usize] |= implication;
+            }
+        }
+
+        #[cfg(not(feature = "rayon"))]
+        for dead in dead_range {
+            for (descriptor, implication) in f(self, dead) {
+                self.table[descriptor.0 as usize] |= implication;
+            }
+        }
+    }
+
     /// Deduce the implication of the successor cell.
     fn deduce_successor(&mut self, birth: &[u64], survival: &[u64]) {
+        let size = self.neighborhood_size;
+
         // When all neighbors are known, the successor cell can be deduced directly from the rule.
-        for dead in 0..=self.neighborhood_size {
-            let alive = self.neighborhood_size - dead;
+        self.update(0..size + 1, |_, dead| {
+            let alive = size - dead;
+            let mut updates = Vec::with_capacity(3);
 
             // When the current cell is dead.
             let descriptor_dead = Descriptor::new(dead, alive, None, CellState::Dead);
-            self.table[descriptor_dead.0 as usize] |= if birth.contains(&(alive as u64)) {
-                Implication::SuccessorAlive
-            } else {
-                Implication::SuccessorDead
-            };
+            updates.push((
+                descriptor_dead,
+                if birth.contains(&(alive as u64)) {
+                    Implication::SuccessorAlive
+                } else {
+                    Implication::SuccessorDead
+                }
+                .into(),
+            ));
 
             // When the current cell is alive.
             let descriptor_alive = Descriptor::new(dead, alive, None, CellState::Alive);
-            self.table[descriptor_alive.0 as usize] |= if survival.contains(&(alive as u64)) {
-                Implication::SuccessorAlive
-            } else {
-                Implication::SuccessorDead
-            };
+            updates.push((
+                descriptor_alive,
+                if survival.contains(&(alive as u64)) {
+                    Implication::SuccessorAlive
+                } else {
+                    Implication::SuccessorDead
+                }
+                .into(),
+            ));
 
             // When the current cell is unknown.
             // In this case, the successor cell can still be deduced to be dead, if the number of living
             // neighbors is neither in `birth` nor in `survival`.
             let descriptor_unknown = Descriptor::new(dead, alive, None, None);
             if !birth.contains(&(alive as u64)) && !survival.contains(&(alive as u64)) {
-                self.table[descriptor_unknown.0 as usize] |= Implication::SuccessorDead;
+                updates.push((descriptor_unknown, Implication::SuccessorDead.into()));
             }
-        }
+
+            updates
+        });
 
         // Deduce for the case when some neighbors are unknown.
         //
         // If setting an unknown neighbor to both dead and alive leads to the same implication, then
         // we can deduce that the successor cell should be in that state.
-        for unknown in 1..=self.neighborhood_size {
-            for dead in 0..=self.neighborhood_size - unknown {
-                let alive = self.neighborhood_size - dead - unknown;
+        //
+        // Each number of unknown neighbors only depends on the previous one,
+        // so they are processed one by one.
+        for unknown in 1..=size {
+            self.update(0..size - unknown + 1, |table, dead| {
+                let alive = size - dead - unknown;
+                let mut updates = Vec::with_capacity(3);
 
                 for current in [None, Some(CellState::Dead), Some(CellState::Alive)] {
                     let descriptor = Descriptor::new(dead, alive, None, current);
                     let one_more_dead = Descriptor::new(dead + 1, alive, None, current);
                     let one_more_alive = Descriptor::new(dead, alive + 1, None, current);
 
-                    if self.implies(one_more_dead) == self.implies(one_more_alive) {
-                        self.table[descriptor.0 as usize] = self.implies(one_more_dead);
+                    if table.implies(one_more_dead) == table.implies(one_more_alive) {
+                        updates.push((descriptor, table.implies(one_more_dead)));
                     }
                 }
-            }
+
+                updates
+            });
         }
     }
 
     /// Deduce conflicts.
     fn deduce_conflict(&mut self) {
+        let size = self.neighborhood_size;
+
         // A conflict occurs when the successor cell is known but different from the deduced value.
-        for dead in 0..=self.neighborhood_size {
-            for alive in 0..=self.neighborhood_size - dead {
+        self.update(0..size + 1, |table, dead| {
+            let mut updates = Vec::new();
+
+            for alive in 0..=size - dead {
                 for current in [None, Some(CellState::Dead), Some(CellState::Alive)] {
                     // First set the successor cell to be unknown.
                     let descriptor = Descriptor::new(dead, alive, None, current);
-                    let implication = self.implies(descriptor);
+                    let implication = table.implies(descriptor);
 
                     // If the successor cell is deduced to be alive, then it should not be dead.
                     if implication.contains(Implication::SuccessorAlive) {
                         let descriptor_dead =
                             Descriptor::new(dead, alive, CellState::Dead, current);
-                        self.table[descriptor_dead.0 as usize] = Implication::Conflict.into();
+                        updates.push((descriptor_dead, Implication::Conflict.into()));
                     }
 
                     // If the successor cell is deduced to be dead, then it should not be alive.
                     if implication.contains(Implication::SuccessorDead) {
                         let descriptor_alive =
                             Descriptor::new(dead, alive, CellState::Alive, current);
-                        self.table[descriptor_alive.0 as usize] = Implication::Conflict.into();
+                        updates.push((descriptor_alive, Implication::Conflict.into()));
                     }
                 }
             }
-        }
+
+            updates
+        });
     }
 
     /// Deduce the implication of the current cell.
     fn deduce_current(&mut self) {
+        let size = self.neighborhood_size;
+
         // If setting the current cell to some state leads to a conflict, then it should be in the
         // opposite state.
-        for dead in 0..=self.neighborhood_size {
-            for alive in 0..=self.neighborhood_size - dead {
+        self.update(0..size + 1, |table, dead| {
+            let mut updates = Vec::new();
+
+            for alive in 0..=size - dead {
                 for successor in [CellState::Dead, CellState::Alive] {
                     let descriptor = Descriptor::new(dead, alive, successor, None);
                     let current_dead = Descriptor::new(dead, alive, successor, CellState::Dead);
                     let current_alive = Descriptor::new(dead, alive, successor, CellState::Alive);
 
-                    if self.implies(current_dead).contains(Implication::Conflict) {
-                        self.table[descriptor.0 as usize] |= Implication::CurrentAlive;
+                    if table.implies(current_dead).contains(Implication::Conflict) {
+                        updates.push((descriptor, Implication::CurrentAlive.into()));
                     }
 
-                    if self.implies(current_alive).contains(Implication::Conflict) {
-                        self.table[descriptor.0 as usize] |= Implication::CurrentDead;
+                    if table.implies(current_alive).contains(Implication::Conflict) {
+                        updates.push((descriptor, Implication::CurrentDead.into()));
                     }
                 }
             }
-        }
+
+            updates
+        });
     }
 
     /// Deduce the implication of the neighborhood.
     fn deduce_neighborhood(&mut self) {
+        let size = self.neighborhood_size;
+
         // If setting an unknown neighbor to some state leads to a conflict, then all unknown
         // neighbors should be in the opposite state.
-        for unknown in 1..=self.neighborhood_size {
-            for dead in 0..=self.neighborhood_size - unknown {
-                let alive = self.neighborhood_size - dead - unknown;
+        //
+        // This only reads whether there is a conflict, which is not changed here,
+        // so all numbers of unknown neighbors can be processed at once.
+        self.update(0..size, |table, dead| {
+            let mut updates = Vec::new();
+
+            for unknown in 1..=size - dead {
+                let alive = size - dead - unknown;
 
                 for successor in [CellState::Dead, CellState::Alive] {
                     for current in [None, Some(CellState::Dead), Some(CellState::Alive)] {
@@ -409,21 +619,633 @@ impl RuleTable {
                         let one_more_dead = Descriptor::new(dead + 1, alive, successor, current);
                         let one_more_alive = Descriptor::new(dead, alive + 1, successor, current);
 
-                        if self.implies(one_more_dead).contains(Implication::Conflict) {
-                            self.table[descriptor.0 as usize] |= Implication::NeighborhoodAlive;
+                        if table.implies(one_more_dead).contains(Implication::Conflict) {
+                            updates.push((descriptor, Implication::NeighborhoodAlive.into()));
                         }
 
-                        if self.implies(one_more_alive).contains(Implication::Conflict) {
-                            self.table[descriptor.0 as usize] |= Implication::NeighborhoodDead;
+                        if table
+                            .implies(one_more_alive)
+                            .contains(Implication::Conflict)
+                        {
+                            updates.push((descriptor, Implication::NeighborhoodDead.into()));
                         }
                     }
                 }
             }
-        }
+
+            updates
+        });
     }
 
     /// Find the implication of a neighborhood descriptor.
     pub(crate) fn implies(&self, descriptor: Descriptor) -> BitFlags<Implication> {
         self.table[descriptor.0 as usize]
     }
+
+    /// Find the implications of a neighborhood, given the numbers of dead and living neighbors,
+    /// and the states of the successor and current cells, where [`None`] means unknown.
+    ///
+    /// This looks up the same table as the search, so it can be used to check the deductions
+    /// of a rule from outside the crate.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `dead + alive` is larger than the size of the neighborhood.
+    pub fn implications(
+        &self,
+        dead: usize,
+        alive: usize,
+        successor: Option<CellState>,
+        current: Option<CellState>,
+    ) -> ImplicationSet {
+        assert!(
+            dead + alive <= self.neighborhood_size,
+            "The neighborhood has only {} cells",
+            self.neighborhood_size
+        );
+
+        self.implies(Descriptor::new(dead, alive, successor, current))
+            .into()
+    }
+}
+
+/// The version of the file format of [`RuleTable::save`].
+///
+/// It must be increased whenever the format or the layout of the table changes,
+/// e.g., the bits of a [`Descriptor`] or of an [`Implication`],
+/// so that tables saved by an older version are rejected.
+#[cfg(feature = "serde")]
+const RULE_TABLE_VERSION: u32 = 1;
+
+/// The header of a saved [`RuleTable`], written as one line of JSON before the table.
+#[cfg(feature = "serde")]
+#[derive(Serialize, Deserialize)]
+struct RuleTableHeader {
+    version: u32,
+    rule: String,
+    neighborhood_size: usize,
+    radius: u32,
+    offsets: Vec<(i32, i32)>,
+}
+
+#[cfg(feature = "serde")]
+impl RuleTable {
+    /// Save the table to a file, so that it can be [loaded](RuleTable::load) later
+    /// instead of being built again.
+    ///
+    /// The file starts with a line of JSON recording the version of the format, the rule,
+    /// and the neighborhood, followed by the table itself, one byte per entry.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), RuleTableError> {
+        let header = RuleTableHeader {
+            version: RULE_TABLE_VERSION,
+            rule: self.rule.clone(),
+            neighborhood_size: self.neighborhood_size,
+            radius: self.radius,
+            offsets: self.offsets.clone(),
+        };
+
+        let mut writer = BufWriter::new(File::create(path)?);
+        serde_json::to_writer(&mut writer, &header)?;
+        writer.write_all(b"\n")?;
+        let bytes = self
+            .table
+            .iter()
+            .map(|flags| flags.bits())
+            .collect::<Vec<_>>();
+        writer.write_all(&bytes)?;
+        writer.flush()?;
+        Ok(())
+    }
+
+    /// Load a table [saved](RuleTable::save) for the given rule.
+    ///
+    /// A file saved by another version of the format returns a
+    /// [`VersionMismatch`](RuleTableError::VersionMismatch) error,
+    /// and a file saved for another rule returns a
+    /// [`RuleMismatch`](RuleTableError::RuleMismatch) error,
+    /// so a stale table is never used.
+    ///
+    /// The neighbor offsets, the radius and the neighborhood size are derived from the rule,
+    /// as in [`new`](RuleTable::new), rather than trusted from the file. If the header
+    /// disagrees with them, e.g., because the order of the neighbors has changed since
+    /// the file was saved, a [`Corrupted`](RuleTableError::Corrupted) error is returned.
+    ///
+    /// To create worlds with the loaded table, [insert](RuleTableCache::insert) it into
+    /// a [`RuleTableCache`], and pass the cache to
+    /// [`World::new_with_cache`](crate::World::new_with_cache).
+    pub fn load(path: impl AsRef<Path>, rule: &Rule) -> Result<Self, RuleTableError> {
+        let mut reader = BufReader::new(File::open(path)?);
+        let mut line = String::new();
+        reader.read_line(&mut line)?;
+        let header: RuleTableHeader = serde_json::from_str(&line)?;
+
+        if header.version != RULE_TABLE_VERSION {
+            return Err(RuleTableError::VersionMismatch {
+                version: header.version,
+            });
+        }
+
+        if header.rule != rule.to_string() {
+            return Err(RuleTableError::RuleMismatch { rule: header.rule });
+        }
+
+        let rule = rule
+            .without_center()
+            .map_err(|_| RuleTableError::Corrupted)?;
+        let neighborhood_size = rule.neighborhood_size();
+        let offsets = rule.neighbor_coords();
+        let radius = rule.radius();
+
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes)?;
+
+        if header.offsets != offsets
+            || header.radius != radius
+            || header.neighborhood_size != neighborhood_size
+            || neighborhood_size > MAX_NEIGHBORHOOD_SIZE
+            || bytes.len() != 1 << Descriptor::BITS
+        {
+            return Err(RuleTableError::Corrupted);
+        }
+
+        let table = bytes
+            .into_iter()
+            .map(BitFlags::from_bits)
+            .collect::<Result<_, _>>()
+            .map_err(|_| RuleTableError::Corrupted)?;
+
+        Ok(Self {
+            rule: header.rule,
+            neighborhood_size,
+            offsets,
+            radius,
+            table,
+        })
+    }
+}
+
+/// Whether a neighborhood is supported by the rule table.
+///
+/// The neighborhood must be totalistic, and invariant under all the rotations and reflections
+/// of the square grid, so that it is compatible with every [`Symmetry`](crate::Symmetry)
+/// and [`Transformation`](crate::Transformation). This excludes the hexagonal neighborhood.
+/// It must also have at least one neighbor.
+fn is_supported_neighborhood(neighborhood: &Neighborhood) -> bool {
+    if neighborhood.size() == 0 {
+        return false;
+    }
+
+    match neighborhood {
+        Neighborhood::Totalistic(neighborhood_type, _) => {
+            *neighborhood_type != NeighborhoodType::Hexagonal
+        }
+        Neighborhood::CustomTotalistic(_) => neighborhood.is_isotropic(),
+        _ => false,
+    }
+}
+
+/// Check that the weighted sum of living neighbors fits in a [`Descriptor`].
+///
+/// Non-totalistic neighborhoods are not checked here, since their conditions are
+/// not sums of living neighbors.
+fn check_weighted_sum(neighborhood: &Neighborhood) -> Result<(), ConfigError> {
+    if matches!(
+        neighborhood,
+        Neighborhood::Nontotalistic(_, _) | Neighborhood::CustomNontotalistic(_)
+    ) {
+        return Ok(());
+    }
+
+    let max_condition = neighborhood.max_condition();
+    if max_condition > Descriptor::MAX_COUNT {
+        return Err(ConfigError::SumTooLarge { max_condition });
+    }
+
+    Ok(())
+}
+
+/// Parse a rule string, and check whether the rule is supported.
+///
+/// See [`Config::parse_rule`](crate::Config::parse_rule) for the supported rules.
+pub(crate) fn parse_supported_rule(rule_str: &str) -> Result<Rule, ConfigError> {
+    let rule = Rule::from_str(rule_str).map_err(|_| ConfigError::InvalidRule)?;
+
+    if rule.contains_b0() || rule.states != 2 {
+        return Err(ConfigError::UnsupportedRule);
+    }
+
+    if !is_supported_neighborhood(&rule.neighborhood) {
+        return Err(ConfigError::UnsupportedRule);
+    }
+
+    let neighborhood_size = rule.neighborhood_size();
+
+    if neighborhood_size > MAX_NEIGHBORHOOD_SIZE {
+        return Err(ConfigError::UnsupportedRule);
+    }
+
+    Ok(rule)
+}
+
+/// The offsets of the neighbors of a cell under a rule, relative to the cell itself.
+///
+/// The offsets are in the same order as in the rule table of a [`World`](crate::World).
+/// This only parses the rule, without building the lookup table or a world,
+/// so it is cheap enough to be called whenever the rule string changes,
+/// e.g. to show the shape of the neighborhood in a user interface.
+///
+/// Returns an error if the rule string is invalid or the rule is not supported.
+/// See [`Config::parse_rule`](crate::Config::parse_rule) for the supported rules.
+pub fn neighborhood_offsets(rule_str: &str) -> Result<Vec<(i32, i32)>, ConfigError> {
+    Ok(parse_supported_rule(rule_str)?.neighbor_coords())
+}
+
+/// A thread-safe cache of [`RuleTable`]s, keyed by rule strings.
+///
+/// Building a rule table takes some time, especially for large neighborhoods.
+/// Tools that create many worlds with the same rule can share a cache,
+/// and pass it to [`World::new_with_cache`](crate::World::new_with_cache),
+/// so that the table for each rule string is only built once.
+#[derive(Debug, Default)]
+pub struct RuleTableCache {
+    /// The cached rule tables.
+    tables: Mutex<HashMap<String, Arc<RuleTable>>>,
+}
+
+impl RuleTableCache {
+    /// Create an empty cache.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the rule table for a rule string, building it if it is not in the cache yet.
+    ///
+    /// Errors are not cached.
+    pub fn get_or_build(&self, rule_str: &str) -> Result<Arc<RuleTable>, ConfigError> {
+        if let Some(table) = self.tables.lock().unwrap().get(rule_str) {
+            return Ok(Arc::clone(table));
+        }
+
+        // Build the table without holding the lock, so that other threads are not blocked.
+        let rule = parse_supported_rule(rule_str)?;
+        let table = Arc::new(RuleTable::new(&rule)?);
+
+        let table = Arc::clone(
+            self.tables
+                .lock()
+                .unwrap()
+                .entry(rule_str.to_string())
+                .or_insert(table),
+        );
+        Ok(table)
+    }
+
+    /// Insert a rule table that was built elsewhere, e.g., [loaded](RuleTable::load)
+    /// from a file, so that worlds created with this cache use it instead of building it again.
+    ///
+    /// Return a [`RuleTableMismatch`](ConfigError::RuleTableMismatch) error if the table
+    /// is built for another rule than `rule_str`. A table already cached for `rule_str`
+    /// is replaced.
+    pub fn insert(&self, rule_str: &str, table: RuleTable) -> Result<Arc<RuleTable>, ConfigError> {
+        let rule = parse_supported_rule(rule_str)?;
+        if table.rule != rule.to_string() {
+            return Err(ConfigError::RuleTableMismatch);
+        }
+
+        let table = Arc::new(table);
+        self.tables
+            .lock()
+            .unwrap()
+            .insert(rule_str.to_string(), Arc::clone(&table));
+        Ok(table)
+    }
+
+    /// The number of rule tables in the cache.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.tables.lock().unwrap().len()
+    }
+
+    /// Whether the cache is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.tables.lock().unwrap().is_empty()
+    }
+
+    /// Remove all rule tables from the cache.
+    #[inline]
+    pub fn clear(&self) {
+        self.tables.lock().unwrap().clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        config::Config,
+        world::{Status, World},
+    };
+    use ca_rules2::Neighbor;
+
+    #[test]
+    fn test_custom_neighborhood() {
+        // The cross neighborhood of radius 2, written as a list of offsets.
+        let cross = "R2,C2,S2,B3,N[(-2,0),(-1,0),(1,0),(2,0),(0,-2),(0,-1),(0,1),(0,2)]";
+        let offsets = neighborhood_offsets(cross).unwrap();
+        assert_eq!(offsets.len(), 8);
+        assert!(RuleTable::new(&cross.parse().unwrap()).is_ok());
+
+        let config = Config::new(cross, 8, 8, 1);
+        assert!(config.parse_rule().is_ok());
+
+        // A neighborhood that is not symmetric under reflections.
+        let knight = "R2,C2,S2,B3,N[(1,2),(2,-1),(-1,-2),(-2,1)]";
+        assert!(matches!(
+            neighborhood_offsets(knight),
+            Err(ConfigError::UnsupportedRule)
+        ));
+
+        // An empty neighborhood, either a custom one built by hand or a radius of 0.
+        let empty = Rule {
+            states: 2,
+            neighborhood: Neighborhood::CustomTotalistic(Vec::new()),
+            birth: Vec::new(),
+            survival: Vec::new(),
+        };
+        assert!(matches!(
+            RuleTable::new(&empty),
+            Err(ConfigError::UnsupportedRule)
+        ));
+        assert!(matches!(
+            World::new(Config::new("R0,C2,S,B,NM", 4, 4, 1)),
+            Err(ConfigError::UnsupportedRule)
+        ));
+        assert!(matches!(
+            World::new(Config::new("R1,C2,S,B,N[]", 4, 4, 1)),
+            Err(ConfigError::InvalidRule)
+        ));
+    }
+
+    #[test]
+    fn test_implications() {
+        let table = RuleTable::new(&"B3/S23".parse().unwrap()).unwrap();
+
+        // Birth.
+        assert_eq!(
+            table.implications(5, 3, None, Some(CellState::Dead)),
+            ImplicationSet {
+                successor_alive: true,
+                ..ImplicationSet::default()
+            }
+        );
+
+        // A dead cell with 4 living neighbors cannot become alive.
+        assert!(
+            table
+                .implications(4, 4, Some(CellState::Alive), Some(CellState::Dead))
+                .conflict
+        );
+
+        // The only unknown neighbor must be alive for the birth.
+        assert!(
+            table
+                .implications(5, 2, Some(CellState::Alive), Some(CellState::Dead))
+                .neighborhood_alive
+        );
+
+        // Nothing is known.
+        assert_eq!(
+            table.implications(0, 0, None, None),
+            ImplicationSet::default()
+        );
+    }
+
+    #[test]
+    fn test_weighted_sum_too_large() {
+        // Only two neighbors, but the weighted sum can be up to 100.
+        let rule = Rule {
+            states: 2,
+            neighborhood: Neighborhood::CustomWeighted(vec![
+                Neighbor::new((-1, 0), 50),
+                Neighbor::new((1, 0), 50),
+            ]),
+            birth: vec![50],
+            survival: vec![100],
+        };
+        assert!(matches!(
+            RuleTable::new(&rule),
+            Err(ConfigError::SumTooLarge { max_condition: 100 })
+        ));
+
+        // Small weights fit, but weighted rules are not supported yet.
+        let rule = Rule {
+            states: 2,
+            neighborhood: Neighborhood::CustomWeighted(vec![
+                Neighbor::new((-1, 0), 2),
+                Neighbor::new((1, 0), 2),
+            ]),
+            birth: vec![2],
+            survival: vec![4],
+        };
+        assert!(matches!(
+            RuleTable::new(&rule),
+            Err(ConfigError::UnsupportedRule)
+        ));
+    }
+
+    #[test]
+    fn test_cell_state_glyph() {
+        let glyphs = CellState::iter().map(CellState::glyph).collect::<String>();
+        assert_eq!(glyphs, ".o");
+        assert_eq!(CellState::glyph_opt(None), '?');
+        assert_eq!(CellState::glyph_opt(Some(CellState::Alive)), 'o');
+    }
+
+    #[test]
+    fn test_invalid_condition() {
+        let mut rule = Rule {
+            states: 2,
+            neighborhood: Neighborhood::Totalistic(NeighborhoodType::Moore, 1),
+            birth: vec![3],
+            survival: vec![2, 3],
+        };
+        assert!(RuleTable::new(&rule).is_ok());
+
+        rule.survival.push(9);
+        assert!(matches!(
+            RuleTable::new(&rule),
+            Err(ConfigError::InvalidCondition)
+        ));
+    }
+
+    #[test]
+    fn test_inner_totalistic() {
+        // Conway's Life, counting the center cell in the neighborhood.
+        let mut coords = NeighborhoodType::Moore.neighbor_coords(1);
+        coords.push((0, 0));
+        let inner = Rule {
+            states: 2,
+            neighborhood: Neighborhood::CustomTotalistic(coords),
+            birth: vec![3],
+            survival: vec![3, 4],
+        };
+        let outer = Rule::from_str("B3/S23").unwrap();
+
+        let table = RuleTable::new(&inner).unwrap();
+        assert_eq!(table.neighborhood_size, 8);
+        assert_eq!(table.table, RuleTable::new(&outer).unwrap().table);
+
+        let config = Config::new("B3/S23", 4, 4, 1).with_rule(inner);
+        assert_eq!(config.rule_str, "B3/S23");
+    }
+
+    #[test]
+    fn test_neighbor_order() {
+        // The default rule, factorio, has a cross neighborhood of radius 3.
+        let rule = Rule::from_str("R3,C2,S2,B3,N+").unwrap();
+        let table = RuleTable::new(&rule).unwrap();
+        assert_eq!(table.neighborhood_size, 12);
+        assert_eq!(table.radius, 3);
+        assert_eq!(
+            table.offsets,
+            [
+                (-3, 0),
+                (-2, 0),
+                (-1, 0),
+                (0, -3),
+                (0, -2),
+                (0, -1),
+                (0, 1),
+                (0, 2),
+                (0, 3),
+                (1, 0),
+                (2, 0),
+                (3, 0),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_neighborhood_offsets() {
+        let offsets = neighborhood_offsets("R3,C2,S2,B3,N+").unwrap();
+        let table = RuleTable::new(&Rule::from_str("R3,C2,S2,B3,N+").unwrap()).unwrap();
+        assert_eq!(offsets, table.offsets);
+
+        assert_eq!(neighborhood_offsets("B3/S23").unwrap().len(), 8);
+        assert!(matches!(
+            neighborhood_offsets("B2/S34H"),
+            Err(ConfigError::UnsupportedRule)
+        ));
+        assert!(matches!(
+            neighborhood_offsets("foo"),
+            Err(ConfigError::InvalidRule)
+        ));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_save_load() {
+        let path = std::env::temp_dir().join(format!(
+            "factoriosrc_test_rule_table_{}.bin",
+            std::process::id()
+        ));
+        let rule = Rule::from_str("R2,C2,S6-9,B7-8,NM").unwrap();
+        let table = RuleTable::new(&rule).unwrap();
+        table.save(&path).unwrap();
+
+        let loaded = RuleTable::load(&path, &rule).unwrap();
+        assert_eq!(loaded.rule, table.rule);
+        assert_eq!(loaded.offsets, table.offsets);
+        assert_eq!(loaded.radius, table.radius);
+        assert_eq!(loaded.neighborhood_size, table.neighborhood_size);
+        assert_eq!(loaded.table, table.table);
+
+        // A world built from the loaded table searches identically.
+        let rule_str = "R2,C2,S6-9,B7-8,NM";
+        let cache = RuleTableCache::new();
+        assert!(matches!(
+            cache.insert("B3/S23", RuleTable::load(&path, &rule).unwrap()),
+            Err(ConfigError::RuleTableMismatch)
+        ));
+        let cached = cache.insert(rule_str, loaded).unwrap();
+        let config = Config::new(rule_str, 5, 5, 1);
+        let mut world = World::new_with_cache(config.clone(), &cache).unwrap();
+        assert!(std::ptr::eq(world.rule_table(), &*cached));
+        let mut expected = World::new(config).unwrap();
+        let mut count = 0;
+        while world.search(None) == Status::Solved {
+            assert_eq!(expected.search(None), Status::Solved);
+            assert_eq!(world.rle(0, true), expected.rle(0, true));
+            count += 1;
+        }
+        assert_eq!(expected.search(None), Status::NoSolution);
+        assert!(count > 0);
+
+        assert!(matches!(
+            RuleTable::load(&path, &Rule::from_str("B3/S23").unwrap()),
+            Err(RuleTableError::RuleMismatch { .. })
+        ));
+
+        let mut bytes = std::fs::read(&path).unwrap();
+        let newline = bytes.iter().position(|&b| b == b'\n').unwrap();
+        let header = std::str::from_utf8(&bytes[..newline]).unwrap();
+        let mut stale = header
+            .replace("\"version\":1", "\"version\":0")
+            .into_bytes();
+        stale.extend_from_slice(&bytes[newline..]);
+        std::fs::write(&path, stale).unwrap();
+        assert!(matches!(
+            RuleTable::load(&path, &rule),
+            Err(RuleTableError::VersionMismatch { version: 0 })
+        ));
+
+        // The neighbors in the header are in another order.
+        let mut value: serde_json::Value = serde_json::from_str(header).unwrap();
+        value["offsets"].as_array_mut().unwrap().swap(0, 1);
+        let mut reordered = value.to_string().into_bytes();
+        reordered.extend_from_slice(&bytes[newline..]);
+        std::fs::write(&path, reordered).unwrap();
+        assert!(matches!(
+            RuleTable::load(&path, &rule),
+            Err(RuleTableError::Corrupted)
+        ));
+
+        bytes.pop();
+        std::fs::write(&path, &bytes).unwrap();
+        assert!(matches!(
+            RuleTable::load(&path, &rule),
+            Err(RuleTableError::Corrupted)
+        ));
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_rule_table_cache() {
+        let cache = RuleTableCache::new();
+        assert!(cache.is_empty());
+
+        let table1 = cache.get_or_build("B3/S23").unwrap();
+        let table2 = cache.get_or_build("B3/S23").unwrap();
+        assert!(Arc::ptr_eq(&table1, &table2));
+        assert_eq!(cache.len(), 1);
+
+        assert!(matches!(
+            cache.get_or_build("B3/S23/3"),
+            Err(ConfigError::UnsupportedRule)
+        ));
+        assert!(matches!(
+            cache.get_or_build("foo"),
+            Err(ConfigError::InvalidRule)
+        ));
+        assert_eq!(cache.len(), 1);
+
+        let config = crate::Config::new("B3/S23", 4, 4, 1);
+        let world1 = crate::World::new_with_cache(config.clone(), &cache).unwrap();
+        let world2 = crate::World::new_with_cache(config, &cache).unwrap();
+        assert!(std::ptr::eq(world1.rule_table(), world2.rule_table()));
+        assert!(std::ptr::eq(world1.rule_table(), &*table1));
+    }
 }
@@ -1,12 +1,20 @@
 use rand::Rng;
+#[cfg(test)]
+use std::collections::BTreeSet;
+use std::{collections::HashSet, num::NonZeroUsize, time::Instant};
 
 use crate::{
     cell::LifeCell,
-    config::NewState,
+    config::{Boundary, NewState},
+    error::ConflictError,
     rule::{CellState, Implication},
-    world::{Reason, Status, World},
+    world::{Coord, Reason, SearchOutcome, Status, World},
 };
 
+/// The number of steps between two checks of the clock in
+/// [`search_until_instant`](World::search_until_instant).
+const DEADLINE_CHECK_INTERVAL: usize = 1024;
+
 impl World {
     /// Check the neighborhood descriptor for a cell to see what it implies.
     ///
@@ -28,6 +36,7 @@ impl World {
 
         // A conflict was found.
         if implication.contains(Implication::Conflict) {
+            self.conflict = cell;
             return None;
         }
 
@@ -70,7 +79,7 @@ impl World {
 
             for i in 0..self.rule.neighborhood_size {
                 if let Some(neighbor) = cell.neighborhood[i].as_ref() {
-                    if neighbor.state().is_none() {
+                    if self.cell_state(neighbor).is_none() {
                         self.set_cell(neighbor, state, Reason::Deduced);
                     }
                 }
@@ -108,16 +117,43 @@ impl World {
             return None;
         }
 
+        // Check if some generation can no longer reach the lower bound of the population,
+        // even if all its unknown cells become alive.
+        if self.config.min_population.is_some_and(|min_population| {
+            self.population
+                .iter()
+                .zip(&self.unknown_count)
+                .any(|(population, unknown)| population + unknown < min_population)
+        }) {
+            return None;
+        }
+
+        // Check if some generation has too many living cells for the exact population,
+        // or can no longer reach it.
+        if self
+            .config
+            .exact_population
+            .is_some_and(|exact_population| {
+                self.population
+                    .iter()
+                    .zip(&self.unknown_count)
+                    .any(|(&population, unknown)| {
+                        population > exact_population || population + unknown < exact_population
+                    })
+            })
+        {
+            return None;
+        }
+
         // Deduce the state of some cells by symmetry.
-        let state = cell.state().unwrap();
+        let state = self.cell_state(cell).unwrap();
         for i in 0..cell.symmetry.len() {
             let symmetry = &*cell.symmetry[i];
-            let symmetry_state = symmetry.state();
 
-            if symmetry_state.is_none() {
-                self.set_cell(symmetry, state, Reason::Deduced);
-            } else if symmetry_state.unwrap() != state {
-                return None;
+            match self.cell_state(symmetry) {
+                None => self.set_cell(symmetry, state, Reason::Deduced),
+                Some(symmetry_state) if symmetry_state != state => return None,
+                _ => {}
             }
         }
 
@@ -161,18 +197,20 @@ impl World {
     /// - If this goes back to the time before the search started, return [`NoSolution`](Status::NoSolution).
     /// - Otherwise, return [`Running`](Status::Running).
     fn backtrack(&mut self) -> Status {
+        self.backtracks += 1;
+
         while let Some((cell, reason)) = self.stack.pop() {
             unsafe {
                 let cell = &*cell;
                 match reason {
                     Reason::Known => break,
-                    Reason::Deduced => self.unset_cell(cell),
+                    Reason::Deduced | Reason::Flipped => self.unset_cell(cell),
                     Reason::Guessed => {
-                        let state = cell.state().unwrap();
+                        let state = self.cell_state(cell).unwrap();
                         self.stack_index = self.stack.len();
                         self.start = cell.next;
                         self.unset_cell(cell);
-                        self.set_cell(cell, !state, Reason::Deduced);
+                        self.set_cell(cell, !state, Reason::Flipped);
                         return Status::Running;
                     }
                 }
@@ -182,13 +220,89 @@ impl World {
         Status::NoSolution
     }
 
+    /// The current depth of the stack, i.e., the number of cells whose states are known.
+    ///
+    /// This can be recorded and passed to [`backtrack_to`](World::backtrack_to) later.
+    #[inline]
+    pub const fn stack_depth(&self) -> usize {
+        self.stack.len()
+    }
+
+    /// Backtrack to a stack depth that was recorded earlier with
+    /// [`stack_depth`](World::stack_depth).
+    ///
+    /// All cells that were deduced or guessed after that point become unknown again.
+    /// Unlike the backtracking in the search, no guess is flipped, so the search will
+    /// make the same guesses again unless the caller changes something in between.
+    ///
+    /// This never unwinds the cells that are known before the search starts.
+    /// If `stack_depth` is not smaller than the current depth, this does nothing.
+    ///
+    /// Some deductions that were made before that point but depend on the unwound cells
+    /// may be lost, so the search may need a few more guesses. This does not affect
+    /// the correctness of the search.
+    pub fn backtrack_to(&mut self, stack_depth: usize) {
+        let mut unwound = false;
+
+        while self.stack.len() > stack_depth {
+            let (cell, reason) = *self.stack.last().unwrap();
+
+            if reason == Reason::Known {
+                break;
+            }
+
+            self.stack.pop();
+            unsafe {
+                self.unset_cell(&*cell);
+            }
+            unwound = true;
+        }
+
+        if unwound {
+            // Look for unknown cells from the beginning, since some cells before
+            // the current starting point may have become unknown.
+            self.start = self.head;
+            self.stack_index = self.stack_index.min(self.stack.len());
+            self.status = Status::Running;
+        }
+    }
+
+    /// Restart the search if it makes no progress for a while.
+    ///
+    /// See [`restart_after`](crate::Config::restart_after) for more details.
+    fn check_restart(&mut self) {
+        let Some(restart_after) = self.restart_after else {
+            return;
+        };
+
+        if self.config.new_state != NewState::Random {
+            return;
+        }
+
+        if self.stack.len() > self.max_depth {
+            self.max_depth = self.stack.len();
+            self.steps_without_progress = 0;
+        } else {
+            self.steps_without_progress += 1;
+
+            if self.steps_without_progress >= restart_after {
+                self.backtrack_to(0);
+                self.max_depth = self.stack.len();
+                self.steps_without_progress = 0;
+                self.restart_after = Some(restart_after.saturating_mul(2));
+            }
+        }
+    }
+
     /// Find a cell whose state is unknown, and make a guess.
     ///
     /// If no cell is found, return [`None`].
     fn guess(&mut self) -> Option<()> {
+        let from_head = self.start == self.head;
+
         unsafe {
             while let Some(cell) = self.start.as_ref() {
-                if cell.state().is_none() {
+                if self.cell_state(cell).is_none() {
                     let state = match self.config.new_state {
                         NewState::Alive => CellState::Alive,
                         NewState::Dead => CellState::Dead,
@@ -202,9 +316,68 @@ impl World {
             }
         }
 
+        // After backtracking a guess made by `guess_cell`, some cells before
+        // the starting point may still be unknown. Look for them from the beginning.
+        if !from_head && self.unknown_count.iter().any(|&count| count > 0) {
+            self.start = self.head;
+            return self.guess();
+        }
+
         None
     }
 
+    /// Force the cell at the given coordinates to be the next guess, with the given state.
+    ///
+    /// This exposes the guessing primitive of the search, so that external code can
+    /// choose the cells to guess one by one, while still using the built-in deduction
+    /// and backtracking. Continue with [`search`](World::search) or
+    /// [`search_with`](World::search_with) to deduce from the guess. If it leads to
+    /// a conflict, the search backtracks and flips it to the opposite state,
+    /// exactly like the guesses made by the search itself.
+    ///
+    /// The coordinates are [canonicalized](World::canonicalize_coord) first.
+    /// Cells outside the world are always known to be dead.
+    ///
+    /// If the cell is already known to have the given state, this does nothing.
+    /// If it is already known to have a different state, a [`ConflictError`] is returned,
+    /// and the world is left unchanged.
+    ///
+    /// Otherwise, the guess is pushed onto the stack, and the status becomes
+    /// [`Running`](Status::Running), even if it was [`NoSolution`](Status::NoSolution) before.
+    /// The following invariants of the search are upheld:
+    ///
+    /// - The guess is recorded after all the cells that are already on the stack, and
+    ///   the cells that have not been checked yet are still checked before the search
+    ///   makes a new guess of its own.
+    /// - The cells that are known before the search starts stay at the bottom of the stack.
+    ///   In particular, [`try_set_known`](World::try_set_known) can no longer be used
+    ///   afterwards.
+    /// - The search never reports a solution while some cell is unknown, even if
+    ///   the forced guess is out of the [search order](crate::Config::search_order).
+    pub fn guess_cell(&mut self, coord: Coord, state: CellState) -> Result<(), ConflictError> {
+        let cell = self.get_cell_by_coord_ptr(self.canonicalize_coord(coord));
+
+        let existing = if cell.is_null() {
+            Some(CellState::Dead)
+        } else {
+            unsafe { self.cell_state(&*cell) }
+        };
+
+        match existing {
+            None => {
+                unsafe { self.set_cell(&*cell, state, Reason::Guessed) };
+                self.status = Status::Running;
+                Ok(())
+            }
+            Some(existing) if existing == state => Ok(()),
+            Some(existing) => Err(ConflictError {
+                coord,
+                existing,
+                attempted: state,
+            }),
+        }
+    }
+
     /// One step of the search.
     ///
     /// Check all cells in the stack that have not been checked yet,
@@ -225,11 +398,20 @@ impl World {
         }
     }
 
-    /// When a pattern is found, check that its period is correct.
+    /// The smallest period of the pattern, taking the translation into account.
     ///
-    /// For example, when we are searching for a period 4 oscillator,
-    /// we need to exclude still lifes and period 2 oscillators.
-    fn check_period(&self) -> bool {
+    /// This is always a divisor of the [`period`](crate::Config::period) of the world.
+    /// It is only meaningful when the search is [solved](Status::Solved),
+    /// since unknown cells are treated as if they had a state of their own.
+    ///
+    /// Only the divisors `p0` of the period such that the translation can be evenly split
+    /// into `period / p0` steps are checked. For an oscillator, i.e., when there is no
+    /// translation, every divisor is a candidate; for a spaceship, most divisors can be skipped
+    /// without looking at the cells.
+    ///
+    /// This scans the whole world each time it is called.
+    /// Use [`cached_true_period`](World::cached_true_period) to avoid repeating the scan.
+    pub fn true_period(&self) -> u32 {
         let (w, h, p) = (
             self.config.width as i32,
             self.config.height as i32,
@@ -239,13 +421,17 @@ impl World {
         let dy = self.config.dy;
 
         // The actual period of the pattern must be a divisor of the period we are searching for.
+        // Check the divisors from the smallest to the largest.
 
-        'd: for d in 2..=p {
-            if p % d == 0 && dx % d == 0 && dy % d == 0 {
-                // Check that if the actual period is p / d.
-                // If so, return false.
+        'p0: for p0 in 1..p {
+            if p % p0 == 0 {
+                let d = p / p0;
+
+                if dx % d != 0 || dy % d != 0 {
+                    continue;
+                }
 
-                let p0 = p / d;
+                // Check that if the actual period is p0.
                 let dx0 = dx / d;
                 let dy0 = dy / d;
 
@@ -255,55 +441,669 @@ impl World {
                         let state0 = self.get_cell_state((x, y, 0));
                         let state1 = self.get_cell_state((x - dx0, y - dy0, p0));
                         if state0 != state1 {
-                            continue 'd;
+                            continue 'p0;
                         }
                     }
                 }
 
-                return false;
+                return p0 as u32;
             }
         }
 
-        true
+        p as u32
+    }
+
+    /// The same as [`true_period`](World::true_period), but the result is cached
+    /// until the next time a cell is set or unset.
+    ///
+    /// This is useful when the period of the same solution is queried several times,
+    /// e.g., by the search itself and then by the caller.
+    pub fn cached_true_period(&self) -> u32 {
+        if let Some(period) = self.true_period_cache.get() {
+            return period;
+        }
+
+        let period = self.true_period();
+        self.true_period_cache.set(Some(period));
+        period
+    }
+
+    /// The velocity of a solved spaceship, as `(dx, dy, period)`.
+    ///
+    /// The period is the [true period](World::true_period), and the displacement is
+    /// reduced accordingly. For example, a period-8 search with a translation of `(2, 2)`
+    /// that finds a glider gives `(1, 1, 4)`, i.e., `c/4` diagonal.
+    /// The result is in the same form as the arguments of
+    /// [`with_velocity`](crate::Config::with_velocity).
+    ///
+    /// Return [`None`] if the search is not [solved](Status::Solved),
+    /// or if the pattern does not move, i.e., it is an oscillator or a still life.
+    pub fn velocity(&self) -> Option<(i32, i32, u32)> {
+        if self.status != Status::Solved || (self.config.dx, self.config.dy) == (0, 0) {
+            return None;
+        }
+
+        let period = self.cached_true_period();
+        let d = (self.config.period / period) as i32;
+
+        Some((self.config.dx / d, self.config.dy / d, period))
+    }
+
+    /// When a pattern is found, check that its period is correct.
+    ///
+    /// For example, when we are searching for a period 4 oscillator,
+    /// we need to exclude still lifes and period 2 oscillators.
+    ///
+    /// If [`search_up_to_period`](crate::Config::search_up_to_period) is set,
+    /// any period that divides the period of the world is accepted.
+    ///
+    /// When the period is 1, there is nothing to check.
+    ///
+    /// This does not rely on the front. With [generation rotation](crate::Config::allow_generation_rotation),
+    /// the front only lies on one generation, and a pattern with a smaller period can meet it
+    /// just as well, so such patterns always reach this check. The whole first generation is
+    /// compared, which determines all the later generations, so they are rejected here.
+    fn check_period(&self) -> bool {
+        self.config.period == 1
+            || self.config.search_up_to_period.is_some()
+            || self.cached_true_period() == self.config.period
+    }
+
+    /// When a pattern is found, check that it touches all four edges of the world,
+    /// if [`full_bounding_box`](crate::Config::full_bounding_box) is enabled.
+    ///
+    /// The edges may be touched in different generations.
+    fn check_bounding_box(&self) -> bool {
+        if !self.config.full_bounding_box {
+            return true;
+        }
+
+        let (w, h, p) = (
+            self.config.width as i32,
+            self.config.height as i32,
+            self.config.period as i32,
+        );
+
+        let is_alive = |x, y, t| self.get_cell_state((x, y, t)) == Some(CellState::Alive);
+
+        let mut top = false;
+        let mut bottom = false;
+        let mut left = false;
+        let mut right = false;
+
+        for t in 0..p {
+            for x in 0..w {
+                top |= is_alive(x, 0, t);
+                bottom |= is_alive(x, h - 1, t);
+            }
+
+            for y in 0..h {
+                left |= is_alive(0, y, t);
+                right |= is_alive(w - 1, y, t);
+            }
+        }
+
+        top && bottom && left && right
+    }
+
+    /// Check that every generation has exactly the [`exact_population`](crate::Config::exact_population)
+    /// if it is specified.
+    fn check_exact_population(&self) -> bool {
+        self.config.exact_population.is_none_or(|exact_population| {
+            self.population
+                .iter()
+                .all(|&population| population == exact_population)
+        })
+    }
+
+    /// Check that every cell in [`require_alive`](crate::Config::require_alive) is alive.
+    fn check_require_alive(&self) -> bool {
+        self.config
+            .require_alive
+            .iter()
+            .all(|&coord| self.get_cell_state(coord) == Some(CellState::Alive))
+    }
+
+    /// Check that the pattern does not repeat itself within a [torus](Boundary::Torus),
+    /// so that it is not a solution on a smaller torus.
+    ///
+    /// Only horizontal and vertical shifts are checked, since a diagonal repeat does not
+    /// give a smaller rectangular torus. Among them, only shifts by a proper divisor of
+    /// the width or the height that give a torus large enough to be searched are checked.
+    /// Smaller repeats are allowed, since their patterns can only be found on this torus
+    /// or a larger one. It suffices to check the first generation, since the other
+    /// generations are determined by it.
+    fn check_spatial_period(&self) -> bool {
+        if self.config.boundary != Boundary::Torus {
+            return true;
+        }
+
+        let (w, h) = (self.config.width as i32, self.config.height as i32);
+        let min_size = 2 * self.parsed_rule.radius() as i32 + 1;
+
+        let repeats = |sx: i32, sy: i32| {
+            (0..w).all(|x| {
+                (0..h).all(|y| {
+                    self.get_cell_state((x, y, 0)) == self.get_cell_state((x + sx, y + sy, 0))
+                })
+            })
+        };
+
+        !(min_size..w).any(|d| w % d == 0 && repeats(d, 0))
+            && !(min_size..h).any(|d| h % d == 0 && repeats(0, d))
+    }
+
+    /// Check that a pattern found by the search passes all the built-in checks:
+    /// its period is correct, it touches all edges if required, it has the exact population
+    /// if required, it does not repeat itself on a torus, and the required cells are alive.
+    fn is_acceptable(&self) -> bool {
+        self.check_period()
+            && self.check_bounding_box()
+            && self.check_exact_population()
+            && self.check_spatial_period()
+            && self.check_require_alive()
+    }
+
+    /// Backtrack from a solution to look for the next one.
+    ///
+    /// If [`reduce_max_population`](crate::Config::reduce_max_population) is enabled,
+    /// the maximum population is first reduced below the population of the solution.
+    fn backtrack_from_solution(&mut self) -> Status {
+        if self.config.reduce_max_population {
+            let population = *self.population.iter().min().unwrap();
+            self.max_population = Some(population - 1);
+            self.config.max_population = self.max_population;
+        }
+        self.backtrack()
     }
 
     /// The main loop of the search.
     ///
-    /// Search for a solution, or until the maximum number of steps is reached.
+    /// Run until a solution is found, no more solutions exist, or `steps` reaches `max_steps`.
+    /// `steps` is incremented for each step, so that it can be shared between several calls.
+    ///
+    /// A pattern that passes the built-in checks is only accepted as a solution if `accept`
+    /// returns `true`. Otherwise the search backtracks as if the pattern were invalid.
     ///
     /// Update and return the search status.
-    pub fn search(&mut self, max_steps: impl Into<Option<usize>>) -> Status {
-        let mut steps = 0;
-        let max_steps = max_steps.into();
+    fn search_steps(
+        &mut self,
+        max_steps: Option<usize>,
+        steps: &mut usize,
+        accept: &impl Fn(&Self) -> bool,
+    ) -> Status {
+        // If there are no steps left, do nothing, not even backtracking from a solution.
+        if max_steps.is_some_and(|max_steps| *steps >= max_steps) {
+            return self.status;
+        }
 
         let mut status = match self.status {
             // If the current status is `Solved`, backtrack to find the next solution.
-            Status::Solved => {
-                if self.config.reduce_max_population {
-                    let population = *self.population.iter().min().unwrap();
-                    self.max_population = Some(population - 1);
-                    self.config.max_population = self.max_population;
-                }
-                self.backtrack()
-            }
+            Status::Solved => self.backtrack_from_solution(),
             Status::NoSolution => Status::NoSolution,
             _ => Status::Running,
         };
 
-        while status == Status::Running && !max_steps.is_some_and(|max_steps| steps >= max_steps) {
+        while status == Status::Running && max_steps.is_none_or(|max_steps| *steps < max_steps) {
             status = self.step();
 
-            // If a pattern is found, check that its period is correct,
-            // and backtrack if not.
-            if status == Status::Solved && !self.check_period() {
+            // If a pattern is found, check that it passes the built-in checks,
+            // and that it is accepted by the filter.
+            // Backtrack if not.
+            if status == Status::Solved
+                && !(self.is_acceptable() && {
+                    self.status = Status::Solved;
+                    accept(self)
+                })
+            {
                 status = self.backtrack();
             }
 
-            steps += 1;
+            if status == Status::Running {
+                self.check_restart();
+            }
+
+            *steps += 1;
+            self.steps += 1;
         }
 
         self.status = status;
 
         status
     }
+
+    /// Search for a solution, or until the maximum number of steps is reached.
+    ///
+    /// If `max_steps` is `Some(0)`, this is a no-op that returns the current status.
+    /// In particular, a world that is already [`Solved`](Status::Solved) stays solved,
+    /// instead of backtracking to look for the next solution.
+    ///
+    /// Update and return the search status.
+    pub fn search(&mut self, max_steps: impl Into<Option<usize>>) -> Status {
+        self.search_steps(max_steps.into(), &mut 0, &|_| true)
+    }
+
+    /// Search for a solution, or until the maximum number of steps is reached,
+    /// and return the number of steps actually performed along with the status.
+    ///
+    /// This is the same as [`search`](World::search). The number of steps is less than
+    /// `max_steps` if the search found a solution or ran out of solutions early,
+    /// which can be used to adapt the step budget of the next call.
+    pub fn search_counted(&mut self, max_steps: impl Into<Option<usize>>) -> (Status, usize) {
+        let mut steps = 0;
+        let status = self.search_steps(max_steps.into(), &mut steps, &|_| true);
+        (status, steps)
+    }
+
+    /// Search for a solution that is accepted by a user-defined filter,
+    /// or until the maximum number of steps is reached.
+    ///
+    /// This is the same as [`search`](World::search), but each time a pattern passing
+    /// the built-in checks (the period, the bounding box, etc.) is found, `accept` is called
+    /// with the world in the [`Solved`](Status::Solved) state. If it returns `false`,
+    /// the pattern is rejected, and the search backtracks and continues as if the pattern
+    /// were invalid. Rejected patterns do not affect
+    /// [`reduce_max_population`](crate::Config::reduce_max_population).
+    ///
+    /// Update and return the search status.
+    pub fn search_with_filter(
+        &mut self,
+        max_steps: impl Into<Option<usize>>,
+        accept: impl Fn(&Self) -> bool,
+    ) -> Status {
+        self.search_steps(max_steps.into(), &mut 0, &accept)
+    }
+
+    /// Search for a solution, or until the search has backtracked `max_backtracks` times.
+    ///
+    /// This is the same as [`search`](World::search), but the budget is the number of
+    /// backtracks instead of the number of steps, which measures how "stuck" the search is
+    /// rather than how much work it has done. Backtracking from a solution to look for the next
+    /// one also counts. The number of backtracks so far is in [`stats`](World::stats).
+    ///
+    /// If `max_backtracks` is `0`, this is a no-op that returns the current status.
+    ///
+    /// The budget is checked between steps, and a step that resumes from a solution
+    /// backtracks once from the solution and possibly once more on a conflict,
+    /// so the search may backtrack `max_backtracks + 1` times.
+    ///
+    /// Update and return the search status. If the budget is exhausted before a solution
+    /// is found or the search is finished, the status is [`Running`](Status::Running).
+    pub fn search_max_backtracks(&mut self, max_backtracks: u64) -> Status {
+        let start = self.backtracks;
+
+        // A step backtracks at most once, or twice if it starts from a solution,
+        // so the budget is checked after every step and may be exceeded by one.
+        while self.backtracks - start < max_backtracks {
+            let status = self.search_steps(Some(1), &mut 0, &|_| true);
+
+            if status != Status::Running {
+                return status;
+            }
+        }
+
+        self.status
+    }
+
+    /// Search for a solution, or until the given deadline is reached.
+    ///
+    /// The clock is checked once every 1024 steps, so the search may run
+    /// slightly past the deadline. If the deadline has already passed, this is a no-op
+    /// that returns the current status, just like [`search`](World::search) with `Some(0)`.
+    ///
+    /// Since the deadline is an absolute point in time, several calls, possibly on
+    /// different worlds, can share the same deadline without recomputing the remaining time.
+    ///
+    /// Update and return the search status. If the deadline is reached before a solution
+    /// is found or the search is finished, the status is [`Running`](Status::Running).
+    pub fn search_until_instant(&mut self, deadline: Instant) -> Status {
+        while Instant::now() < deadline {
+            let status = self.search_steps(Some(DEADLINE_CHECK_INTERVAL), &mut 0, &|_| true);
+
+            if status != Status::Running {
+                return status;
+            }
+        }
+
+        self.status
+    }
+
+    /// Search for a solution, or until the maximum number of steps is reached,
+    /// and report why the search stopped.
+    ///
+    /// This is the same as [`search`](World::search), but the returned [`SearchOutcome`]
+    /// also tells whether the step budget was exhausted.
+    pub fn search_outcome(&mut self, max_steps: impl Into<Option<usize>>) -> SearchOutcome {
+        let status = self.search(max_steps);
+
+        SearchOutcome {
+            status,
+            exhausted_budget: matches!(status, Status::NotStarted | Status::Running),
+        }
+    }
+
+    /// Run one batch of the search with a fixed step budget, for cooperative scheduling.
+    ///
+    /// The search itself is synchronous, and never spawns a thread. To run it in an async
+    /// runtime, or in a single-threaded environment like WebAssembly in a browser, call this
+    /// repeatedly with a small budget, and give the control back to the scheduler between
+    /// the calls, e.g. with `tokio::task::yield_now().await`, or by scheduling the next batch
+    /// with `requestAnimationFrame`.
+    ///
+    /// The budget is at least 1 step, so that each call makes progress.
+    /// Otherwise this is the same as [`search_outcome`](World::search_outcome):
+    /// keep calling it while [`exhausted_budget`](SearchOutcome::exhausted_budget) is `true`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use factoriosrc_lib::{Config, Status, World};
+    /// let mut world = World::new(Config::new("B3/S23", 3, 3, 2)).unwrap();
+    ///
+    /// let outcome = loop {
+    ///     let outcome = world.search_batch(100);
+    ///     if !outcome.exhausted_budget {
+    ///         break outcome;
+    ///     }
+    ///     // In an async function, yield to the runtime here:
+    ///     // tokio::task::yield_now().await;
+    /// };
+    ///
+    /// assert_eq!(outcome.status, Status::Solved);
+    /// ```
+    pub fn search_batch(&mut self, budget: usize) -> SearchOutcome {
+        self.search_outcome(budget.max(1))
+    }
+
+    /// Check whether the known cells are still consistent with the rule,
+    /// without making any guess.
+    ///
+    /// This propagates the deductions from the cells that have been set,
+    /// e.g. with [`try_set_known`](World::try_set_known), and returns `false` if a conflict
+    /// is found. It is much cheaper than a full search, so it can be called each time
+    /// a cell is changed, e.g. to give immediate feedback in an editor.
+    ///
+    /// Returning `true` does not mean that a solution exists, only that no contradiction
+    /// can be found without guessing.
+    ///
+    /// The deduced cells stay set, and the status is not changed, so the search can be
+    /// started or resumed afterwards as usual.
+    pub fn is_consistent(&mut self) -> bool {
+        self.status != Status::NoSolution && self.check_stack().is_some()
+    }
+
+    /// Search until the first time a neighborhood descriptor implies a conflict,
+    /// and return the coordinates of the cell whose descriptor conflicted.
+    ///
+    /// This is useful for diagnosing over-constrained configurations:
+    /// when a search immediately returns [`NoSolution`](Status::NoSolution),
+    /// the returned cell shows where the contradiction arises.
+    ///
+    /// The search stops before backtracking, so the cells that lead to the conflict
+    /// are still set. Conflicts that are not found by a descriptor, e.g., an empty front
+    /// or a population that is too large, are backtracked as usual.
+    ///
+    /// If a solution is found, or no more solutions exist, before any such conflict,
+    /// return [`None`], and the status is updated as in [`search`](World::search).
+    /// Otherwise the status is [`Running`](Status::Running), and the search can be
+    /// resumed from the conflict with [`search`](World::search).
+    pub fn first_conflict(&mut self) -> Option<Coord> {
+        let mut status = match self.status {
+            Status::Solved => self.backtrack_from_solution(),
+            Status::NoSolution => Status::NoSolution,
+            _ => Status::Running,
+        };
+
+        while status == Status::Running {
+            self.conflict = std::ptr::null();
+
+            if self.check_stack().is_none() {
+                if !self.conflict.is_null() {
+                    self.status = Status::Running;
+                    let coord = unsafe { self.index_to_coord(self.cell_to_index(self.conflict)) };
+                    return Some(coord);
+                }
+
+                status = self.backtrack();
+            } else if self.guess().is_none() {
+                status = if self.is_acceptable() {
+                    Status::Solved
+                } else {
+                    self.backtrack()
+                };
+            }
+        }
+
+        self.status = status;
+
+        None
+    }
+
+    /// Search for solutions until no more solutions exist, or the maximum number
+    /// of steps is reached.
+    ///
+    /// Unlike [`search`](World::search), this does not stop when a solution is found.
+    /// Instead, `on_solution` is called with the world in the [`Solved`](Status::Solved)
+    /// state, and then the search continues to look for the next solution.
+    /// [`reduce_max_population`](crate::Config::reduce_max_population) is applied
+    /// between solutions, just like when calling [`search`](World::search) repeatedly.
+    ///
+    /// If the maximum number of steps is reached right after a solution is found,
+    /// the returned status is [`Solved`](Status::Solved), and the callback has already
+    /// been called for that solution.
+    ///
+    /// Update and return the search status.
+    pub fn search_with(
+        &mut self,
+        max_steps: impl Into<Option<usize>>,
+        mut on_solution: impl FnMut(&Self),
+    ) -> Status {
+        let max_steps = max_steps.into();
+        let mut steps = 0;
+
+        loop {
+            if max_steps.is_some_and(|max_steps| steps >= max_steps) {
+                return self.status;
+            }
+
+            let status = self.search_steps(max_steps, &mut steps, &|_| true);
+
+            if status != Status::Solved {
+                return status;
+            }
+
+            on_solution(self);
+        }
+    }
+
+    /// Search for `n` distinct solutions, and return their
+    /// [canonical RLEs](World::canonical_solution_rle).
+    ///
+    /// Solutions that only differ by a transformation, a translation, or a phase are the same
+    /// object, and only the first one found is kept, so the result never contains duplicates.
+    /// The RLEs are in the order the solutions are found, which follows the
+    /// [search order](crate::Config::search_order) and the choice of
+    /// [`new_state`](crate::Config::new_state).
+    ///
+    /// The search stops as soon as `n` distinct solutions are found, and stays at the last one,
+    /// so it can be continued later. If fewer than `n` solutions are returned, the search space
+    /// is exhausted and the status is [`NoSolution`](Status::NoSolution).
+    pub fn find_distinct(&mut self, n: usize) -> Vec<String> {
+        self.distinct_solutions(n)
+            .into_iter()
+            .map(|(_, rle)| rle)
+            .collect()
+    }
+
+    /// Search for up to `limit` distinct solutions, and return their
+    /// [canonical RLEs](World::canonical_solution_rle), sorted by the population of their
+    /// [minimal phase](World::minimal_phase), and then lexicographically by the RLE.
+    ///
+    /// The solutions are collected as in [`find_distinct`](World::find_distinct), so they are
+    /// the first `limit` distinct solutions that the search finds, not necessarily the smallest
+    /// ones. Only with an unlimited `limit`, e.g. [`usize::MAX`], is the result a complete
+    /// catalog of the smallest solutions first. The order does not depend on the order in which
+    /// the solutions are found.
+    pub fn enumerate_sorted(&mut self, limit: usize) -> Vec<String> {
+        let mut solutions = self.distinct_solutions(limit);
+        solutions.sort_unstable();
+        solutions.into_iter().map(|(_, rle)| rle).collect()
+    }
+
+    /// Search for `n` distinct solutions, and return the populations of their minimal phases,
+    /// and their canonical RLEs, in the order they are found.
+    fn distinct_solutions(&mut self, n: usize) -> Vec<(usize, String)> {
+        let mut seen = HashSet::new();
+        let mut solutions = Vec::new();
+
+        while solutions.len() < n && self.search(None) == Status::Solved {
+            let rle = self.canonical_solution_rle().unwrap();
+            if seen.insert(rle.clone()) {
+                solutions.push((self.population(self.minimal_phase()), rle));
+            }
+        }
+
+        solutions
+    }
+
+    /// Enumerate all solutions using several threads.
+    ///
+    /// The search tree is split on the first few unknown cells in the search order:
+    /// each branch fixes these cells to a different combination of states, so the
+    /// branches are disjoint and together cover the whole search space. The branches
+    /// are distributed among `threads` threads, each of which builds its own world from
+    /// the configuration and the cells that are known before the search starts.
+    ///
+    /// Return the solutions in [RLE format](World::rle) of the 0-th generation,
+    /// without duplicates. They are ordered by branch, and then in the order they are found.
+    ///
+    /// Any progress of the search in `self` is ignored; the search starts from scratch.
+    /// Since the branches are searched independently,
+    /// [`reduce_max_population`](crate::Config::reduce_max_population) only applies within
+    /// each branch. If `threads` is 0, a single thread is used. No more threads are spawned
+    /// than the [available parallelism](std::thread::available_parallelism) or the number of
+    /// branches.
+    pub fn search_parallel(self, threads: usize) -> Vec<String> {
+        let parallelism = std::thread::available_parallelism().map_or(1, NonZeroUsize::get);
+        let threads = threads.clamp(1, parallelism);
+        let config = self.config.clone();
+        let memory_limit = self.memory_limit;
+
+        // The cells that are known before the search starts, including the ones
+        // set by `try_set_known`.
+        let known = self
+            .stack
+            .iter()
+            .filter(|&&(_, reason)| reason == Reason::Known)
+            .map(|&(cell, _)| unsafe {
+                let coord = self.index_to_coord(self.cell_to_index(cell));
+                (coord, self.cell_state(&*cell).unwrap())
+            })
+            .collect::<Vec<_>>();
+
+        drop(self);
+
+        // Create a world for a branch, with the known cells and the cells fixed by the branch.
+        // Return `None` if the fixed cells conflict with the known cells.
+        let new_world = |fixed: &[(Coord, CellState)]| {
+            let mut world = Self::new_with_limit(config.clone(), memory_limit).ok()?;
+            for &(coord, state) in known.iter().chain(fixed) {
+                world.try_set_known(coord, state).ok()?;
+            }
+            Some(world)
+        };
+
+        // Find the first few unknown cells in the search order, enough to give each
+        // thread at least one branch.
+        let mut split = Vec::new();
+        if let Some(world) = new_world(&[]) {
+            let mut cell = world.head;
+            while split.len() < threads.next_power_of_two().trailing_zeros() as usize {
+                let Some(c) = (unsafe { cell.as_ref() }) else {
+                    break;
+                };
+                if unsafe { world.cell_state(c) }.is_none() {
+                    split.push(world.index_to_coord(unsafe { world.cell_to_index(cell) }));
+                }
+                cell = c.next;
+            }
+        }
+
+        let branches = (0..1usize << split.len())
+            .map(|i| {
+                split
+                    .iter()
+                    .enumerate()
+                    .map(|(j, &coord)| {
+                        let state = if i >> j & 1 == 0 {
+                            CellState::Dead
+                        } else {
+                            CellState::Alive
+                        };
+                        (coord, state)
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+
+        // Do not spawn threads that would have no branch to search.
+        let threads = threads.min(branches.len());
+
+        let mut results = std::thread::scope(|scope| {
+            // Spawn all the threads before joining any of them.
+            let mut handles = Vec::with_capacity(threads);
+            for i in 0..threads {
+                let branches = &branches;
+                let new_world = &new_world;
+                handles.push(scope.spawn(move || {
+                    branches
+                        .iter()
+                        .enumerate()
+                        .skip(i)
+                        .step_by(threads)
+                        .map(|(b, fixed)| {
+                            let mut solutions = Vec::new();
+                            if let Some(mut world) = new_world(fixed) {
+                                world.search_with(None, |world| {
+                                    solutions.push(world.rle(0, true));
+                                });
+                            }
+                            (b, solutions)
+                        })
+                        .collect::<Vec<_>>()
+                }));
+            }
+
+            handles
+                .into_iter()
+                .flat_map(|handle| handle.join().unwrap())
+                .collect::<Vec<_>>()
+        });
+
+        results.sort_unstable_by_key(|&(b, _)| b);
+
+        let mut seen = HashSet::new();
+        results
+            .into_iter()
+            .flat_map(|(_, solutions)| solutions)
+            .filter(|solution| seen.insert(solution.clone()))
+            .collect()
+    }
+
+    /// Enumerate all the solutions, and return their
+    /// [canonical RLEs](World::canonical_solution_rle).
+    ///
+    /// Solutions that only differ by a transformation, a translation, or a phase are the same
+    /// in the result, so two configurations that should find the same patterns can be compared,
+    /// even if they find them in different positions or orientations.
+    ///
+    /// This is only meant for tests on small worlds.
+    #[cfg(test)]
+    pub(crate) fn enumerate_all_solutions(&mut self) -> BTreeSet<String> {
+        let mut solutions = BTreeSet::new();
+        self.search_with(None, |world| {
+            solutions.insert(world.canonical_solution_rle().unwrap());
+        });
+        solutions
+    }
 }
@@ -25,6 +25,10 @@ use strum::{Display, EnumIter, EnumString, IntoEnumIterator};
 /// Some require the world to have no translation.
 ///
 /// The notation is based on the notation used in group theory.
+///
+/// The same string representation is used by [`Display`](std::fmt::Display),
+/// [`FromStr`](std::str::FromStr), and, when the corresponding features are enabled,
+/// `clap` and `serde`.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Display, EnumIter, EnumString)]
 #[cfg_attr(feature = "clap", derive(ValueEnum), value(rename_all = "PascalCase"))]
 #[cfg_attr(feature = "documented", derive(Documented, DocumentedFields))]
@@ -244,6 +248,10 @@ impl Transformation {
 ///
 /// The notation is borrowed from the Oscar Cunningham's
 /// [Logic Life Search](https://github.com/OscarCunningham/logic-life-search).
+///
+/// The same string representation (e.g. `D2|` for [`D2H`](Symmetry::D2H)) is used by
+/// [`Display`](std::fmt::Display), [`FromStr`](std::str::FromStr), and, when the
+/// corresponding features are enabled, `clap` and `serde`.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Display, EnumIter, EnumString)]
 #[cfg_attr(feature = "clap", derive(ValueEnum), value(rename_all = "PascalCase"))]
 #[cfg_attr(feature = "documented", derive(Documented, DocumentedFields))]
@@ -316,6 +324,7 @@ pub enum Symmetry {
     /// Symmetry with respect to both diagonal and antidiagonal reflections.
     ///
     /// This requires the world to be square, and have no translation.
+    #[cfg_attr(feature = "clap", value(name = "D4X"))]
     D4X,
 
     /// Symmetry with respect to all the above rotations and reflections.
@@ -501,4 +510,43 @@ mod tests {
             }
         }
     }
+
+    /// The string forms used by [`Display`], `clap` and `serde` should all agree.
+    #[test]
+    #[cfg(all(feature = "clap", feature = "serde"))]
+    fn test_string_representations() {
+        use std::str::FromStr;
+
+        fn check<T>(value: T)
+        where
+            T: std::fmt::Display
+                + FromStr
+                + ValueEnum
+                + Serialize
+                + for<'de> Deserialize<'de>
+                + PartialEq
+                + std::fmt::Debug,
+            <T as FromStr>::Err: std::fmt::Debug,
+        {
+            let display = value.to_string();
+
+            let clap = value.to_possible_value().unwrap();
+            assert_eq!(clap.get_name(), display);
+
+            let serde = serde_json::to_string(&value).unwrap();
+            assert_eq!(serde, serde_json::to_string(&display).unwrap());
+
+            assert_eq!(<T as FromStr>::from_str(&display).unwrap(), value);
+            assert_eq!(<T as ValueEnum>::from_str(&display, false).unwrap(), value);
+            assert_eq!(serde_json::from_str::<T>(&serde).unwrap(), value);
+        }
+
+        for t in Transformation::iter() {
+            check(t);
+        }
+
+        for s in Symmetry::iter() {
+            check(s);
+        }
+    }
 }
@@ -1,18 +1,29 @@
+#[cfg(feature = "packed-state")]
+use crate::cell::PackedStates;
 #[cfg(feature = "serde")]
-use crate::error::SerdeError;
+use crate::error::{FindError, SerdeError};
 use crate::{
     cell::LifeCell,
-    config::{Config, SearchOrder},
-    error::ConfigError,
-    rule::{CellState, RuleTable},
-    symmetry::Symmetry,
+    config::{Boundary, Config, NewState, RelaxStrategy, SearchOrder},
+    error::{ConfigError, ConflictError, RleError, SeedError},
+    rle::{RleToken, RleTokenizer},
+    rule::{CellState, RuleTable, RuleTableCache},
+    symmetry::Transformation,
 };
+use ca_rules2::Rule;
 #[cfg(feature = "documented")]
 use documented::{Documented, DocumentedFields};
 use rand::SeedableRng;
 use rand_xoshiro::Xoshiro256PlusPlus;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize, Serializer};
+use std::{
+    cell::Cell,
+    collections::HashSet,
+    fmt::{self, Formatter},
+    io::BufRead,
+    sync::Arc,
+};
 use strum::Display;
 
 /// Coordinates of a cell in the world.
@@ -21,6 +32,27 @@ use strum::Display;
 /// The third coordinate is the generation of the cell.
 pub type Coord = (i32, i32, i32);
 
+/// Create the [`Coord`] of the cell at `(x, y)` on generation `t`.
+///
+/// This is the same as the tuple `(x, y, t)`, but reads better when building seed patterns.
+#[inline]
+pub const fn coord(x: i32, y: i32, t: i32) -> Coord {
+    (x, y, t)
+}
+
+/// Translate a [`Coord`] by `(dx, dy)`, keeping its generation.
+///
+/// ```
+/// use factoriosrc_lib::{coord, translate};
+///
+/// assert_eq!(translate(coord(1, 2, 3), -1, 1), (0, 3, 3));
+/// ```
+#[inline]
+pub const fn translate(coord: Coord, dx: i32, dy: i32) -> Coord {
+    let (x, y, t) = coord;
+    (x + dx, y + dy, t)
+}
+
 /// The reason why a cell is set to a state.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -36,6 +68,14 @@ pub(crate) enum Reason {
     /// The state is chosen as a guess.
     #[cfg_attr(feature = "serde", serde(rename = "g"))]
     Guessed,
+
+    /// The state is the opposite of a guess that led to a conflict,
+    /// set when backtracking from that guess.
+    ///
+    /// It is undone like a deduction, but unlike a deduction,
+    /// it cannot be recovered from the other cells on the stack.
+    #[cfg_attr(feature = "serde", serde(rename = "f"))]
+    Flipped,
 }
 
 /// Status of the search.
@@ -53,6 +93,125 @@ pub enum Status {
     NoSolution,
 }
 
+/// The outcome of a call to [`World::search_outcome`].
+///
+/// The [`Status`] alone does not tell why a search stopped while it is still
+/// [`Running`](Status::Running). This also records whether the step budget ran out,
+/// so that front-ends can tell "stopped by the budget, can be resumed" from
+/// "stopped because there is something to report" without keeping their own state.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SearchOutcome {
+    /// The search status after the call.
+    pub status: Status,
+    /// Whether the search stopped because the step budget ran out,
+    /// before finding a solution or running out of solutions.
+    pub exhausted_budget: bool,
+}
+
+impl SearchOutcome {
+    /// Whether the search is truly finished, i.e., there are no more solutions.
+    #[inline]
+    pub fn is_finished(self) -> bool {
+        self.status == Status::NoSolution
+    }
+
+    /// Whether the search can be resumed by calling [`World::search`] again.
+    ///
+    /// This is the case if the budget ran out, or if a solution was found,
+    /// in which case the next call looks for the next solution.
+    #[inline]
+    pub fn is_resumable(self) -> bool {
+        !self.is_finished()
+    }
+}
+
+/// Where the cells of the old world are placed in the new world by
+/// [`World::grow_preserving`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum GrowAlignment {
+    /// The old world is placed at the top left corner of the new world,
+    /// so that the coordinates of the cells are unchanged.
+    #[default]
+    Corner,
+    /// The old world is placed at the center of the new world.
+    ///
+    /// If the size grows by an odd number, the extra row or column is added to the top or
+    /// the left side. In particular, when the size grows by 1, this is the opposite corner
+    /// of [`Corner`](GrowAlignment::Corner).
+    Center,
+}
+
+/// Statistics of the search so far, returned by [`World::stats`].
+///
+/// The counters are cumulative over all calls to the search methods,
+/// and are kept in [checkpoints](World::checkpoint).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SearchStats {
+    /// The number of steps made by the search.
+    ///
+    /// Each step either checks the cells in the stack and makes a guess, or backtracks.
+    pub steps: u64,
+    /// The number of times the search has backtracked.
+    pub backtracks: u64,
+    /// The current depth of the stack, i.e., the number of cells whose states are known.
+    pub stack_depth: usize,
+    /// The number of living cells on each generation.
+    pub population: Vec<usize>,
+}
+
+/// A summary of the values derived from the configuration when creating a [`World`].
+///
+/// Returned by [`World::plan`]. It shows what the search is going to do
+/// without actually searching, so that the configuration can be tuned before a long run.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SearchPlan {
+    /// The search order, resolved from the configuration if it is not specified.
+    pub search_order: SearchOrder,
+    /// The width of the world.
+    pub width: u32,
+    /// The height of the world.
+    pub height: u32,
+    /// The period of the pattern.
+    pub period: u32,
+    /// The width of the frame of dead cells around the world.
+    pub border: u32,
+    /// The number of cells in the world, including the cells in the border.
+    pub cell_count: usize,
+    /// The number of cells in the search region, i.e., `width * height * period`.
+    pub region_cell_count: usize,
+    /// The number of cells on the front.
+    ///
+    /// At least one of them must be alive, so that the search does not find
+    /// the same pattern shifted by one cell.
+    pub front_count: usize,
+    /// The offsets of the neighbors relative to the center cell.
+    pub offsets: Vec<(i32, i32)>,
+    /// The estimated memory used by the cells and the stack, in bytes.
+    pub world_bytes: usize,
+    /// The memory used by the rule table, in bytes.
+    pub table_bytes: usize,
+}
+
+impl fmt::Display for SearchPlan {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        writeln!(f, "search order: {}", self.search_order)?;
+        writeln!(
+            f,
+            "world size: {}x{}, period {}, border {}",
+            self.width, self.height, self.period, self.border
+        )?;
+        writeln!(
+            f,
+            "cells: {}, {} in the search region",
+            self.cell_count, self.region_cell_count
+        )?;
+        writeln!(f, "front cells: {}", self.front_count)?;
+        writeln!(f, "neighbors: {} {:?}", self.offsets.len(), self.offsets)?;
+        writeln!(f, "world memory: {} bytes", self.world_bytes)?;
+        write!(f, "rule table memory: {} bytes", self.table_bytes)
+    }
+}
+
 /// The main struct of the search algorithm.
 ///
 /// # Example
@@ -76,8 +235,24 @@ pub struct World {
     /// The configuration of the world.
     pub(crate) config: Config,
 
+    /// The parsed rule.
+    pub(crate) parsed_rule: Rule,
+
     /// The rule table.
-    pub(crate) rule: RuleTable,
+    ///
+    /// It may be shared with other worlds through a [`RuleTableCache`].
+    pub(crate) rule: Arc<RuleTable>,
+
+    /// The width of the frame of dead cells around the world.
+    ///
+    /// This is the radius of the neighborhood, or zero on a torus.
+    pub(crate) border: u32,
+
+    /// The memory limit the world was created with.
+    ///
+    /// The worlds that [`search_parallel`](World::search_parallel) builds for the branches
+    /// use the same limit.
+    pub(crate) memory_limit: Option<usize>,
 
     /// A pointer to the list of cells.
     pub(crate) cells_ptr: *mut [LifeCell],
@@ -85,15 +260,29 @@ pub struct World {
     /// The length of the list of cells.
     pub(crate) size: usize,
 
+    /// The states of the cells, indexed in the same way as the list of cells.
+    #[cfg(feature = "packed-state")]
+    pub(crate) states: PackedStates,
+
     /// A random number generator for guessing the state of an unknown cell.
     pub(crate) rng: Xoshiro256PlusPlus,
 
     /// The number of living cells on each generation.
     pub(crate) population: Vec<usize>,
 
+    /// The largest number of living cells on a single generation
+    /// that has been reached so far during the search.
+    pub(crate) peak_population: usize,
+
     /// The upper bound of the population.
     pub(crate) max_population: Option<usize>,
 
+    /// The number of unknown cells on each generation.
+    ///
+    /// This is used to check whether a generation can still reach
+    /// the [`min_population`](Config::min_population).
+    pub(crate) unknown_count: Vec<usize>,
+
     /// The number of unknown or living cells on the front, i.e. the first row or column,
     /// depending on the search order.
     ///
@@ -123,6 +312,36 @@ pub struct World {
     /// The starting point to look for an unknown cell according to the search order.
     pub(crate) start: *const LifeCell,
 
+    /// The first cell according to the search order, i.e., the initial value of `start`.
+    pub(crate) head: *const LifeCell,
+
+    /// The current number of steps without progress before restarting the search.
+    ///
+    /// It starts at [`restart_after`](Config::restart_after), and doubles after each restart.
+    pub(crate) restart_after: Option<usize>,
+
+    /// The largest depth of the stack since the last restart.
+    pub(crate) max_depth: usize,
+
+    /// The number of consecutive steps since the depth of the stack last exceeded `max_depth`.
+    pub(crate) steps_without_progress: usize,
+
+    /// The number of steps made by the search so far.
+    pub(crate) steps: u64,
+
+    /// The number of times the search has backtracked so far.
+    pub(crate) backtracks: u64,
+
+    /// The last cell whose neighborhood descriptor implied a conflict.
+    ///
+    /// This is only used by [`first_conflict`](World::first_conflict).
+    pub(crate) conflict: *const LifeCell,
+
+    /// The cached result of [`true_period`](World::true_period).
+    ///
+    /// It is cleared whenever a cell is set or unset.
+    pub(crate) true_period_cache: Cell<Option<u32>>,
+
     /// The search status.
     pub(crate) status: Status,
 }
@@ -145,27 +364,98 @@ impl Serialize for World {
     }
 }
 
+/// The default upper bound of the memory allocated for a world, in bytes.
+///
+/// See [`World::new_with_limit`] for more details.
+pub const DEFAULT_MEMORY_LIMIT: usize = 512 * 1024 * 1024;
+
 impl World {
     /// Create a new world from a configuration.
+    ///
+    /// The memory allocated for the world is limited to [`DEFAULT_MEMORY_LIMIT`].
+    /// Use [`new_with_limit`](World::new_with_limit) to change the limit.
     pub fn new(config: Config) -> Result<Self, ConfigError> {
+        Self::new_with_limit(config, DEFAULT_MEMORY_LIMIT)
+    }
+
+    /// Create a new world from a configuration, with an upper bound of the memory
+    /// allocated for the world, in bytes.
+    ///
+    /// The memory is estimated from the number of cells, including the cells
+    /// outside the world that are within the radius of the neighborhood,
+    /// and the size of the stack that records them.
+    /// If it exceeds the limit, a [`WorldTooLarge`](ConfigError::WorldTooLarge) error
+    /// is returned before anything is allocated.
+    ///
+    /// If the limit is [`None`], the memory is not limited.
+    pub fn new_with_limit(
+        config: Config,
+        memory_limit: impl Into<Option<usize>>,
+    ) -> Result<Self, ConfigError> {
+        Self::new_inner(config, memory_limit.into(), None)
+    }
+
+    /// Create a new world from a configuration, and get the rule table from a cache
+    /// instead of building it from scratch.
+    ///
+    /// This is useful when creating many worlds with the same rule.
+    /// See [`RuleTableCache`] for more details.
+    ///
+    /// The memory allocated for the world is limited to [`DEFAULT_MEMORY_LIMIT`].
+    pub fn new_with_cache(config: Config, cache: &RuleTableCache) -> Result<Self, ConfigError> {
+        Self::new_inner(config, Some(DEFAULT_MEMORY_LIMIT), Some(cache))
+    }
+
+    /// Create a new world from a configuration, with an optional memory limit
+    /// and an optional rule table cache.
+    fn new_inner(
+        config: Config,
+        memory_limit: Option<usize>,
+        cache: Option<&RuleTableCache>,
+    ) -> Result<Self, ConfigError> {
         let mut config = config;
         config.check()?;
 
-        let rule = RuleTable::new(&config.parse_rule()?)?;
+        let parsed_rule = config.parse_rule()?;
         let max_population = config.max_population;
+        let restart_after = config.restart_after;
 
         let (w, h, p) = (
-            config.width as i32,
-            config.height as i32,
-            config.period as i32,
+            config.width as usize,
+            config.height as usize,
+            config.period as usize,
         );
-        let r = rule.radius as i32;
+        // A torus has no border, since its edges are glued together.
+        let border = match config.boundary {
+            Boundary::Dead => parsed_rule.radius(),
+            Boundary::Torus => 0,
+        };
+        let r = border as usize;
 
         // Number of cells in the world.
-        let size = ((w + 2 * r) * (h + 2 * r) * p) as usize;
+        let size = (w + 2 * r)
+            .checked_mul(h + 2 * r)
+            .and_then(|size| size.checked_mul(p));
+
+        let bytes = size.and_then(Self::world_bytes);
+
+        if let Some(memory_limit) = memory_limit {
+            if bytes.is_none_or(|bytes| bytes > memory_limit) {
+                return Err(ConfigError::WorldTooLarge {
+                    bytes: bytes.unwrap_or(usize::MAX),
+                });
+            }
+        }
+
+        let size = size.ok_or(ConfigError::WorldTooLarge { bytes: usize::MAX })?;
+
+        let rule = match cache {
+            Some(cache) => cache.get_or_build(&config.rule_str)?,
+            None => Arc::new(RuleTable::new(&parsed_rule)?),
+        };
 
         let cells = (0..size)
-            .map(|i| LifeCell::new(i as i32 % p))
+            .map(|i| LifeCell::new((i % p) as i32))
             .collect::<Box<[_]>>();
 
         let cells_ptr = Box::into_raw(cells);
@@ -177,19 +467,113 @@ impl World {
 
         let mut world = Self {
             config,
+            parsed_rule,
             rule,
+            border,
+            memory_limit,
             cells_ptr,
             size,
+            #[cfg(feature = "packed-state")]
+            states: PackedStates::new(size),
             rng,
-            population: vec![0; p as usize],
+            population: vec![0; p],
+            peak_population: 0,
             max_population,
+            unknown_count: vec![size / p; p],
             front_count: 0,
             stack: Vec::with_capacity(size),
             stack_index: 0,
             start: std::ptr::null(),
+            head: std::ptr::null(),
+            restart_after,
+            max_depth: 0,
+            steps_without_progress: 0,
+            steps: 0,
+            backtracks: 0,
+            conflict: std::ptr::null(),
+            true_period_cache: Cell::new(None),
             status: Status::NotStarted,
         };
         world.init();
+        world.head = world.start;
+
+        Ok(world)
+    }
+
+    /// Create a new world from a configuration, and set some cells to be known.
+    ///
+    /// Only one cell of each orbit of the [`symmetry`](Config::symmetry) needs to be given.
+    /// The other cells in the orbit are linked to it, so their states will be deduced
+    /// when the search starts. Giving several cells of the same orbit with the same state
+    /// is also allowed.
+    ///
+    /// See [`try_set_known`](World::try_set_known) for how each cell is set.
+    pub fn seed_with_symmetry(
+        config: Config,
+        cells: &[(Coord, CellState)],
+    ) -> Result<Self, SeedError> {
+        let mut world = Self::new(config)?;
+
+        for &(coord, state) in cells {
+            world.try_set_known(coord, state)?;
+        }
+
+        Ok(world)
+    }
+
+    /// Create a new world from a configuration, and set the cells of a pattern in RLE format
+    /// to be known, on the first generation, with the top-left corner at `(0, 0)`.
+    ///
+    /// The RLE is read line by line from `reader`, and the cells are set as they are read,
+    /// so large patterns, e.g. agars or tiled seeds, do not need to be loaded all at once.
+    /// The header line and comment lines starting with `#` are skipped.
+    ///
+    /// In the body, `b` or `.` is a dead cell, and `o` or `A` is a living cell, which are set
+    /// with [`try_set_known`](World::try_set_known). `?` is an unknown cell, which is skipped,
+    /// like the cells omitted at the end of a line, and after the last line.
+    /// So a pattern can leave some cells for the search to decide.
+    ///
+    /// A living cell outside the world is an [`OutOfBounds`](RleError::OutOfBounds) error.
+    /// Dead cells outside the world are allowed, and skipped, since they are always dead.
+    pub fn seed_from_rle_reader(config: Config, reader: impl BufRead) -> Result<Self, RleError> {
+        let mut world = Self::new(config).map_err(SeedError::from)?;
+        let (w, h) = (world.config.width as i32, world.config.height as i32);
+        let (mut x, mut y) = (0i32, 0i32);
+
+        for token in RleTokenizer::new(reader) {
+            match token? {
+                RleToken::Cells(count, state) => {
+                    // Only the part of the run inside the world is set cell by cell,
+                    // so a huge run count does not take a huge amount of time.
+                    let end = x.saturating_add_unsigned(count);
+
+                    if let Some(state) = state {
+                        if (0..h).contains(&y) {
+                            for x in x.min(w)..end.min(w) {
+                                world
+                                    .try_set_known((x, y, 0), state)
+                                    .map_err(SeedError::from)?;
+                            }
+                        }
+
+                        // The first cell of the run that is outside the world, if any.
+                        let outside = if (0..h).contains(&y) { x.max(w) } else { x };
+                        if state == CellState::Alive && outside < end {
+                            return Err(RleError::OutOfBounds {
+                                coord: (outside, y, 0),
+                            });
+                        }
+                    }
+
+                    x = end;
+                }
+                RleToken::NewLines(count) => {
+                    x = 0;
+                    y = y.saturating_add_unsigned(count);
+                }
+                RleToken::End => break,
+            }
+        }
 
         Ok(world)
     }
@@ -208,20 +592,24 @@ impl World {
     fn init_front(&mut self) {
         let mut use_front = false;
 
+        // The generation that contains the front, when only one generation is used.
+        let front_t = self.config.front_generation.unwrap_or(0) as i32;
+
+        // Whether only one generation is used as the front. When the period is 1,
+        // there is only one generation, so this holds even without generation rotation.
+        let single_generation = self.config.period == 1 || self.config.allow_generation_rotation;
+
         match self.config.search_order.unwrap() {
             // If the search order is row-first, the front is the first row.
             SearchOrder::RowFirst => {
-                if self.config.symmetry.is_subgroup_of(Symmetry::D2H)
-                    && self.config.transformation.is_element_of(Symmetry::D2H)
-                    && self.config.diagonal_width.is_none()
-                {
+                if self.config.front_reduction_applies(SearchOrder::RowFirst) {
                     use_front = true;
 
                     // If `dx` is zero, a pattern is still valid if we reflect it horizontally.
                     // So we only need to consider the left half of the first row.
 
                     let w = if self.config.dx == 0 {
-                        (self.config.width + 1) / 2
+                        self.config.width.div_ceil(2)
                     } else {
                         self.config.width
                     };
@@ -233,10 +621,12 @@ impl World {
                     // If `dx` is zero, `dy` is positive, a similar argument still applies.
                     // But the front becomes the `dy-1`-th row of the first generation.
 
-                    if self.config.dx == 0 && self.config.dy >= 0 {
+                    if single_generation && self.config.dx == 0 && self.config.dy >= 0 {
                         let y = self.config.dy.max(1) - 1;
                         for x in 0..w as i32 {
-                            self.get_cell_by_coord_mut((x, y, 0)).unwrap().is_front = true;
+                            self.get_cell_by_coord_mut((x, y, front_t))
+                                .unwrap()
+                                .is_front = true;
                             self.front_count += 1;
                         }
                     } else {
@@ -252,9 +642,9 @@ impl World {
 
             // If the search order is column-first, the front is the first column.
             SearchOrder::ColumnFirst => {
-                if self.config.symmetry.is_subgroup_of(Symmetry::D2V)
-                    && self.config.transformation.is_element_of(Symmetry::D2V)
-                    && self.config.diagonal_width.is_none()
+                if self
+                    .config
+                    .front_reduction_applies(SearchOrder::ColumnFirst)
                 {
                     use_front = true;
 
@@ -262,7 +652,7 @@ impl World {
                     // So we only need to consider the top half of the first column.
 
                     let h = if self.config.dy == 0 {
-                        (self.config.height + 1) / 2
+                        self.config.height.div_ceil(2)
                     } else {
                         self.config.height
                     };
@@ -274,10 +664,12 @@ impl World {
                     // If `dy` is zero, `dx` is positive, a similar argument still applies.
                     // But the front becomes the `dx-1`-th column of the first generation.
 
-                    if self.config.dx >= 0 && self.config.dy == 0 {
+                    if single_generation && self.config.dx >= 0 && self.config.dy == 0 {
                         let x = self.config.dx.max(1) - 1;
                         for y in 0..h as i32 {
-                            self.get_cell_by_coord_mut((x, y, 0)).unwrap().is_front = true;
+                            self.get_cell_by_coord_mut((x, y, front_t))
+                                .unwrap()
+                                .is_front = true;
                             self.front_count += 1;
                         }
                     } else {
@@ -293,9 +685,7 @@ impl World {
 
             // If the search order is diagonal, the front is both the first row and the first column.
             SearchOrder::Diagonal => {
-                if self.config.symmetry.is_subgroup_of(Symmetry::D2D)
-                    && self.config.transformation.is_element_of(Symmetry::D2D)
-                {
+                if self.config.front_reduction_applies(SearchOrder::Diagonal) {
                     use_front = true;
 
                     let d = self.config.diagonal_width.unwrap_or(self.config.width);
@@ -310,10 +700,13 @@ impl World {
                     // If `dx` equals `dy` and is positive, a similar argument still applies.
                     // But the front becomes the `dy-1`-th row of the first generation.
 
-                    if self.config.dx == self.config.dy && self.config.dx >= 0 {
+                    if single_generation && self.config.dx == self.config.dy && self.config.dx >= 0
+                    {
                         let y = self.config.dy.max(1) - 1;
                         for x in 0..d as i32 {
-                            self.get_cell_by_coord_mut((x, y, 0)).unwrap().is_front = true;
+                            self.get_cell_by_coord_mut((x, y, front_t))
+                                .unwrap()
+                                .is_front = true;
                             self.front_count += 1;
                         }
                     } else {
@@ -337,11 +730,14 @@ impl World {
             }
         }
 
-        // If `use_front` is false, the front is the whole pattern at the first generation.
+        // If `use_front` is false, the front is the whole pattern at the first generation,
+        // or the front generation if it is specified.
         if !use_front {
             for x in 0..self.config.width as i32 {
                 for y in 0..self.config.height as i32 {
-                    self.get_cell_by_coord_mut((x, y, 0)).unwrap().is_front = true;
+                    self.get_cell_by_coord_mut((x, y, front_t))
+                        .unwrap()
+                        .is_front = true;
                     self.front_count += 1;
                 }
             }
@@ -358,7 +754,7 @@ impl World {
             self.config.height as i32,
             self.config.period as i32,
         );
-        let r = self.rule.radius as i32;
+        let r = self.border as i32;
 
         for x in -r..w + r {
             for y in -r..h + r {
@@ -390,7 +786,7 @@ impl World {
             self.config.height as i32,
             self.config.period as i32,
         );
-        let r = self.rule.radius as i32;
+        let r = self.border as i32;
 
         for x in -r..w + r {
             for y in -r..h + r {
@@ -424,7 +820,7 @@ impl World {
             self.config.height as i32,
             self.config.period as i32,
         );
-        let r = self.rule.radius as i32;
+        let r = self.border as i32;
 
         for x in -r..w + r {
             for y in -r..h + r {
@@ -463,7 +859,7 @@ impl World {
                             let cell = self.get_cell_by_coord_ptr((x, y, t));
 
                             unsafe {
-                                if (*cell).state().is_none() {
+                                if self.cell_state(&*cell).is_none() {
                                     let next = self.start;
                                     self.start = cell;
                                     self.get_cell_by_coord_mut((x, y, t)).unwrap().next = next;
@@ -481,7 +877,7 @@ impl World {
                             let cell = self.get_cell_by_coord_ptr((x, y, t));
 
                             unsafe {
-                                if (*cell).state().is_none() {
+                                if self.cell_state(&*cell).is_none() {
                                     let next = self.start;
                                     self.start = cell;
                                     self.get_cell_by_coord_mut((x, y, t)).unwrap().next = next;
@@ -500,16 +896,16 @@ impl World {
                         let y = a - x;
 
                         if (0..w).contains(&y)
-                            && !self
+                            && self
                                 .config
                                 .diagonal_width
-                                .is_some_and(|d| (x - y).abs() >= d as i32)
+                                .is_none_or(|d| (x - y).abs() < d as i32)
                         {
                             for t in (0..self.config.period as i32).rev() {
                                 let cell = self.get_cell_by_coord_ptr((x, y, t));
 
                                 unsafe {
-                                    if (*cell).state().is_none() {
+                                    if self.cell_state(&*cell).is_none() {
                                         let next = self.start;
                                         self.start = cell;
                                         self.get_cell_by_coord_mut((x, y, t)).unwrap().next = next;
@@ -536,7 +932,7 @@ impl World {
             self.config.height as i32,
             self.config.period as i32,
         );
-        let r = self.rule.radius as i32;
+        let r = self.border as i32;
 
         for x in -r..w + r {
             for y in -r..h + r {
@@ -563,14 +959,22 @@ impl World {
     /// Get a raw pointer to a cell by its coordinates.
     ///
     /// Return a null pointer if the cell is outside the world.
-    fn get_cell_by_coord_ptr(&self, coord: Coord) -> *mut LifeCell {
-        let (x, y, t) = coord;
+    ///
+    /// If the [`boundary`](Config::boundary) is a [`Torus`](Boundary::Torus),
+    /// the spatial coordinates wrap around, so only the generation can be out of range.
+    pub(crate) fn get_cell_by_coord_ptr(&self, coord: Coord) -> *mut LifeCell {
+        let (mut x, mut y, t) = coord;
         let (w, h, p) = (
             self.config.width as i32,
             self.config.height as i32,
             self.config.period as i32,
         );
-        let r = self.rule.radius as i32;
+        let r = self.border as i32;
+
+        if self.config.boundary == Boundary::Torus {
+            x = x.rem_euclid(w);
+            y = y.rem_euclid(h);
+        }
 
         if (-r..w + r).contains(&x) && (-r..h + r).contains(&y) && (0..p).contains(&t) {
             let index = t + (x + r) * p + (y + r) * p * (w + 2 * r);
@@ -581,6 +985,67 @@ impl World {
         }
     }
 
+    /// Convert a raw pointer to a [`LifeCell`] to an index in the world.
+    ///
+    /// # Safety
+    ///
+    /// The raw pointer must be valid and point to a cell in the world.
+    /// Otherwise the behavior is undefined.
+    pub(crate) const unsafe fn cell_to_index(&self, cell: *const LifeCell) -> usize {
+        let offset = cell.offset_from(self.cells_ptr as *const LifeCell);
+        offset as usize
+    }
+
+    /// Get the state of a cell.
+    ///
+    /// # Safety
+    ///
+    /// The cell must be in the same world as `self`.
+    /// Otherwise the behavior is undefined.
+    #[cfg(not(feature = "packed-state"))]
+    pub(crate) const unsafe fn cell_state(&self, cell: &LifeCell) -> Option<CellState> {
+        cell.state()
+    }
+
+    /// Get the state of a cell.
+    ///
+    /// # Safety
+    ///
+    /// The cell must be in the same world as `self`.
+    /// Otherwise the behavior is undefined.
+    #[cfg(feature = "packed-state")]
+    pub(crate) unsafe fn cell_state(&self, cell: &LifeCell) -> Option<CellState> {
+        self.states.get(self.cell_to_index(cell))
+    }
+
+    /// Overwrite the state of a cell, without updating anything else.
+    ///
+    /// # Safety
+    ///
+    /// The cell must be in the same world as `self`.
+    /// Otherwise the behavior is undefined.
+    unsafe fn write_cell_state(&self, cell: &LifeCell, state: Option<CellState>) {
+        #[cfg(not(feature = "packed-state"))]
+        cell.state.set(state);
+        #[cfg(feature = "packed-state")]
+        self.states.set(self.cell_to_index(cell), state);
+    }
+
+    /// Convert an index in the world to the coordinates of the cell.
+    ///
+    /// This is the inverse of the computation in [`get_cell_by_coord_ptr`](World::get_cell_by_coord_ptr).
+    pub(crate) const fn index_to_coord(&self, index: usize) -> Coord {
+        let (w, p) = (self.config.width as i32, self.config.period as i32);
+        let r = self.border as i32;
+        let index = index as i32;
+
+        let t = index % p;
+        let x = index / p % (w + 2 * r) - r;
+        let y = index / p / (w + 2 * r) - r;
+
+        (x, y, t)
+    }
+
     /// Get a cell by its coordinates.
     ///
     /// Return [`None`] if the cell is outside the world.
@@ -602,8 +1067,10 @@ impl World {
     /// The cell must be in the same world as `self`.
     /// Otherwise the behavior is undefined.
     pub(crate) unsafe fn set_cell(&mut self, cell: &LifeCell, state: CellState, reason: Reason) {
-        debug_assert!(cell.state().is_none());
-        cell.state.set(Some(state));
+        debug_assert!(self.cell_state(cell).is_none());
+        self.write_cell_state(cell, Some(state));
+        self.true_period_cache.set(None);
+        self.unknown_count[cell.generation as usize] -= 1;
 
         // Update the neighborhood descriptor of the cell, its neighbors and predecessor.
         cell.update_current(state);
@@ -626,9 +1093,11 @@ impl World {
             self.front_count -= 1;
         }
 
-        // If the cell is alive, update the population.
+        // If the cell is alive, update the population and the peak population.
         if state == CellState::Alive {
-            self.population[cell.generation as usize] += 1;
+            let population = &mut self.population[cell.generation as usize];
+            *population += 1;
+            self.peak_population = self.peak_population.max(*population);
         }
 
         // Push the cell to the stack.
@@ -642,9 +1111,11 @@ impl World {
     /// The cell must be in the same world as `self`.
     /// Otherwise the behavior is undefined.
     pub(crate) unsafe fn unset_cell(&mut self, cell: &LifeCell) {
-        debug_assert!(cell.state().is_some());
-        let state = cell.state().unwrap();
-        cell.state.set(None);
+        debug_assert!(self.cell_state(cell).is_some());
+        let state = self.cell_state(cell).unwrap();
+        self.write_cell_state(cell, None);
+        self.true_period_cache.set(None);
+        self.unknown_count[cell.generation as usize] += 1;
 
         // Update the neighborhood descriptor of the cell, its neighbors and predecessor.
         cell.update_current(state);
@@ -678,9 +1149,14 @@ impl World {
     /// If its generation is out of the range `0..period`, we will move it to
     /// the range by taking the modulo of the generation, and apply the translation
     /// and transformation to the x and y coordinates.
+    ///
+    /// This takes constant time, however far the generation is out of the range.
+    /// Moving forward by one period is an affine map `f`. Since every transformation
+    /// has an order dividing 4, `f` applied 4 times is a translation by some vector `s`,
+    /// so `f` applied `4q + r` times is `f` applied `r` times, followed by `q` times `s`.
     #[inline]
     pub const fn canonicalize_coord(&self, coord: Coord) -> Coord {
-        let (mut x, mut y, mut t) = coord;
+        let (mut x, mut y, t) = coord;
         let (w, h, p) = (
             self.config.width as i32,
             self.config.height as i32,
@@ -690,21 +1166,30 @@ impl World {
         let dx = self.config.dx;
         let dy = self.config.dy;
 
-        while t < 0 {
-            t += p;
-            (x, y) = transformation.inverse().apply_with_size(x, y, w, h);
-            x -= dx;
-            y -= dy;
+        // The number of periods to move forward, which is negative if moving backward.
+        let n = t.div_euclid(p);
+        let t = t.rem_euclid(p);
+
+        // The translation after moving forward by 4 periods.
+        let (mut sx, mut sy) = (0, 0);
+        let mut i = 0;
+        while i < 4 {
+            (sx, sy) = transformation.apply_with_size(sx + dx, sy + dy, w, h);
+            i += 1;
         }
 
-        while t >= p {
-            t -= p;
+        let (q, r) = (n.div_euclid(4), n.rem_euclid(4));
+
+        // When moving backward, `r` is still in `0..4`, so `n = 4q + r` with `q` negative.
+        let mut i = 0;
+        while i < r {
             x += dx;
             y += dy;
             (x, y) = transformation.apply_with_size(x, y, w, h);
+            i += 1;
         }
 
-        (x, y, t)
+        translate((x, y, t), q * sx, q * sy)
     }
 
     /// Get the state of a cell by its coordinates.
@@ -712,12 +1197,102 @@ impl World {
     /// The coordinates are [canonicalized](World::canonicalize_coord) before getting the state.
     ///
     /// If the cell is outside the world after canonicalization, it is considered to be dead.
+    /// On a [torus](Boundary::Torus), the coordinates wrap around instead.
     ///
     /// If the cell is unknown, return [`None`].
     #[inline]
     pub fn get_cell_state(&self, coord: Coord) -> Option<CellState> {
         self.get_cell_by_coord(self.canonicalize_coord(coord))
-            .map_or(Some(CellState::Dead), LifeCell::state)
+            .map_or(Some(CellState::Dead), |cell| unsafe {
+                self.cell_state(cell)
+            })
+    }
+
+    /// Get the state of the cell stored at exactly the given coordinates,
+    /// without [canonicalizing](World::canonicalize_coord) them.
+    ///
+    /// This shows what the world actually stores, rather than what can be inferred
+    /// from the periodicity, the translation and the transformation.
+    ///
+    /// Return [`None`] if the cell is unknown, or if it is not stored in the world,
+    /// e.g., if the generation is out of the range `0..period`.
+    #[inline]
+    pub fn get_cell_state_exact(&self, coord: Coord) -> Option<CellState> {
+        self.get_cell_by_coord(coord)
+            .and_then(|cell| unsafe { self.cell_state(cell) })
+    }
+
+    /// The orbit of a cell under the [`symmetry`](Config::symmetry) of the world,
+    /// i.e., all the cells that are forced to have the same state as this cell.
+    ///
+    /// The coordinates are [canonicalized](World::canonicalize_coord) first.
+    /// The result is sorted, and includes the canonicalized cell itself.
+    /// Cells outside the world, which are always dead, are not included.
+    /// If the cell itself is outside the world, the result is empty.
+    ///
+    /// This can be used to preview the effect of [`try_set_known`](World::try_set_known).
+    pub fn symmetry_partners(&self, coord: Coord) -> Vec<Coord> {
+        let (w, h) = (self.config.width as i32, self.config.height as i32);
+        let in_world = |&(x, y, _): &Coord| (0..w).contains(&x) && (0..h).contains(&y);
+
+        let coord = self.canonicalize_coord(coord);
+        if !in_world(&coord) {
+            return Vec::new();
+        }
+
+        self.get_cell_by_coord(coord)
+            .map(|cell| {
+                cell.symmetry
+                    .iter()
+                    .map(|&partner| unsafe { self.index_to_coord(self.cell_to_index(partner)) })
+                    .filter(in_world)
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Set a cell to be known to have the given state before the search starts.
+    ///
+    /// The coordinates are [canonicalized](World::canonicalize_coord) first.
+    /// Cells outside the world are always known to be dead.
+    ///
+    /// Setting a cell to the state it already has does nothing.
+    /// If the cell is already known to have a different state, a [`ConflictError`] is returned,
+    /// and the world is left unchanged.
+    ///
+    /// Conflicts that can only be found by deduction, e.g., with cells that are related to this
+    /// cell by the symmetry, are not detected here. They will be found when the search starts.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the search has already started.
+    pub fn try_set_known(&mut self, coord: Coord, state: CellState) -> Result<(), ConflictError> {
+        assert_eq!(
+            self.status,
+            Status::NotStarted,
+            "Cannot set a known cell after the search has started"
+        );
+
+        let cell = self.get_cell_by_coord_ptr(self.canonicalize_coord(coord));
+
+        let existing = if cell.is_null() {
+            Some(CellState::Dead)
+        } else {
+            unsafe { self.cell_state(&*cell) }
+        };
+
+        match existing {
+            None => {
+                unsafe { self.set_cell(&*cell, state, Reason::Known) };
+                Ok(())
+            }
+            Some(existing) if existing == state => Ok(()),
+            Some(existing) => Err(ConflictError {
+                coord,
+                existing,
+                attempted: state,
+            }),
+        }
     }
 
     /// Get the search status.
@@ -732,6 +1307,105 @@ impl World {
         &self.config
     }
 
+    /// Get the search order that is actually used.
+    ///
+    /// If the [`search_order`](Config::search_order) of the configuration passed to
+    /// [`World::new`] is [`None`], it is chosen automatically when the configuration is checked.
+    /// The original configuration still shows [`None`], but this returns the chosen order.
+    #[inline]
+    pub const fn resolved_search_order(&self) -> SearchOrder {
+        self.config.search_order.unwrap()
+    }
+
+    /// Change the fields of the configuration that are safe to change during the search.
+    ///
+    /// Fields that define the shape of the world, such as the size, the rule and the symmetry,
+    /// are used to build the cells and their links, so they cannot be changed after the world
+    /// is created. The returned [`ConfigTweaks`] only exposes the other fields.
+    #[inline]
+    pub const fn config_mut(&mut self) -> ConfigTweaks<'_> {
+        ConfigTweaks { world: self }
+    }
+
+    /// Get the statistics of the search so far.
+    pub fn stats(&self) -> SearchStats {
+        SearchStats {
+            steps: self.steps,
+            backtracks: self.backtracks,
+            stack_depth: self.stack.len(),
+            population: self.population.clone(),
+        }
+    }
+
+    /// Summarize the values derived from the configuration, such as the resolved search order
+    /// and the number of front cells, without searching.
+    pub fn plan(&self) -> SearchPlan {
+        let cells = unsafe { &*self.cells_ptr };
+
+        SearchPlan {
+            search_order: self.resolved_search_order(),
+            width: self.config.width,
+            height: self.config.height,
+            period: self.config.period,
+            border: self.border,
+            cell_count: self.allocation_size(),
+            region_cell_count: self.region_size(),
+            front_count: cells.iter().filter(|cell| cell.is_front).count(),
+            offsets: self.rule.offsets().to_vec(),
+            world_bytes: Self::world_bytes(self.size).unwrap(),
+            table_bytes: self.rule.table_bytes(),
+        }
+    }
+
+    /// The number of cells in the search region, i.e., `width * height * period`.
+    ///
+    /// This does not include the border of dead cells around the world, so it is the total
+    /// to compare the numbers of known and unknown cells with. Cells outside the
+    /// [`diagonal_width`](Config::diagonal_width) are still included, since they are
+    /// in the bounding box, though they are known to be dead.
+    #[inline]
+    pub const fn region_size(&self) -> usize {
+        self.config.width as usize * self.config.height as usize * self.config.period as usize
+    }
+
+    /// The number of bytes taken by a world of `size` cells, including the stack,
+    /// but not the rule table. Return [`None`] on overflow.
+    const fn world_bytes(size: usize) -> Option<usize> {
+        let Some(bytes) =
+            size.checked_mul(size_of::<LifeCell>() + size_of::<(*const LifeCell, Reason)>())
+        else {
+            return None;
+        };
+
+        #[cfg(feature = "packed-state")]
+        let bytes = bytes.checked_add(PackedStates::bytes(size));
+        #[cfg(not(feature = "packed-state"))]
+        let bytes = Some(bytes);
+
+        bytes
+    }
+
+    /// The number of cells that are allocated for the world,
+    /// including the border of dead cells around the search region.
+    ///
+    /// See [`region_size`](World::region_size) for the cells in the search region only.
+    #[inline]
+    pub const fn allocation_size(&self) -> usize {
+        self.size
+    }
+
+    /// Get the parsed rule, e.g., its birth and survival conditions and its neighborhood.
+    #[inline]
+    pub const fn rule(&self) -> &Rule {
+        &self.parsed_rule
+    }
+
+    /// Get the rule table that is used by the search.
+    #[inline]
+    pub fn rule_table(&self) -> &RuleTable {
+        &self.rule
+    }
+
     /// Get the number of living cells on a generation.
     #[inline]
     pub fn population(&self, t: i32) -> usize {
@@ -739,6 +1413,66 @@ impl World {
         self.population[t as usize]
     }
 
+    /// The generation with the smallest population, which is usually taken as
+    /// the canonical phase of a solution.
+    ///
+    /// If several generations have the same smallest population, the earliest one is returned.
+    /// Only known living cells are counted, so this is only meaningful when the search is
+    /// [solved](Status::Solved).
+    pub fn minimal_phase(&self) -> i32 {
+        (0..self.config.period as i32)
+            .min_by_key(|&t| self.population(t))
+            .unwrap_or(0)
+    }
+
+    /// Iterate over the coordinates of the living cells on a generation.
+    ///
+    /// Instead of scanning the whole world, this goes through the cells whose states
+    /// have been set during the search, so it takes `O(n)` time, where `n` is the number
+    /// of known cells in the world. The order of the cells is unspecified.
+    ///
+    /// If the generation is out of the range `0..period`, we will take the modulo.
+    pub fn live_cells(&self, t: i32) -> impl Iterator<Item = Coord> + '_ {
+        let t = t.rem_euclid(self.config.period as i32);
+        self.stack.iter().filter_map(move |&(cell, _)| unsafe {
+            ((*cell).generation == t && self.cell_state(&*cell) == Some(CellState::Alive))
+                .then(|| self.index_to_coord(self.cell_to_index(cell)))
+        })
+    }
+
+    /// Get the guessed cells on the search path, in the order they were set.
+    ///
+    /// This includes the guesses that were flipped when backtracking from a conflict,
+    /// with their flipped states, since they cannot be deduced from the other cells.
+    /// Unlike the whole stack, this leaves out the cells that are known from the configuration
+    /// or deduced from other cells. Setting these cells in this order, each followed by
+    /// the deductions, leads back to the current position of the search, so this is the minimal
+    /// information needed to replay the search path, e.g. for an undo interface or a compact
+    /// checkpoint.
+    pub fn decisions(&self) -> Vec<(Coord, CellState)> {
+        self.stack
+            .iter()
+            .filter(|&&(_, reason)| matches!(reason, Reason::Guessed | Reason::Flipped))
+            .map(|&(cell, _)| unsafe {
+                (
+                    self.index_to_coord(self.cell_to_index(cell)),
+                    self.cell_state(&*cell).unwrap(),
+                )
+            })
+            .collect()
+    }
+
+    /// Get the largest number of living cells on a single generation
+    /// that has been reached so far during the search.
+    ///
+    /// This includes partial results that were later discarded by backtracking,
+    /// so it can be used to see how close the search gets to
+    /// [`max_population`](Config::max_population).
+    #[inline]
+    pub const fn peak_population(&self) -> usize {
+        self.peak_population
+    }
+
     /// Output a generation of the world in RLE format.
     ///
     /// - Dead cells are represented by `b` if `compact` is `true`, or `.` if `compact` is `false`.
@@ -766,8 +1500,80 @@ impl World {
     /// ooo!
     /// ```
     ///
+    /// When `compact` is `true`, leading empty rows are usually omitted as well. But if the
+    /// configuration has a nontrivial symmetry (see [`center_symmetric`](Config::center_symmetric)),
+    /// they are kept, so that the pattern stays centered in the `x` by `y` bounding box,
+    /// and the symmetry is visually obvious.
+    ///
     /// If the generation is out of the range `0..period`, we will take the modulo.
     pub fn rle(&self, t: i32, compact: bool) -> String {
+        self.rle_by(t, compact, |coord| self.get_cell_state(coord))
+    }
+
+    /// Output a mask of the unknown cells on a generation in RLE format.
+    ///
+    /// Unknown cells are represented by `o`, and known cells by `b` if `compact` is `true`,
+    /// or `.` if `compact` is `false`, regardless of their states. Otherwise the format
+    /// is the same as in [`rle`](World::rle).
+    ///
+    /// This shows the part of the world that the search has not decided yet,
+    /// which shrinks as the search goes deeper.
+    pub fn unknown_mask_rle(&self, t: i32, compact: bool) -> String {
+        self.rle_by(t, compact, |coord| {
+            Some(if self.get_cell_state(coord).is_none() {
+                CellState::Alive
+            } else {
+                CellState::Dead
+            })
+        })
+    }
+
+    /// Output a generation of the world in RLE format, showing only the cells that
+    /// the search branches on, without the cells completed by the symmetry.
+    ///
+    /// Each orbit of the [`symmetry`](Config::symmetry) has one representative that the search
+    /// reaches first in the [search order](Config::search_order); the other cells of the orbit
+    /// are always deduced from it. The representatives form the fundamental domain, and all
+    /// the other cells are shown as unknown, even if their states are known. Cells that are
+    /// known before the search starts are shown as they are, and orbits containing them are
+    /// derived from them, so their other cells are shown as unknown.
+    ///
+    /// Without symmetry, this is the same as [`rle`](World::rle).
+    /// Otherwise the format is the same as in [`rle`](World::rle).
+    pub fn rle_searched_only(&self, t: i32, compact: bool) -> String {
+        // The position of each cell in the search order, starting from 1.
+        // Cells known before the search starts are not in the search order, and have position 0.
+        let mut positions = vec![0; self.size];
+        let mut cell = self.head;
+        let mut position = 0;
+        while let Some(current) = unsafe { cell.as_ref() } {
+            position += 1;
+            positions[unsafe { self.cell_to_index(cell) }] = position;
+            cell = current.next;
+        }
+
+        self.rle_by(t, compact, |coord| {
+            let cell = unsafe { self.get_cell_by_coord_ptr(coord).as_ref()? };
+            let position = positions[unsafe { self.cell_to_index(cell) }];
+
+            let is_representative = cell
+                .symmetry
+                .iter()
+                .all(|&partner| positions[unsafe { self.cell_to_index(partner) }] >= position);
+
+            if position == 0 || is_representative {
+                unsafe { self.cell_state(cell) }
+            } else {
+                None
+            }
+        })
+    }
+
+    /// Output a generation of the world in RLE format, where the state of each cell
+    /// is given by `state` instead of the world itself.
+    ///
+    /// See [`rle`](World::rle) for the format.
+    fn rle_by(&self, t: i32, compact: bool, state: impl Fn(Coord) -> Option<CellState>) -> String {
         let (w, h, p) = (
             self.config.width as i32,
             self.config.height as i32,
@@ -780,14 +1586,19 @@ impl World {
 
         let mut body = String::new();
 
-        let dead_char = if compact { 'b' } else { '.' };
-
+        let dead_char = if compact {
+            'b'
+        } else {
+            CellState::Dead.glyph()
+        };
+
+        let center_symmetric = self.config.center_symmetric();
+
         for y in 0..h {
             for x in 0..w {
-                let c = match self.get_cell_state((x, y, t)) {
+                let c = match state((x, y, t)) {
                     Some(CellState::Dead) => dead_char,
-                    Some(CellState::Alive) => 'o',
-                    None => '?',
+                    state => CellState::glyph_opt(state),
                 };
 
                 body.push(c);
@@ -800,8 +1611,9 @@ impl World {
             }
 
             if y < h - 1 {
-                // Ignore the leading `$` if `compact` is true.
-                if !compact || !body.is_empty() {
+                // Ignore the leading `$` if `compact` is true,
+                // unless the pattern should be kept centered.
+                if !compact || center_symmetric || !body.is_empty() {
                     body.push('$');
                 }
             } else {
@@ -819,43 +1631,114 @@ impl World {
         }
 
         if compact {
-            // Run-length encode the body.
+            run_length_encode(header, &body)
+        } else {
+            header + &body
+        }
+    }
 
-            let mut result = header;
-            let mut line = String::new();
-            let mut count = 0;
-            let mut chars = body.chars().peekable();
+    /// Output the canonical representative of the solution in compact RLE format.
+    ///
+    /// Solutions that only differ by a transformation (see [`Transformation`]),
+    /// a translation, or a phase of the pattern are the same object. Among all the
+    /// generations of the pattern and their images under all 8 transformations,
+    /// this picks the one whose cropped grid is lexicographically smallest,
+    /// comparing first the width, then the height, and then the cells row by row,
+    /// with dead cells before alive cells. Its RLE is then the same for all solutions
+    /// that are the same object, which makes it suitable for catalogs.
+    ///
+    /// Return [`None`] if some cell in the world is still unknown.
+    pub fn canonical_solution_rle(&self) -> Option<String> {
+        if self.unknown_count.iter().any(|&count| count > 0) {
+            return None;
+        }
 
-            while let Some(c) = chars.next() {
-                count += 1;
+        let mut best: Option<(i32, i32, Vec<bool>)> = None;
 
-                if chars.peek() != Some(&c) {
-                    let mut run = if count > 1 {
-                        count.to_string()
-                    } else {
-                        String::new()
-                    };
-                    run.push(c);
+        for t in 0..self.config.period as i32 {
+            let cells = self.live_cells(t).collect::<Vec<_>>();
 
-                    // A line in the output should not be longer than 70 characters.
-                    if line.len() + run.len() > 70 {
-                        result.push_str(&line);
-                        result.push('\n');
-                        line = run;
-                    } else {
-                        line.push_str(&run);
-                    }
+            for transformation in Transformation::iter() {
+                let transformed = cells
+                    .iter()
+                    .map(|&(x, y, _)| transformation.apply(x, y))
+                    .collect::<Vec<_>>();
 
-                    count = 0;
+                let min_x = transformed.iter().map(|&(x, _)| x).min().unwrap_or(0);
+                let min_y = transformed.iter().map(|&(_, y)| y).min().unwrap_or(0);
+                let max_x = transformed.iter().map(|&(x, _)| x).max().unwrap_or(-1);
+                let max_y = transformed.iter().map(|&(_, y)| y).max().unwrap_or(-1);
+                let (w, h) = (max_x - min_x + 1, max_y - min_y + 1);
+
+                let mut grid = vec![false; (w * h) as usize];
+                for (x, y) in transformed {
+                    grid[((y - min_y) * w + x - min_x) as usize] = true;
+                }
+
+                let candidate = (w, h, grid);
+                if best.as_ref().is_none_or(|best| candidate < *best) {
+                    best = Some(candidate);
                 }
             }
+        }
 
-            result.push_str(&line);
+        let (w, h, grid) = best?;
 
-            result
-        } else {
-            header + &body
+        let header = format!("x = {}, y = {}, rule = {}\n", w, h, self.config.rule_str);
+
+        let mut body = String::new();
+        for (y, row) in grid.chunks(w.max(1) as usize).enumerate() {
+            if y > 0 {
+                body.push('$');
+            }
+            for &alive in row {
+                body.push(if alive { 'o' } else { 'b' });
+            }
+            let trim_len = body.trim_end_matches('b').len();
+            body.truncate(trim_len);
+        }
+        body.push('!');
+
+        Some(run_length_encode(header, &body))
+    }
+
+    /// Output the world in Golly's extended RLE format, with a `#CXRLE Pos=x,y` line
+    /// before the header, so that Golly places the pattern at the given position.
+    ///
+    /// `pos` is the position of the top-left cell of the world. Since the leading empty rows
+    /// may be omitted when `compact` is `true` (see [`rle`](World::rle)), the position in
+    /// the output is adjusted accordingly, so the pattern is always placed at the same cells.
+    ///
+    /// This is useful for placing several results on the same Golly canvas.
+    pub fn rle_with_position(&self, t: i32, pos: (i32, i32), compact: bool) -> String {
+        let (x, mut y) = pos;
+
+        // Count the leading empty rows that are omitted by `rle`.
+        if compact && !self.config.center_symmetric() {
+            let (w, h) = (self.config.width as i32, self.config.height as i32);
+            y += (0..h)
+                .take_while(|&y| {
+                    (0..w).all(|x| self.get_cell_state((x, y, t)) == Some(CellState::Dead))
+                })
+                .count() as i32;
         }
+
+        format!("#CXRLE Pos={x},{y}\n{}", self.rle(t, compact))
+    }
+
+    /// Output every generation of the world as a non-compact ASCII grid,
+    /// e.g., to make an animation of an oscillator or a spaceship.
+    ///
+    /// Each frame is the output of [`rle`](World::rle) with `compact` set to `false`,
+    /// without the header line. All frames have the same dimensions.
+    pub fn to_frames_ascii(&self) -> Vec<String> {
+        (0..self.config.period as i32)
+            .map(|t| {
+                let rle = self.rle(t, false);
+                let (_, body) = rle.split_once('\n').unwrap();
+                body.to_string()
+            })
+            .collect()
     }
 
     /// Increment the world size.
@@ -869,8 +1752,125 @@ impl World {
     ///
     /// The world will be replaced by a new world with the new size. The current search status
     /// will be lost.
-    pub fn increase_world_size(&mut self) {
+    ///
+    /// The new world has the same memory limit as this one. If it would exceed the limit,
+    /// a [`WorldTooLarge`](ConfigError::WorldTooLarge) error is returned, and the world
+    /// is left unchanged.
+    pub fn increase_world_size(&mut self) -> Result<(), ConfigError> {
+        *self = Self::new_with_limit(Self::grown_config(&self.config), self.memory_limit)?;
+        Ok(())
+    }
+
+    /// Increment the world size as in [`increase_world_size`](World::increase_world_size),
+    /// but keep the cells whose states are currently known.
+    ///
+    /// Each cell in the world whose state is known, either because it was known before the
+    /// search or because it is part of the current partial result or solution, is set to be
+    /// [known](World::try_set_known) in the new world. A cell at `(x, y, t)` is mapped to
+    /// `(x + dx, y + dy, t)`, where the offset `(dx, dy)` depends on the `alignment`.
+    /// The search then resumes from a state related to the current one, rather than from scratch.
+    ///
+    /// The old result may not fit the new world, e.g., because a known cell is mapped outside
+    /// the diagonal width, or because the symmetry of the new world relates it to a cell with
+    /// a different state. If any conflict is found, with or without [deduction](World::is_consistent),
+    /// the new world is created from scratch instead, as in
+    /// [`increase_world_size`](World::increase_world_size).
+    ///
+    /// Return whether the known cells were kept. As in
+    /// [`increase_world_size`](World::increase_world_size), an error is returned
+    /// if the new world would exceed the memory limit, and the world is left unchanged.
+    pub fn grow_preserving(&mut self, alignment: GrowAlignment) -> Result<bool, ConfigError> {
+        let config = Self::grown_config(&self.config);
+        let (dx, dy) = match alignment {
+            GrowAlignment::Corner => (0, 0),
+            GrowAlignment::Center => (
+                (config.width - self.config.width + 1) as i32 / 2,
+                (config.height - self.config.height + 1) as i32 / 2,
+            ),
+        };
+
+        let mut known = Vec::new();
+        for t in 0..self.config.period as i32 {
+            for y in 0..self.config.height as i32 {
+                for x in 0..self.config.width as i32 {
+                    if let Some(state) = self.get_cell_state_exact((x, y, t)) {
+                        known.push((translate((x, y, t), dx, dy), state));
+                    }
+                }
+            }
+        }
+
+        let mut world = Self::new_with_limit(config.clone(), self.memory_limit)?;
+        let preserved = known
+            .into_iter()
+            .all(|(coord, state)| world.try_set_known(coord, state).is_ok())
+            && world.is_consistent();
+
+        *self = if preserved {
+            world
+        } else {
+            Self::new_with_limit(config, self.memory_limit)?
+        };
+
+        Ok(preserved)
+    }
+
+    /// Relax the configuration after the search finds no solution, and start a new search.
+    ///
+    /// The field named by [`relax_on_no_solution`](Config::relax_on_no_solution) is loosened.
+    /// If it is [`None`], the world size is increased as in
+    /// [`increase_world_size`](World::increase_world_size), so this generalizes it.
+    ///
+    /// The world will be replaced by a new world with the relaxed configuration. The current
+    /// search status will be lost.
+    ///
+    /// Return `false` if there is nothing left to relax, i.e., the diagonal width is already
+    /// the width of the world, or the upper bound of the population is already the number of
+    /// cells in a generation, so that it does not exclude any pattern. This includes the case
+    /// where the field to relax is [`None`], e.g., because the upper bound of the population
+    /// was cleared with [`set_max_population`](ConfigTweaks::set_max_population). In this case,
+    /// the world is left unchanged. As in [`increase_world_size`](World::increase_world_size),
+    /// an error is returned if the new world would exceed the memory limit.
+    pub fn grow_or_relax(&mut self) -> Result<bool, ConfigError> {
         let mut config = self.config.clone();
+
+        match config.relax_on_no_solution {
+            Some(RelaxStrategy::MaxPopulation(n)) => {
+                // The population of a generation can never exceed the number of its cells.
+                let cells = config.width as usize * config.height as usize;
+                // The bound may have been cleared after the world was built.
+                let Some(max) = config.max_population else {
+                    return Ok(false);
+                };
+                if max >= cells {
+                    return Ok(false);
+                }
+                config.max_population = Some(max.saturating_add(n).min(cells));
+            }
+            Some(RelaxStrategy::DiagonalWidth(n)) => {
+                let Some(d) = config.diagonal_width else {
+                    return Ok(false);
+                };
+                if d >= config.width {
+                    return Ok(false);
+                }
+                config.diagonal_width = Some(d.saturating_add(n).min(config.width));
+            }
+            Some(RelaxStrategy::Size(n)) => {
+                for _ in 0..n {
+                    config = Self::grown_config(&config);
+                }
+            }
+            None => config = Self::grown_config(&config),
+        }
+
+        *self = Self::new_with_limit(config, self.memory_limit)?;
+        Ok(true)
+    }
+
+    /// The configuration of the world after [`increase_world_size`](World::increase_world_size).
+    fn grown_config(config: &Config) -> Config {
+        let mut config = config.clone();
         let w = config.width;
         let h = config.height;
         let d = config.diagonal_width;
@@ -885,7 +1885,176 @@ impl World {
             config.height = h + 1;
         }
 
-        *self = Self::new(config).unwrap();
+        config
+    }
+}
+
+/// Compare two generations of two worlds, and output a grid that marks where they differ.
+///
+/// The living cells on generation `ta` of `a` and generation `tb` of `b` are each cropped
+/// to their bounding boxes, and aligned at the top-left corners. This is useful to check that
+/// two searches, e.g. with different search orders, find the same pattern, and to see where
+/// they differ if not. Unknown cells are treated as dead.
+///
+/// The output has the same layout as the non-compact format of [`World::rle`], with a header
+/// giving the size of the grid, and the following characters:
+/// - `.` for cells that are dead in both.
+/// - `o` for cells that are alive in both.
+/// - `-` for cells that are only alive in `a`.
+/// - `+` for cells that are only alive in `b`.
+///
+/// So the two generations are the same up to a translation
+/// if and only if the output contains neither `-` nor `+`.
+pub fn diff_rle(a: &World, ta: i32, b: &World, tb: i32) -> String {
+    // The living cells, relative to the top-left corner of their bounding box,
+    // and the size of the bounding box.
+    let crop = |world: &World, t: i32| {
+        let cells = world
+            .live_cells(t)
+            .map(|(x, y, _)| (x, y))
+            .collect::<Vec<_>>();
+        let min_x = cells.iter().map(|&(x, _)| x).min().unwrap_or(0);
+        let min_y = cells.iter().map(|&(_, y)| y).min().unwrap_or(0);
+        let max_x = cells.iter().map(|&(x, _)| x).max().unwrap_or(-1);
+        let max_y = cells.iter().map(|&(_, y)| y).max().unwrap_or(-1);
+        let cells = cells
+            .into_iter()
+            .map(|(x, y)| (x - min_x, y - min_y))
+            .collect::<HashSet<_>>();
+        (cells, max_x - min_x + 1, max_y - min_y + 1)
+    };
+
+    let (cells_a, wa, ha) = crop(a, ta);
+    let (cells_b, wb, hb) = crop(b, tb);
+    let (w, h) = (wa.max(wb), ha.max(hb));
+
+    let mut result = format!("x = {w}, y = {h}\n");
+
+    for y in 0..h {
+        for x in 0..w {
+            result.push(
+                match (cells_a.contains(&(x, y)), cells_b.contains(&(x, y))) {
+                    (false, false) => '.',
+                    (true, true) => 'o',
+                    (true, false) => '-',
+                    (false, true) => '+',
+                },
+            );
+        }
+
+        result.push_str(if y < h - 1 { "$\n" } else { "!\n" });
+    }
+
+    if h == 0 {
+        result.push_str("!\n");
+    }
+
+    result
+}
+
+/// Run-length encode the body of an RLE, and append it to the header.
+///
+/// A line in the output is not longer than 70 characters.
+fn run_length_encode(header: String, body: &str) -> String {
+    let mut result = header;
+    let mut line = String::new();
+    let mut count = 0;
+    let mut chars = body.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        count += 1;
+
+        if chars.peek() != Some(&c) {
+            let mut run = if count > 1 {
+                count.to_string()
+            } else {
+                String::new()
+            };
+            run.push(c);
+
+            if line.len() + run.len() > 70 {
+                result.push_str(&line);
+                result.push('\n');
+                line = run;
+            } else {
+                line.push_str(&run);
+            }
+
+            count = 0;
+        }
+    }
+
+    result.push_str(&line);
+
+    result
+}
+
+/// A handle to change the fields of the configuration of a [`World`] that do not
+/// affect the structure of the world.
+///
+/// Created by [`World::config_mut`]. Each setter returns the handle itself,
+/// so that several changes can be chained.
+///
+/// The changes take effect from the next step of the search. Branches that were already
+/// pruned under the old values are not revisited, so relaxing a population bound
+/// during the search may miss some solutions.
+#[derive(Debug)]
+pub struct ConfigTweaks<'a> {
+    /// The world to change.
+    world: &'a mut World,
+}
+
+impl ConfigTweaks<'_> {
+    /// Set how to guess the state of an unknown cell.
+    ///
+    /// This can be called in the middle of a search, e.g., to switch to
+    /// [`Random`](NewState::Random) guessing after the search stalls with
+    /// [`Dead`](NewState::Dead). Only the guesses made afterwards use the new strategy.
+    /// The cells that are already known, guessed or deduced, stay as they are, and
+    /// the branches that were already explored are not revisited by the change itself.
+    ///
+    /// Switching to [`Random`](NewState::Random) also enables
+    /// [`restart_after`](Config::restart_after), if it is set. A restart undoes all guesses,
+    /// so as noted there, a solution may then be found more than once.
+    ///
+    /// See [`new_state`](Config::new_state) for more details.
+    pub const fn set_new_state(&mut self, new_state: NewState) -> &mut Self {
+        self.world.config.new_state = new_state;
+        self
+    }
+
+    /// Set the random seed, and reseed the random number generator with it.
+    ///
+    /// See [`seed`](Config::seed) for more details.
+    pub fn set_seed(&mut self, seed: u64) -> &mut Self {
+        self.world.config.seed = Some(seed);
+        self.world.rng = Xoshiro256PlusPlus::seed_from_u64(seed);
+        self
+    }
+
+    /// Set the upper bound of the population.
+    ///
+    /// See [`max_population`](Config::max_population) for more details.
+    pub const fn set_max_population(&mut self, max_population: Option<usize>) -> &mut Self {
+        self.world.config.max_population = max_population;
+        self.world.max_population = max_population;
+        self
+    }
+
+    /// Set the lower bound of the population.
+    ///
+    /// See [`min_population`](Config::min_population) for more details.
+    pub const fn set_min_population(&mut self, min_population: Option<usize>) -> &mut Self {
+        self.world.config.min_population = min_population;
+        self
+    }
+
+    /// Set whether to reduce the upper bound of the population when a solution is found.
+    ///
+    /// See [`reduce_max_population`](Config::reduce_max_population) for more details.
+    pub const fn set_reduce_max_population(&mut self, reduce_max_population: bool) -> &mut Self {
+        self.world.config.reduce_max_population = reduce_max_population;
+        self
     }
 }
 
@@ -902,6 +2071,11 @@ struct WorldSerde {
     /// The number of living cells on each generation.
     population: Vec<usize>,
 
+    /// The largest number of living cells on a single generation
+    /// that has been reached so far during the search.
+    #[serde(default)]
+    peak_population: usize,
+
     /// The upper bound of the population.
     max_population: Option<usize>,
 
@@ -938,6 +2112,54 @@ struct WorldSerde {
 
     /// The search status.
     status: Status,
+
+    /// The current number of steps without progress before restarting the search.
+    #[serde(default)]
+    restart_after: Option<usize>,
+
+    /// The largest depth of the stack since the last restart.
+    #[serde(default)]
+    max_depth: usize,
+
+    /// The number of consecutive steps since the depth of the stack last exceeded `max_depth`.
+    #[serde(default)]
+    steps_without_progress: usize,
+
+    /// The number of steps made by the search so far.
+    #[serde(default)]
+    steps: u64,
+
+    /// The number of times the search has backtracked so far.
+    #[serde(default)]
+    backtracks: u64,
+}
+
+/// A search configuration and its result, shared as a human-readable JSON document.
+///
+/// See [`World::export_find`].
+#[cfg(feature = "serde")]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Find {
+    /// The [compact representation](Config#impl-Display-for-Config) of the configuration.
+    spec: String,
+
+    /// The full configuration.
+    config: Config,
+
+    /// The resolved search order.
+    search_order: SearchOrder,
+
+    /// The first generation of the pattern, in the non-compact RLE format.
+    rle: String,
+
+    /// The population of the first generation.
+    population: usize,
+
+    /// The true period of the pattern, if it is solved.
+    period: Option<u32>,
+
+    /// The velocity of the pattern as `(dx, dy, period)`, if it is a solved spaceship.
+    velocity: Option<(i32, i32, u32)>,
 }
 
 #[cfg(feature = "serde")]
@@ -958,17 +2180,6 @@ impl TryFrom<WorldSerde> for World {
 
 #[cfg(feature = "serde")]
 impl World {
-    /// Convert a raw pointer to a [`LifeCell`] to an index in the world.
-    ///
-    /// # Safety
-    ///
-    /// The raw pointer must be valid and point to a cell in the world.
-    /// Otherwise the behavior is undefined.
-    const unsafe fn cell_to_index(&self, cell: *const LifeCell) -> usize {
-        let offset = cell.offset_from(self.cells_ptr as *const LifeCell);
-        offset as usize
-    }
-
     /// Convert an index in the world to a raw pointer to a [`LifeCell`].
     ///
     /// # Safety
@@ -979,6 +2190,71 @@ impl World {
         (self.cells_ptr as *const LifeCell).add(index)
     }
 
+    /// Save the current state of the search to a JSON string.
+    ///
+    /// The world is only modified inside [`search`](World::search) and similar methods,
+    /// and these methods always stop between two steps, so the state is always consistent.
+    /// In particular, a checkpoint taken while the status is [`Running`](Status::Running)
+    /// resumes to the exact same search frontier, including the state of the random number
+    /// generator: restoring it with [`restore`](World::restore) and continuing the search
+    /// finds the same solutions in the same order as an uninterrupted search.
+    ///
+    /// Return an error if the serialization fails, with the same error type as
+    /// [`restore`](World::restore).
+    pub fn checkpoint(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string(self)
+    }
+
+    /// Restore a world from a JSON string created by [`checkpoint`](World::checkpoint).
+    pub fn restore(s: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(s)
+    }
+
+    /// Export the configuration and the result of the search as a JSON document,
+    /// to reproduce and share a find.
+    ///
+    /// The document contains the [compact representation](Config#impl-Display-for-Config)
+    /// of the configuration, the full configuration, the resolved search order,
+    /// the first generation in the non-compact RLE format, and its population.
+    /// If the search is [solved](Status::Solved), it also contains the true period
+    /// and the velocity. Unlike [`checkpoint`](World::checkpoint), the search state
+    /// is not included, so the document is small and readable.
+    ///
+    /// Use [`from_find`](World::from_find) to rebuild the world from it.
+    ///
+    /// Return an error if the serialization fails, as [`checkpoint`](World::checkpoint) does.
+    pub fn export_find(&self) -> Result<String, serde_json::Error> {
+        let solved = self.status == Status::Solved;
+        let find = Find {
+            spec: self.config.to_string(),
+            config: self.config.clone(),
+            search_order: self.resolved_search_order(),
+            rle: self.rle(0, false),
+            population: self.population(0),
+            period: solved.then(|| self.cached_true_period()),
+            velocity: self.velocity(),
+        };
+        serde_json::to_string_pretty(&find)
+    }
+
+    /// Rebuild a world from a document created by [`export_find`](World::export_find),
+    /// and verify that the pattern in it is a solution of the configuration.
+    ///
+    /// Only the configuration and the pattern are used, not the search state.
+    /// The first generation of the pattern is [seeded](World::seed_from_rle_reader)
+    /// into a new world, and the other generations are deduced by the search.
+    /// The returned world is [solved](Status::Solved), with the same pattern.
+    pub fn from_find(s: &str) -> Result<Self, FindError> {
+        let find: Find = serde_json::from_str(s)?;
+        let mut world = Self::seed_from_rle_reader(find.config, find.rle.as_bytes())?;
+
+        if world.search(None) == Status::Solved && world.rle(0, false) == find.rle {
+            Ok(world)
+        } else {
+            Err(FindError::NotASolution)
+        }
+    }
+
     /// Convert a [`World`] to a [`WorldSerde`].
     fn to_serde(&self) -> WorldSerde {
         let stack = self
@@ -986,7 +2262,7 @@ impl World {
             .iter()
             .map(|&(cell, reason)| unsafe {
                 let index = self.cell_to_index(cell);
-                let state = (*cell).state().unwrap();
+                let state = self.cell_state(&*cell).unwrap();
                 (index, state, reason)
             })
             .collect();
@@ -1001,12 +2277,18 @@ impl World {
             config: self.config.clone(),
             rng: self.rng.clone(),
             population: self.population.clone(),
+            peak_population: self.peak_population,
             max_population: self.max_population,
             front_count: self.front_count,
             stack,
             stack_index: self.stack_index,
             start,
             status: self.status,
+            restart_after: self.restart_after,
+            max_depth: self.max_depth,
+            steps_without_progress: self.steps_without_progress,
+            steps: self.steps,
+            backtracks: self.backtracks,
         }
     }
 
@@ -1037,7 +2319,7 @@ impl World {
                 let cell = world.index_to_cell(index);
 
                 // Skip the cell if it already has a state.
-                if (*cell).state().is_none() {
+                if world.cell_state(&*cell).is_none() {
                     world.set_cell(&*cell, state, reason);
                 }
             }
@@ -1056,10 +2338,16 @@ impl World {
 
         world.rng = serde.rng;
         world.population = serde.population;
+        world.peak_population = world.peak_population.max(serde.peak_population);
         world.max_population = serde.max_population;
         world.front_count = serde.front_count;
         world.stack_index = serde.stack_index;
         world.status = serde.status;
+        world.restart_after = serde.restart_after.or(world.restart_after);
+        world.max_depth = serde.max_depth;
+        world.steps_without_progress = serde.steps_without_progress;
+        world.steps = serde.steps;
+        world.backtracks = serde.backtracks;
 
         Ok(world)
     }
@@ -1068,6 +2356,8 @@ impl World {
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::symmetry::Symmetry;
+    use std::collections::BTreeSet;
 
     /// Test with Miri to see if there is any undefined behavior.
     #[test]
@@ -1079,17 +2369,1620 @@ mod test {
     }
 
     #[test]
-    #[cfg(feature = "serde")]
-    fn test_miri_serde() {
-        let config = Config::new("B3/S23", 3, 3, 2);
+    fn test_try_set_known() {
+        let config = Config::new("B3/S23", 4, 4, 1);
         let mut world = World::new(config).unwrap();
 
-        let serde = world.to_serde();
-        let mut world2 = World::try_from(serde).unwrap();
+        assert_eq!(world.try_set_known((1, 1, 0), CellState::Alive), Ok(()));
+        assert_eq!(world.try_set_known((1, 1, 0), CellState::Alive), Ok(()));
+        assert_eq!(
+            world.try_set_known((1, 1, 0), CellState::Dead),
+            Err(ConflictError {
+                coord: (1, 1, 0),
+                existing: CellState::Alive,
+                attempted: CellState::Dead,
+            })
+        );
+        assert_eq!(
+            world.try_set_known((-1, 0, 0), CellState::Alive),
+            Err(ConflictError {
+                coord: (-1, 0, 0),
+                existing: CellState::Dead,
+                attempted: CellState::Alive,
+            })
+        );
 
-        world.search(None);
-        world2.search(None);
-        assert_eq!(world.status(), world2.status());
-        assert_eq!(world.rle(0, true), world2.rle(0, true));
+        assert_eq!(world.search(None), Status::Solved);
+        assert_eq!(world.get_cell_state((1, 1, 0)), Some(CellState::Alive));
+    }
+
+    #[test]
+    fn test_seed_with_symmetry() {
+        let config = Config::new("B3/S23", 6, 6, 1).with_symmetry(Symmetry::D4O);
+        let orbit = [(1, 1, 0), (4, 1, 0), (1, 4, 0), (4, 4, 0)];
+
+        // Seeding one cell of the orbit.
+        let mut world =
+            World::seed_with_symmetry(config.clone(), &[(orbit[0], CellState::Alive)]).unwrap();
+        assert_eq!(world.search(None), Status::Solved);
+        for coord in orbit {
+            assert_eq!(world.get_cell_state(coord), Some(CellState::Alive));
+        }
+
+        // Seeding the whole orbit.
+        let cells = orbit.map(|coord| (coord, CellState::Alive));
+        let mut world = World::seed_with_symmetry(config, &cells).unwrap();
+        assert_eq!(world.search(None), Status::Solved);
+        for coord in orbit {
+            assert_eq!(world.get_cell_state(coord), Some(CellState::Alive));
+        }
+    }
+
+    #[test]
+    fn test_search_up_to_period() {
+        let count = |config: Config| {
+            let mut world = World::new(config).unwrap();
+            let mut periods = Vec::new();
+            world.search_with(None, |world| periods.push(world.true_period()));
+            periods
+        };
+
+        let p1 = count(Config::new("B3/S23", 5, 5, 1));
+        let p2 = count(Config::new("B3/S23", 5, 5, 2));
+        let up_to_p2 = count(Config::new("B3/S23", 5, 5, 2).with_search_up_to_period(2));
+
+        assert!(p1.iter().all(|&p| p == 1));
+        assert!(p2.iter().all(|&p| p == 2));
+        assert_eq!(up_to_p2.iter().filter(|&&p| p == 1).count(), p1.len());
+        assert_eq!(up_to_p2.iter().filter(|&&p| p == 2).count(), p2.len());
+        assert_eq!(up_to_p2.len(), p1.len() + p2.len());
+    }
+
+    #[test]
+    fn test_live_cells() {
+        let config = Config::new("B3/S23", 6, 6, 4).with_translations(1, 1);
+        let mut world = World::new(config).unwrap();
+        assert_eq!(world.search(None), Status::Solved);
+
+        for t in 0..4 {
+            let mut live_cells: Vec<_> = world.live_cells(t).collect();
+            live_cells.sort_unstable();
+
+            let mut expected = Vec::new();
+            for x in 0..6 {
+                for y in 0..6 {
+                    if world.get_cell_state((x, y, t)) == Some(CellState::Alive) {
+                        expected.push((x, y, t));
+                    }
+                }
+            }
+
+            assert_eq!(live_cells, expected);
+            assert_eq!(live_cells.len(), world.population(t));
+        }
+    }
+
+    #[test]
+    fn test_backtrack_to() {
+        let config = Config::new("B3/S23", 5, 5, 2);
+        let mut world = World::new(config).unwrap();
+        let known_depth = world.stack_depth();
+
+        assert_eq!(world.search(None), Status::Solved);
+        let solution = world.rle(0, true);
+
+        // Unwinding to the beginning makes the same guesses again.
+        world.backtrack_to(0);
+        assert_eq!(world.stack_depth(), known_depth);
+        assert_eq!(world.status(), Status::Running);
+        assert_eq!(world.population(0), 0);
+        assert_eq!(world.search(None), Status::Solved);
+        assert_eq!(world.rle(0, true), solution);
+
+        // Unwinding to a larger depth does nothing.
+        let depth = world.stack_depth();
+        world.backtrack_to(depth + 1);
+        assert_eq!(world.stack_depth(), depth);
+        assert_eq!(world.status(), Status::Solved);
+        assert_eq!(world.rle(0, true), solution);
+    }
+
+    #[test]
+    fn test_first_conflict() {
+        let config = Config::new("B3/S23", 5, 5, 1);
+
+        // A lone living cell dies, so its descriptor conflicts before any guess.
+        let cells = [((2, 2, 0), CellState::Alive)];
+        let mut world = World::seed_with_symmetry(config.clone(), &cells).unwrap();
+        let depth = world.stack_depth();
+        let (x, y, t) = world.first_conflict().unwrap();
+        assert!((x - 2).abs() <= 1 && (y - 2).abs() <= 1 && t == 0);
+        assert_eq!(world.status(), Status::Running);
+
+        // Resuming from the conflict finds the same solution as a fresh search.
+        assert_eq!(world.search(None), Status::Solved);
+        let mut fresh = World::seed_with_symmetry(config, &cells).unwrap();
+        assert_eq!(fresh.stack_depth(), depth);
+        assert_eq!(fresh.search(None), Status::Solved);
+        assert_eq!(world.rle(0, true), fresh.rle(0, true));
+
+        // Resuming from a solution reduces the maximum population, as in `search`.
+        let config = Config::still_life("B3/S23", 4, 4).with_reduce_max_population();
+        let mut world = World::new(config).unwrap();
+        assert_eq!(world.search(None), Status::Solved);
+        let population = world.population(0);
+        world.first_conflict();
+        assert_eq!(world.config().max_population, Some(population - 1));
+    }
+
+    #[test]
+    fn test_min_population() {
+        let populations = |config: Config| {
+            let mut world = World::new(config).unwrap();
+            let mut populations = Vec::new();
+            world.search_with(None, |world| populations.push(world.population(0)));
+            populations
+        };
+
+        let config = Config::still_life("B3/S23", 4, 4);
+        let all = populations(config.clone());
+        let bounded = populations(config.with_min_population(7));
+
+        assert!(all.iter().any(|&population| population < 7));
+        assert!(bounded.iter().all(|&population| population >= 7));
+        assert_eq!(
+            bounded.len(),
+            all.iter().filter(|&&population| population >= 7).count()
+        );
+
+        let config = Config::new("B3/S23", 4, 4, 1)
+            .with_min_population(5)
+            .with_max_population(4);
+        assert!(matches!(
+            World::new(config),
+            Err(ConfigError::InvalidMinPopulation)
+        ));
+    }
+
+    #[test]
+    fn test_exact_population() {
+        let populations = |config: Config| {
+            let mut world = World::new(config).unwrap();
+            let mut populations = Vec::new();
+            world.search_with(None, |world| {
+                populations.push((0..2).map(|t| world.population(t)).collect::<Vec<_>>());
+            });
+            populations
+        };
+
+        let config = Config::new("B3/S23", 4, 4, 2).with_search_up_to_period(2);
+        let all = populations(config.clone());
+        let exact = populations(config.with_exact_population(6));
+
+        assert!(exact.iter().all(|p| p == &[6, 6]));
+        assert_eq!(
+            exact.len(),
+            all.iter().filter(|p| p.iter().all(|&p| p == 6)).count()
+        );
+        assert!(exact.len() < all.iter().filter(|p| p.contains(&6)).count());
+
+        let config = Config::new("B3/S23", 4, 4, 1)
+            .with_exact_population(5)
+            .with_max_population(4);
+        assert!(matches!(
+            World::new(config),
+            Err(ConfigError::InvalidExactPopulation)
+        ));
+    }
+
+    #[test]
+    fn test_search_outcome() {
+        let mut world = World::new(Config::new("B3/S23", 4, 4, 1)).unwrap();
+
+        let outcome = world.search_outcome(1);
+        assert_eq!(outcome.status, Status::Running);
+        assert!(outcome.exhausted_budget);
+        assert!(outcome.is_resumable());
+
+        let outcome = world.search_outcome(None);
+        assert_eq!(outcome.status, Status::Solved);
+        assert!(!outcome.exhausted_budget);
+        assert!(outcome.is_resumable());
+
+        let mut world = World::new(Config::new("B3/S23", 1, 1, 1)).unwrap();
+        let outcome = world.search_outcome(None);
+        assert_eq!(outcome.status, Status::NoSolution);
+        assert!(!outcome.exhausted_budget);
+        assert!(outcome.is_finished());
+    }
+
+    #[test]
+    fn test_search_parallel() {
+        let config = Config::new("B3/S23", 5, 5, 2);
+
+        let mut world = World::new(config.clone()).unwrap();
+        let mut expected = Vec::new();
+        world.search_with(None, |world| expected.push(world.rle(0, true)));
+        expected.sort();
+        expected.dedup();
+
+        for threads in [0, 1, 3, 4] {
+            let world = World::new(config.clone()).unwrap();
+            let mut solutions = world.search_parallel(threads);
+            solutions.sort();
+            assert_eq!(solutions, expected, "{threads} threads");
+        }
+
+        // Far more threads than branches.
+        let world = World::new(Config::new("B3/S23", 2, 2, 1)).unwrap();
+        assert_eq!(
+            world.search_parallel(usize::MAX),
+            ["x = 2, y = 2, rule = B3/S23\n2o$2o!"]
+        );
+
+        // Known cells are kept in every branch.
+        let cells = [((2, 2, 0), CellState::Alive)];
+        let world = World::seed_with_symmetry(config, &cells).unwrap();
+        let solutions = world.search_parallel(4);
+        assert!(!solutions.is_empty());
+        assert!(solutions.len() < expected.len());
+    }
+
+    #[test]
+    fn test_symmetry_partners() {
+        let config = Config::new("B3/S23", 6, 6, 2).with_symmetry(Symmetry::D4O);
+        let world = World::new(config).unwrap();
+
+        assert_eq!(
+            world.symmetry_partners((1, 2, 1)),
+            vec![(1, 2, 1), (1, 3, 1), (4, 2, 1), (4, 3, 1)]
+        );
+
+        // The center of the rotation is its only partner.
+        let world = World::new(Config::new("B3/S23", 5, 5, 1).with_symmetry(Symmetry::C4)).unwrap();
+        assert_eq!(world.symmetry_partners((2, 2, 0)), vec![(2, 2, 0)]);
+        assert_eq!(
+            world.symmetry_partners((0, 1, 0)),
+            vec![(0, 1, 0), (1, 4, 0), (3, 0, 0), (4, 3, 0)]
+        );
+
+        assert!(world.symmetry_partners((5, 5, 0)).is_empty());
+    }
+
+    #[test]
+    fn test_restart_after() {
+        let config = Config::new("B3/S23", 6, 6, 2)
+            .with_new_state(NewState::Random)
+            .with_seed(42);
+
+        let mut world = World::new(config.clone().with_restart_after(10)).unwrap();
+        assert_eq!(world.search(None), Status::Solved);
+        assert!(world.restart_after.unwrap() > 10);
+
+        // The solution is still valid.
+        let cells: Vec<_> = (0..6)
+            .flat_map(|y| (0..6).map(move |x| (x, y, 0)))
+            .map(|coord| (coord, world.get_cell_state(coord).unwrap()))
+            .collect();
+        let mut check = World::seed_with_symmetry(config, &cells).unwrap();
+        assert_eq!(check.search(None), Status::Solved);
+
+        // The search can still find that there are no solutions.
+        let config = Config::new("B3/S23", 4, 4, 3)
+            .with_new_state(NewState::Random)
+            .with_restart_after(1);
+        let mut world = World::new(config).unwrap();
+        assert_eq!(world.search(None), Status::NoSolution);
+
+        assert!(matches!(
+            World::new(Config::new("B3/S23", 4, 4, 1).with_restart_after(0)),
+            Err(ConfigError::InvalidRestartAfter)
+        ));
+    }
+
+    #[test]
+    fn test_config_mut() {
+        let mut world = World::new(Config::new("B3/S23", 4, 4, 1)).unwrap();
+        assert_eq!(world.search(None), Status::Solved);
+
+        world
+            .config_mut()
+            .set_new_state(NewState::Alive)
+            .set_seed(1)
+            .set_max_population(Some(4))
+            .set_reduce_max_population(true);
+
+        let config = world.config();
+        assert_eq!(config.new_state, NewState::Alive);
+        assert_eq!(config.seed, Some(1));
+        assert_eq!(config.max_population, Some(4));
+        assert!(config.reduce_max_population);
+
+        // The new bound is used by the search.
+        while world.search(None) == Status::Solved {
+            assert!(world.population(0) <= 4);
+        }
+        assert_eq!(world.status(), Status::NoSolution);
+    }
+
+    #[test]
+    fn test_plan() {
+        let config = Config::new("B3/S23", 4, 3, 2);
+        let world = World::new(config).unwrap();
+        let plan = world.plan();
+
+        assert_eq!(plan.search_order, SearchOrder::ColumnFirst);
+        assert_eq!((plan.width, plan.height, plan.period), (4, 3, 2));
+        assert_eq!(plan.border, 1);
+        assert_eq!(plan.cell_count, 6 * 5 * 2);
+        assert_eq!(plan.cell_count, world.allocation_size());
+        assert_eq!(plan.region_cell_count, 4 * 3 * 2);
+        assert_eq!(plan.region_cell_count, world.region_size());
+        // The upper half of the first column, in the first generation only.
+        assert_eq!(plan.front_count, 2);
+        assert_eq!(plan.offsets.len(), 8);
+        assert!(plan.table_bytes > 0);
+        assert!(plan
+            .to_string()
+            .starts_with(&format!("search order: {}\n", SearchOrder::ColumnFirst)));
+    }
+
+    #[test]
+    fn test_canonical_solution_rle() {
+        // All the solutions are gliders, in different phases and positions.
+        let config = Config::new("B3/S23", 5, 5, 4).with_translations(1, 1);
+        let mut world = World::new(config).unwrap();
+        assert_eq!(world.canonical_solution_rle(), None);
+
+        let mut canonical = HashSet::new();
+        while world.search(None) == Status::Solved {
+            canonical.insert(world.canonical_solution_rle().unwrap());
+        }
+
+        assert_eq!(
+            canonical.into_iter().collect::<Vec<_>>(),
+            vec!["x = 3, y = 3, rule = B3/S23\n2bo$obo$b2o!".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_is_consistent() {
+        let config = Config::new("B3/S23", 4, 4, 1);
+
+        // A block is a still life.
+        let mut world = World::new(config.clone()).unwrap();
+        for coord in [(0, 0, 0), (0, 1, 0), (1, 0, 0), (1, 1, 0)] {
+            world.try_set_known(coord, CellState::Alive).unwrap();
+            assert!(world.is_consistent());
+        }
+        assert_eq!(world.status(), Status::NotStarted);
+        assert_eq!(world.search(None), Status::Solved);
+
+        // A lone living cell dies.
+        let mut world = World::new(config).unwrap();
+        world.try_set_known((0, 1, 0), CellState::Alive).unwrap();
+        assert!(world.is_consistent());
+        for coord in [(0, 0, 0), (0, 2, 0), (1, 0, 0), (1, 1, 0), (1, 2, 0)] {
+            world.try_set_known(coord, CellState::Dead).unwrap();
+        }
+        assert!(!world.is_consistent());
+        assert_eq!(world.search(None), Status::NoSolution);
+    }
+
+    #[test]
+    fn test_canonicalize_coord_closed_form() {
+        // The iterative version, moving one period at a time.
+        fn iterative(world: &World, coord: Coord) -> Coord {
+            let (mut x, mut y, mut t) = coord;
+            let config = world.config();
+            let (w, h, p) = (
+                config.width as i32,
+                config.height as i32,
+                config.period as i32,
+            );
+            let transformation = config.transformation;
+
+            while t < 0 {
+                t += p;
+                (x, y) = transformation.inverse().apply_with_size(x, y, w, h);
+                x -= config.dx;
+                y -= config.dy;
+            }
+
+            while t >= p {
+                t -= p;
+                x += config.dx;
+                y += config.dy;
+                (x, y) = transformation.apply_with_size(x, y, w, h);
+            }
+
+            (x, y, t)
+        }
+
+        let configs = [
+            Config::new("B3/S23", 5, 5, 4).with_translations(1, 1),
+            Config::new("B3/S23", 6, 6, 3)
+                .with_translations(0, 0)
+                .with_transformation(Transformation::R1),
+            Config::new("B3/S23", 7, 4, 2)
+                .with_translations(1, 0)
+                .with_transformation(Transformation::S2),
+            Config::new("B3/S23", 5, 5, 5)
+                .with_translations(2, 2)
+                .with_transformation(Transformation::S3),
+        ];
+
+        for config in configs {
+            let world = World::new(config).unwrap();
+            for t in [-1001, -17, -4, -1, 0, 1, 3, 4, 5, 9, 16, 1000, 1003] {
+                for (x, y) in [(0, 0), (2, 3), (-5, 7)] {
+                    assert_eq!(
+                        world.canonicalize_coord((x, y, t)),
+                        iterative(&world, (x, y, t))
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_unknown_mask_rle() {
+        let config = Config::new("B3/S23", 3, 2, 1);
+        let mut world = World::new(config).unwrap();
+        assert_eq!(
+            world.unknown_mask_rle(0, false),
+            "x = 3, y = 2, rule = B3/S23\nooo$\nooo!\n"
+        );
+
+        world.try_set_known((1, 0, 0), CellState::Alive).unwrap();
+        world.try_set_known((2, 1, 0), CellState::Dead).unwrap();
+        assert_eq!(
+            world.unknown_mask_rle(0, true),
+            "x = 3, y = 2, rule = B3/S23\nobo$2o!"
+        );
+
+        assert_eq!(world.search(None), Status::Solved);
+        assert_eq!(
+            world.unknown_mask_rle(0, true),
+            "x = 3, y = 2, rule = B3/S23\n!"
+        );
+    }
+
+    #[test]
+    fn test_stats() {
+        let config = Config::new("B3/S23", 4, 4, 1);
+        let mut world = World::new(config).unwrap();
+        let stats = world.stats();
+        assert_eq!((stats.steps, stats.backtracks), (0, 0));
+        assert_eq!(stats.population, vec![0]);
+
+        assert_eq!(world.search(None), Status::Solved);
+        let stats = world.stats();
+        assert!(stats.steps > 0);
+        assert_eq!(stats.stack_depth, world.stack_depth());
+        assert_eq!(stats.population, vec![world.population(0)]);
+
+        assert_eq!(world.search(None), Status::Solved);
+        let next = world.stats();
+        assert!(next.steps > stats.steps);
+        assert!(next.backtracks > stats.backtracks);
+    }
+
+    #[test]
+    fn test_search_until_instant() {
+        use std::time::{Duration, Instant};
+
+        let config = Config::new("B3/S23", 4, 4, 1);
+
+        // A deadline in the past does nothing.
+        let mut world = World::new(config.clone()).unwrap();
+        assert_eq!(
+            world.search_until_instant(Instant::now() - Duration::from_secs(1)),
+            Status::NotStarted
+        );
+
+        // The same solutions as a normal search are found before a distant deadline.
+        let deadline = Instant::now() + Duration::from_secs(60);
+        let mut expected = World::new(config).unwrap();
+        while world.search_until_instant(deadline) == Status::Solved {
+            assert_eq!(expected.search(None), Status::Solved);
+            assert_eq!(world.rle(0, true), expected.rle(0, true));
+        }
+        assert_eq!(world.status(), Status::NoSolution);
+        assert_eq!(expected.search(None), Status::NoSolution);
+    }
+
+    #[test]
+    fn test_equivalent_search_spaces() {
+        let solutions = |config: Config| World::new(config).unwrap().enumerate_all_solutions();
+
+        // Whether a grid in RLE format is invariant under a horizontal or vertical reflection.
+        let is_reflection_symmetric = |rle: &String| {
+            let (header, body) = rle.split_once('\n').unwrap();
+            let width = header
+                .split(", ")
+                .next()
+                .unwrap()
+                .trim_start_matches("x = ")
+                .parse::<usize>()
+                .unwrap();
+
+            let mut rows = Vec::new();
+            let mut row = Vec::new();
+            let mut count = 0;
+            for c in body.chars() {
+                match c {
+                    '0'..='9' => count = count * 10 + c.to_digit(10).unwrap() as usize,
+                    'b' | 'o' => {
+                        row.extend(std::iter::repeat_n(c == 'o', count.max(1)));
+                        count = 0;
+                    }
+                    '$' | '!' => {
+                        row.resize(width, false);
+                        rows.push(std::mem::take(&mut row));
+                        for _ in 1..count {
+                            rows.push(vec![false; width]);
+                        }
+                        count = 0;
+                    }
+                    _ => {}
+                }
+            }
+
+            let flipped = rows.iter().rev().cloned().collect::<Vec<_>>();
+            let mirrored = rows
+                .iter()
+                .map(|row| row.iter().rev().copied().collect::<Vec<_>>())
+                .collect::<Vec<_>>();
+            rows == flipped || rows == mirrored
+        };
+
+        // In a square world, a pattern with vertical reflection symmetry is a rotation of
+        // a pattern with horizontal reflection symmetry, so the canonical RLEs are the same.
+        for (w, h) in [(4, 4), (5, 5)] {
+            // The search order does not matter.
+            let config = Config::still_life("B3/S23", w, h);
+            let all = solutions(config.clone());
+            assert!(!all.is_empty());
+            for search_order in [SearchOrder::RowFirst, SearchOrder::ColumnFirst] {
+                assert_eq!(
+                    solutions(config.clone().with_search_order(search_order)),
+                    all
+                );
+            }
+
+            // A symmetric search finds exactly the symmetric solutions.
+            let symmetric = solutions(config.with_symmetry(Symmetry::D2H));
+            let filtered = all
+                .into_iter()
+                .filter(is_reflection_symmetric)
+                .collect::<BTreeSet<_>>();
+            assert_eq!(symmetric, filtered);
+        }
+
+        // Rotating the generations only removes duplicates.
+        let config = Config::new("B3/S23", 5, 5, 2).with_full_bounding_box();
+        assert_eq!(
+            solutions(config.clone()),
+            solutions(config.without_generation_rotation())
+        );
+    }
+
+    #[test]
+    fn test_grow_preserving() {
+        // A block in the top left corner.
+        let block = || {
+            let config = Config::new("B3/S23", 4, 4, 1);
+            let mut world = World::new(config).unwrap();
+            for coord in [(0, 0, 0), (1, 0, 0), (0, 1, 0), (1, 1, 0)] {
+                world.try_set_known(coord, CellState::Alive).unwrap();
+            }
+            assert_eq!(world.search(None), Status::Solved);
+            world
+        };
+
+        let mut corner = block();
+        assert!(corner.grow_preserving(GrowAlignment::Corner).unwrap());
+        assert_eq!((corner.config().width, corner.config().height), (4, 5));
+        assert_eq!(corner.get_cell_state((0, 0, 0)), Some(CellState::Alive));
+        assert_eq!(corner.get_cell_state((2, 2, 0)), Some(CellState::Dead));
+        assert_eq!(corner.search(None), Status::Solved);
+
+        let mut center = block();
+        assert!(center.grow_preserving(GrowAlignment::Center).unwrap());
+        assert_eq!(center.get_cell_state((0, 1, 0)), Some(CellState::Alive));
+        assert_eq!(center.get_cell_state((2, 3, 0)), Some(CellState::Dead));
+
+        // With a C2 symmetry, the block is no longer symmetric after growing by 1.
+        let config = Config::new("B3/S23", 4, 4, 1).with_symmetry(Symmetry::C2);
+        let mut world = World::new(config).unwrap();
+        world.try_set_known((1, 1, 0), CellState::Alive).unwrap();
+        assert_eq!(world.search(None), Status::Solved);
+        assert!(!world.grow_preserving(GrowAlignment::Corner).unwrap());
+        assert_eq!((world.config().width, world.config().height), (4, 5));
+        assert_eq!(world.get_cell_state((1, 1, 0)), None);
+    }
+
+    #[test]
+    fn test_search_with_filter() {
+        // Only accept still lifes with more than 4 cells.
+        let config = Config::new("B3/S23", 4, 4, 1);
+        let mut world = World::new(config).unwrap();
+        let mut populations = Vec::new();
+
+        while world.search_with_filter(None, |world| world.population(0) > 4) == Status::Solved {
+            populations.push(world.population(0));
+        }
+
+        let mut all_populations = Vec::new();
+        let config = Config::new("B3/S23", 4, 4, 1);
+        let mut world = World::new(config).unwrap();
+        world.search_with(None, |world| all_populations.push(world.population(0)));
+
+        assert!(!populations.is_empty());
+        assert!(populations.iter().all(|&population| population > 4));
+        assert_eq!(
+            populations,
+            all_populations
+                .into_iter()
+                .filter(|&population| population > 4)
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_cached_true_period() {
+        // A blinker in a period-2 world.
+        let config = Config::new("B3/S23", 3, 3, 2);
+        let mut world = World::new(config).unwrap();
+        assert_eq!(world.search(None), Status::Solved);
+        assert_eq!(world.cached_true_period(), 2);
+        assert_eq!(world.true_period_cache.get(), Some(2));
+
+        // Any change of the cells invalidates the cache.
+        world.backtrack_to(0);
+        assert_eq!(world.true_period_cache.get(), None);
+        assert_eq!(world.cached_true_period(), world.true_period());
+    }
+
+    #[test]
+    fn test_velocity() {
+        // The glider.
+        let config = Config::new("B3/S23", 5, 5, 4).with_translations(1, 1);
+        let mut world = World::new(config).unwrap();
+        assert_eq!(world.velocity(), None);
+        assert_eq!(world.search(None), Status::Solved);
+        assert_eq!(world.velocity(), Some((1, 1, 4)));
+
+        // The glider, found by a search with twice the period.
+        let config = Config::new("B3/S23", 5, 5, 8)
+            .with_translations(2, 2)
+            .with_search_up_to_period(8);
+        let mut world = World::new(config).unwrap();
+        assert_eq!(world.search(None), Status::Solved);
+        assert_eq!(world.velocity(), Some((1, 1, 4)));
+
+        // The blinker does not move.
+        let config = Config::new("B3/S23", 3, 3, 2);
+        let mut world = World::new(config).unwrap();
+        assert_eq!(world.search(None), Status::Solved);
+        assert_eq!(world.velocity(), None);
+    }
+
+    #[test]
+    fn test_minimal_phase() {
+        // The beacon has 8 cells in one phase, and 6 cells in the other.
+        let beacon = |cells: &[(i32, i32)]| {
+            let config = Config::new("B3/S23", 4, 4, 2);
+            let mut world = World::new(config).unwrap();
+            for x in 0..4 {
+                for y in 0..4 {
+                    let state = if cells.contains(&(x, y)) {
+                        CellState::Alive
+                    } else {
+                        CellState::Dead
+                    };
+                    world.try_set_known((x, y, 0), state).unwrap();
+                }
+            }
+            assert_eq!(world.search(None), Status::Solved);
+            world
+        };
+
+        let world = beacon(&[
+            (0, 0),
+            (1, 0),
+            (0, 1),
+            (1, 1),
+            (2, 2),
+            (3, 2),
+            (2, 3),
+            (3, 3),
+        ]);
+        assert_eq!(world.minimal_phase(), 1);
+        assert_eq!(world.population(1), 6);
+
+        let world = beacon(&[(0, 0), (1, 0), (0, 1), (3, 2), (2, 3), (3, 3)]);
+        assert_eq!(world.minimal_phase(), 0);
+    }
+
+    #[test]
+    fn test_search_counted() {
+        let config = Config::new("B3/S23", 16, 16, 1);
+        let mut world = World::new(config).unwrap();
+        assert_eq!(world.search_counted(10), (Status::Running, 10));
+        assert_eq!(world.stats().steps, 10);
+
+        let config = Config::new("B3/S23", 3, 3, 2);
+        let mut world = World::new(config).unwrap();
+        let (status, steps) = world.search_counted(1000);
+        assert_eq!(status, Status::Solved);
+        assert!(steps < 1000);
+        assert_eq!(world.stats().steps, steps as u64);
+
+        assert_eq!(world.search_counted(0), (Status::Solved, 0));
+    }
+
+    #[test]
+    fn test_search_batch() {
+        let mut world = World::new(Config::new("B3/S23", 3, 3, 2)).unwrap();
+
+        // A zero budget still makes progress.
+        let outcome = world.search_batch(0);
+        assert_eq!(world.stats().steps, 1);
+        assert!(outcome.exhausted_budget || outcome.status == Status::Solved);
+
+        let mut batches = 1;
+        while world.search_batch(1).exhausted_budget {
+            batches += 1;
+        }
+        assert_eq!(world.status(), Status::Solved);
+        assert_eq!(world.stats().steps, batches + 1);
+    }
+
+    #[test]
+    fn test_resolved_search_order() {
+        let config = Config::new("B3/S23", 4, 16, 1);
+        assert_eq!(config.search_order, None);
+
+        let world = World::new(config.clone()).unwrap();
+        assert_eq!(world.resolved_search_order(), SearchOrder::RowFirst);
+        assert_eq!(world.plan().search_order, SearchOrder::RowFirst);
+
+        let world = World::new(config.with_search_order(SearchOrder::ColumnFirst)).unwrap();
+        assert_eq!(world.resolved_search_order(), SearchOrder::ColumnFirst);
+    }
+
+    #[test]
+    fn test_decisions() {
+        let mut world = World::new(Config::new("B3/S23", 4, 4, 2)).unwrap();
+        assert!(world.decisions().is_empty());
+
+        assert_eq!(world.search(None), Status::Solved);
+        let decisions = world.decisions();
+        assert!(!decisions.is_empty());
+        assert_eq!(
+            decisions.len(),
+            world
+                .stack
+                .iter()
+                .filter(|&&(_, reason)| matches!(reason, Reason::Guessed | Reason::Flipped))
+                .count()
+        );
+        for &(coord, state) in &decisions {
+            assert_eq!(world.get_cell_state(coord), Some(state));
+        }
+
+        // Replay the decisions after some backtracking.
+        let config = Config::new("B3/S23", 5, 5, 4).with_translations(1, 1);
+        let mut world = World::new(config.clone()).unwrap();
+        assert_eq!(world.search(None), Status::Solved);
+        assert!(world
+            .stack
+            .iter()
+            .any(|&(_, reason)| reason == Reason::Flipped));
+
+        let mut replay = World::new(config).unwrap();
+        for (coord, state) in world.decisions() {
+            replay.guess_cell(coord, state).unwrap();
+        }
+        assert_eq!(replay.search(1), Status::Solved);
+        assert_eq!(replay.rle(0, true), world.rle(0, true));
+    }
+
+    #[test]
+    fn test_front_generation() {
+        let phases = |config: Config| {
+            let mut world = World::new(config).unwrap();
+            let mut phases = BTreeSet::new();
+            world.search_with(None, |world| {
+                let mut rles = [world.rle(0, true), world.rle(1, true)];
+                rles.sort();
+                phases.insert(rles);
+            });
+            phases
+        };
+
+        // The same oscillators are found, in a different phase.
+        let config = Config::new("B3/S23", 5, 5, 2).with_full_bounding_box();
+        let first = phases(config.clone());
+        assert!(!first.is_empty());
+        assert_eq!(phases(config.clone().with_front_generation(1)), first);
+
+        assert!(matches!(
+            World::new(config.clone().with_front_generation(2)),
+            Err(ConfigError::InvalidFrontGeneration)
+        ));
+        assert!(matches!(
+            World::new(
+                config
+                    .without_generation_rotation()
+                    .with_front_generation(1)
+            ),
+            Err(ConfigError::InvalidFrontGeneration)
+        ));
+    }
+
+    #[test]
+    fn test_search_max_backtracks() {
+        let config = Config::new("B3/S23", 5, 5, 4).with_translations(1, 1);
+
+        let mut world = World::new(config.clone()).unwrap();
+        assert_eq!(world.search_max_backtracks(0), Status::NotStarted);
+        assert_eq!(world.search_max_backtracks(3), Status::Running);
+        assert_eq!(world.stats().backtracks, 3);
+
+        // Without a step budget, the search finds the same solution.
+        let mut expected = World::new(config).unwrap();
+        assert_eq!(expected.search(None), Status::Solved);
+        while world.search_max_backtracks(1) == Status::Running {}
+        assert_eq!(world.status(), Status::Solved);
+        assert_eq!(world.rle(0, true), expected.rle(0, true));
+        assert_eq!(world.stats().backtracks, expected.stats().backtracks);
+    }
+
+    #[test]
+    fn test_diff_rle() {
+        let glider = || {
+            let config = Config::new("B3/S23", 5, 5, 4).with_translations(1, 1);
+            let mut world = World::new(config).unwrap();
+            assert_eq!(world.search(None), Status::Solved);
+            world
+        };
+
+        let a = glider();
+        let b = glider();
+        let same = diff_rle(&a, 0, &b, 0);
+        assert!(same.starts_with("x = 3, y = 3\n"));
+        assert!(!same.contains(['-', '+']));
+
+        // A different phase of the glider.
+        let different = diff_rle(&a, 0, &a, 1);
+        assert!(different.contains('-') || different.contains('+'));
+        assert_eq!(different.matches(['o', '-']).count(), a.population(0));
+        assert_eq!(different.matches(['o', '+']).count(), a.population(1));
+    }
+
+    #[test]
+    fn test_seed_from_rle_reader() {
+        let config = Config::new("B3/S23", 5, 5, 4).with_translations(1, 1);
+        let rle = "#C The first rows of a glider, with the last two rows left unknown.\n\
+                   x = 5, y = 3, rule = B3/S23\n\
+                   5b$\n5b$b3o?!\n";
+        let mut world = World::seed_from_rle_reader(config.clone(), rle.as_bytes()).unwrap();
+        assert_eq!(world.get_cell_state((1, 2, 0)), Some(CellState::Alive));
+        assert_eq!(world.get_cell_state((0, 2, 0)), Some(CellState::Dead));
+        assert_eq!(world.get_cell_state((4, 2, 0)), None);
+        assert_eq!(world.get_cell_state((1, 3, 0)), None);
+        assert_eq!(world.search(None), Status::Solved);
+        assert_eq!(world.population(0), 5);
+
+        // Dead cells outside the world are fine, but living cells are not.
+        assert!(World::seed_from_rle_reader(config.clone(), &b"6b$o!"[..]).is_ok());
+        assert!(matches!(
+            World::seed_from_rle_reader(config.clone(), &b"5bo!"[..]),
+            Err(RleError::OutOfBounds { coord: (5, 0, 0) })
+        ));
+
+        // Huge runs are clamped to the edge of the world instead of being walked cell by cell.
+        let world =
+            World::seed_from_rle_reader(config.clone(), &b"4000000000b$4000000000?$2o!"[..])
+                .unwrap();
+        assert_eq!(world.get_cell_state((4, 0, 0)), Some(CellState::Dead));
+        assert_eq!(world.get_cell_state((0, 1, 0)), None);
+        assert_eq!(world.get_cell_state((1, 2, 0)), Some(CellState::Alive));
+        assert!(matches!(
+            World::seed_from_rle_reader(config.clone(), &b"2b4000000000o!"[..]),
+            Err(RleError::OutOfBounds { coord: (5, 0, 0) })
+        ));
+        assert!(matches!(
+            World::seed_from_rle_reader(config.clone(), &b"4000000000$4000000000o!"[..]),
+            Err(RleError::OutOfBounds {
+                coord: (0, i32::MAX, 0)
+            })
+        ));
+        assert!(matches!(
+            World::seed_from_rle_reader(config, &b"o$bx!"[..]),
+            Err(RleError::InvalidChar { line: 1, char: 'x' })
+        ));
+        assert!(matches!(
+            World::seed_from_rle_reader(Config::new("B3/S23", 0, 5, 1), &b"o!"[..]),
+            Err(RleError::Seed(SeedError::InvalidConfig(
+                ConfigError::InvalidSize
+            )))
+        ));
+    }
+
+    #[test]
+    fn test_still_life_fast_path() {
+        let solutions = |config: Config| {
+            let mut world = World::new(config).unwrap();
+            let front_count = world.plan().front_count;
+            let mut solutions = BTreeSet::new();
+            world.search_with(None, |world| {
+                assert_eq!(world.cached_true_period(), 1);
+                solutions.insert(world.rle(0, true));
+            });
+            (front_count, solutions)
+        };
+
+        let config = Config::still_life("B3/S23", 4, 4);
+        let (front_count, still_lifes) = solutions(config.clone());
+        // The left half of the first row.
+        assert_eq!(front_count, 2);
+        // Loaf, pond, long boat, and their rotations and reflections.
+        assert!(still_lifes.len() > 3);
+
+        // With only one generation, the generation rotation makes no difference.
+        assert_eq!(
+            solutions(config.without_generation_rotation()),
+            (front_count, still_lifes)
+        );
+    }
+
+    #[test]
+    fn test_guess_cell() {
+        let config = Config::new("B3/S23", 4, 4, 2);
+        let solutions = |world: &mut World| {
+            let mut solutions = BTreeSet::new();
+            world.search_with(None, |world| {
+                solutions.insert(world.rle(0, true));
+            });
+            solutions
+        };
+
+        let expected = solutions(&mut World::new(config.clone()).unwrap());
+        assert!(!expected.is_empty());
+
+        // A guess at the end of the search order, which is flipped when backtracking.
+        let mut world = World::new(config.clone()).unwrap();
+        world.guess_cell((3, 3, 1), CellState::Alive).unwrap();
+        assert_eq!(world.decisions(), [((3, 3, 1), CellState::Alive)]);
+        assert_eq!(solutions(&mut world), expected);
+
+        let mut world = World::new(config).unwrap();
+        assert_eq!(world.guess_cell((-1, 0, 0), CellState::Dead), Ok(()));
+        assert_eq!(
+            world.guess_cell((-1, 0, 0), CellState::Alive),
+            Err(ConflictError {
+                coord: (-1, 0, 0),
+                existing: CellState::Dead,
+                attempted: CellState::Alive,
+            })
+        );
+        assert!(world.decisions().is_empty());
+    }
+
+    #[test]
+    fn test_find_distinct() {
+        let config = Config::new("B3/S23", 4, 4, 2);
+        let expected = World::new(config.clone())
+            .unwrap()
+            .enumerate_all_solutions();
+        assert!(expected.len() > 1);
+
+        let mut world = World::new(config.clone()).unwrap();
+        let all = world.find_distinct(usize::MAX);
+        assert_eq!(world.status(), Status::NoSolution);
+        assert_eq!(all.len(), expected.len());
+        assert_eq!(all.iter().cloned().collect::<BTreeSet<_>>(), expected);
+
+        let mut world = World::new(config).unwrap();
+        assert!(world.find_distinct(0).is_empty());
+        assert_eq!(world.status(), Status::NotStarted);
+        assert_eq!(world.find_distinct(1), all[..1]);
+        assert_eq!(world.status(), Status::Solved);
+
+        // All the gliders in the world are the same object.
+        let mut world = World::new(Config::new("B3/S23", 5, 5, 4).with_translations(1, 1)).unwrap();
+        assert_eq!(world.find_distinct(10).len(), 1);
+    }
+
+    #[test]
+    fn test_enumerate_sorted() {
+        let config = Config::new("B3/S23", 4, 4, 1);
+        let found = World::new(config.clone())
+            .unwrap()
+            .find_distinct(usize::MAX);
+        let sorted = World::new(config.clone())
+            .unwrap()
+            .enumerate_sorted(usize::MAX);
+        assert!(sorted.len() > 2);
+        assert_eq!(
+            sorted.iter().collect::<BTreeSet<_>>(),
+            found.iter().collect::<BTreeSet<_>>()
+        );
+
+        // The block has the smallest population.
+        assert_eq!(sorted[0], "x = 2, y = 2, rule = B3/S23\n2o$2o!");
+
+        let population = |rle: &String| {
+            let (_, body) = rle.split_once('\n').unwrap();
+            let mut count = 0;
+            let mut population = 0;
+            for c in body.chars() {
+                if let Some(digit) = c.to_digit(10) {
+                    count = count * 10 + digit;
+                } else {
+                    if c == 'o' {
+                        population += count.max(1);
+                    }
+                    count = 0;
+                }
+            }
+            population
+        };
+        assert!(sorted
+            .windows(2)
+            .all(|w| population(&w[0]) <= population(&w[1])));
+
+        let mut world = World::new(config).unwrap();
+        let first = world.enumerate_sorted(2);
+        assert_eq!(first.len(), 2);
+        assert!(first.iter().all(|rle| found[..2].contains(rle)));
+    }
+
+    #[test]
+    fn test_generation_rotation_composite_period() {
+        // In this world, there are both period 2 and period 4 oscillators.
+        let config = Config::new("B2/S", 5, 5, 4);
+
+        let mut world = World::new(config.clone().with_search_up_to_period(4)).unwrap();
+        let mut periods = BTreeSet::new();
+        world.search_with(None, |world| {
+            periods.insert(world.true_period());
+        });
+        assert_eq!(periods, BTreeSet::from([2, 4]));
+
+        let solutions = |config: Config| {
+            let mut world = World::new(config).unwrap();
+            let mut solutions = BTreeSet::new();
+            world.search_with(None, |world| {
+                // Compare all the generations, not only the first one.
+                for p0 in [1, 2] {
+                    assert!((0..4).any(|t| (0..5).any(|x| (0..5).any(|y| {
+                        world.get_cell_state((x, y, t)) != world.get_cell_state((x, y, t + p0))
+                    }))));
+                }
+                solutions.insert(world.canonical_solution_rle().unwrap());
+            });
+            solutions
+        };
+
+        let rotated = solutions(config.clone());
+        assert!(!rotated.is_empty());
+        assert_eq!(rotated, solutions(config.without_generation_rotation()));
+    }
+
+    #[test]
+    fn test_rle_searched_only() {
+        let mut world = World::new(Config::new("B3/S23", 4, 4, 1)).unwrap();
+        assert_eq!(world.search(None), Status::Solved);
+        assert_eq!(world.rle_searched_only(0, true), world.rle(0, true));
+
+        let mut world =
+            World::new(Config::new("B3/S23", 4, 4, 1).with_symmetry(Symmetry::C4)).unwrap();
+        assert_eq!(world.search(None), Status::Solved);
+        let searched = world.rle_searched_only(0, false);
+
+        // One cell in each orbit of size 4 is shown, and the other three are unknown.
+        let unknown = searched.matches('?').count();
+        assert_eq!(unknown, 12);
+
+        // The shown cells agree with the full pattern.
+        let full = world.rle(0, false);
+        for (a, b) in searched.chars().zip(full.chars()) {
+            assert!(a == '?' || a == b);
+        }
+    }
+
+    #[test]
+    fn test_grow_or_relax() {
+        assert_eq!(
+            "max-population:3".parse::<RelaxStrategy>().unwrap(),
+            RelaxStrategy::MaxPopulation(3)
+        );
+        assert_eq!(
+            "Size".parse::<RelaxStrategy>().unwrap(),
+            RelaxStrategy::Size(1)
+        );
+        assert!("width:1".parse::<RelaxStrategy>().is_err());
+        for strategy in [
+            RelaxStrategy::MaxPopulation(2),
+            RelaxStrategy::DiagonalWidth(1),
+            RelaxStrategy::Size(3),
+        ] {
+            assert_eq!(
+                strategy.to_string().parse::<RelaxStrategy>().unwrap(),
+                strategy
+            );
+        }
+
+        let config = Config::new("B3/S23", 4, 4, 1);
+        assert!(matches!(
+            World::new(
+                config
+                    .clone()
+                    .with_relax_on_no_solution(RelaxStrategy::Size(0))
+            ),
+            Err(ConfigError::InvalidRelaxStrategy)
+        ));
+        assert!(matches!(
+            World::new(
+                config
+                    .clone()
+                    .with_relax_on_no_solution(RelaxStrategy::MaxPopulation(1))
+            ),
+            Err(ConfigError::InvalidRelaxStrategy)
+        ));
+
+        // No still life with at most 3 cells, but the block has 4.
+        let mut world = World::new(
+            config
+                .clone()
+                .with_max_population(3)
+                .with_relax_on_no_solution(RelaxStrategy::MaxPopulation(1)),
+        )
+        .unwrap();
+        assert_eq!(world.search(None), Status::NoSolution);
+        assert!(world.grow_or_relax().unwrap());
+        assert_eq!(world.config().max_population, Some(4));
+        assert_eq!(world.search(None), Status::Solved);
+
+        // A bound that already allows every cell to be alive cannot be relaxed.
+        let mut world = World::new(
+            config
+                .clone()
+                .with_max_population(15)
+                .with_relax_on_no_solution(RelaxStrategy::MaxPopulation(3)),
+        )
+        .unwrap();
+        assert!(world.grow_or_relax().unwrap());
+        assert_eq!(world.config().max_population, Some(16));
+        assert!(!world.grow_or_relax().unwrap());
+
+        // So can a bound that was cleared after the world was built.
+        world.config_mut().set_max_population(None);
+        assert!(!world.grow_or_relax().unwrap());
+
+        let mut world = World::new(
+            Config::new("B3/S23", 4, 4, 1)
+                .with_diagonal_width(2)
+                .with_relax_on_no_solution(RelaxStrategy::DiagonalWidth(3)),
+        )
+        .unwrap();
+        assert!(world.grow_or_relax().unwrap());
+        assert_eq!(world.config().diagonal_width, Some(4));
+        assert!(!world.grow_or_relax().unwrap());
+
+        let mut world =
+            World::new(config.with_relax_on_no_solution(RelaxStrategy::Size(2))).unwrap();
+        assert!(world.grow_or_relax().unwrap());
+        assert_eq!((world.config().width, world.config().height), (5, 5));
+    }
+
+    #[test]
+    fn test_grow_memory_limit() {
+        let config = Config::new("B3/S23", 4, 4, 1);
+
+        // A grown world keeps the memory limit.
+        let mut world = World::new_with_limit(config.clone(), None).unwrap();
+        world.increase_world_size().unwrap();
+        assert_eq!(world.memory_limit, None);
+
+        // Growing beyond the limit is an error, and the world is left unchanged.
+        let limit = World::new(config.clone()).unwrap().plan().world_bytes;
+        let mut world = World::new_with_limit(config, limit).unwrap();
+        assert!(matches!(
+            world.increase_world_size(),
+            Err(ConfigError::WorldTooLarge { .. })
+        ));
+        assert!(matches!(
+            world.grow_preserving(GrowAlignment::Corner),
+            Err(ConfigError::WorldTooLarge { .. })
+        ));
+        assert!(matches!(
+            world.grow_or_relax(),
+            Err(ConfigError::WorldTooLarge { .. })
+        ));
+        assert_eq!((world.config().width, world.config().height), (4, 4));
+        assert_eq!(world.memory_limit, Some(limit));
+    }
+
+    #[test]
+    fn test_torus() {
+        let config = Config::new("B3/S23", 6, 3, 1).with_boundary(Boundary::Torus);
+        let mut world = World::new(config).unwrap();
+        let alive = |world: &World, x: i32, y: i32| {
+            world.get_cell_state((x, y, 0)) == Some(CellState::Alive)
+        };
+
+        let mut count = 0;
+        while world.search(None) == Status::Solved {
+            count += 1;
+            for x in 0..6 {
+                for y in 0..3 {
+                    // The wrapped neighborhood is computed independently of the world.
+                    let neighbors = (-1..=1)
+                        .flat_map(|i| (-1..=1).map(move |j| (i, j)))
+                        .filter(|&(i, j)| {
+                            (i, j) != (0, 0) && alive(&world, (x + i + 6) % 6, (y + j + 3) % 3)
+                        })
+                        .count();
+                    let next = neighbors == 3 || alive(&world, x, y) && neighbors == 2;
+                    assert_eq!(next, alive(&world, x, y), "{}", world.rle(0, true));
+                }
+            }
+            assert!((0..6).any(|x| (0..3).any(|y| alive(&world, x, y) != alive(&world, x + 3, y))));
+        }
+        assert!(count > 0);
+
+        // An agar on a 3x3 torus, repeated twice, is not a solution on the 6x3 torus.
+        let config = Config::new("B3/S23", 3, 3, 1).with_boundary(Boundary::Torus);
+        let mut world = World::new(config.clone()).unwrap();
+        assert_eq!(world.search(None), Status::Solved);
+        let rle = (0..3)
+            .map(|y| {
+                (0..6)
+                    .map(|x| if alive(&world, x, y) { 'o' } else { 'b' })
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("$");
+        let config = Config { width: 6, ..config };
+        let mut world = World::seed_from_rle_reader(config, rle.as_bytes()).unwrap();
+        assert_eq!(world.search(None), Status::NoSolution);
+
+        assert!(matches!(
+            World::new(Config::new("B3/S23", 2, 5, 1).with_boundary(Boundary::Torus)),
+            Err(ConfigError::TorusTooSmall { min_size: 3 })
+        ));
+        assert!(matches!(
+            World::new(
+                Config::new("B3/S23", 5, 5, 1)
+                    .with_boundary(Boundary::Torus)
+                    .with_diagonal_width(3)
+            ),
+            Err(ConfigError::HasDiagonalWidth)
+        ));
+    }
+
+    #[test]
+    fn test_require_alive() {
+        let config = Config::new("B3/S23", 4, 4, 1);
+        let mut expected = Vec::new();
+        let mut world = World::new(config.clone()).unwrap();
+        while world.search(None) == Status::Solved {
+            if world.get_cell_state((1, 1, 0)) == Some(CellState::Alive) {
+                expected.push(world.rle(0, true));
+            }
+        }
+
+        let mut required = Vec::new();
+        let mut world = World::new(config.clone().with_require_alive((1, 1, 0))).unwrap();
+        while world.search(None) == Status::Solved {
+            required.push(world.rle(0, true));
+        }
+        assert!(!required.is_empty());
+        assert_eq!(required, expected);
+
+        assert!(matches!(
+            World::new(config.with_require_alive((4, 0, 0))),
+            Err(ConfigError::InvalidRequireAlive)
+        ));
+    }
+
+    #[test]
+    fn test_switch_new_state() {
+        let config = Config::new("B3/S23", 5, 5, 4).with_translations(1, 1);
+        let solutions = |world: &mut World| {
+            let mut solutions = Vec::new();
+            while world.search(None) == Status::Solved {
+                solutions.push(world.rle(0, true));
+            }
+            solutions.sort();
+            solutions
+        };
+        let expected = solutions(&mut World::new(config.clone()).unwrap());
+        assert!(!expected.is_empty());
+
+        // Switch to random guessing in the middle of the search.
+        // The same solutions are found, each exactly once.
+        for steps in [1, 10, 50] {
+            let mut world = World::new(config.clone()).unwrap();
+            assert_eq!(world.search(steps), Status::Running);
+            world
+                .config_mut()
+                .set_new_state(NewState::Random)
+                .set_seed(steps as u64);
+            assert_eq!(solutions(&mut world), expected);
+        }
+    }
+
+    #[test]
+    fn test_search_zero_steps() {
+        let mut world = World::new(Config::new("B3/S23", 4, 4, 1)).unwrap();
+        assert_eq!(world.search(0), Status::NotStarted);
+        assert_eq!(world.status(), Status::NotStarted);
+
+        assert_eq!(world.search(None), Status::Solved);
+        let solution = world.rle(0, true);
+        let depth = world.stack_depth();
+
+        assert_eq!(world.search(0), Status::Solved);
+        assert_eq!(world.rle(0, true), solution);
+        assert_eq!(world.stack_depth(), depth);
+        assert_eq!(world.search_with(0, |_| unreachable!()), Status::Solved);
+    }
+
+    #[test]
+    fn test_to_frames_ascii() {
+        let config = Config::new("B3/S23", 3, 3, 2);
+        let mut world = World::new(config).unwrap();
+        assert_eq!(world.search(None), Status::Solved);
+
+        let frames = world.to_frames_ascii();
+        assert_eq!(frames.len(), 2);
+        assert_ne!(frames[0], frames[1]);
+
+        for frame in frames {
+            let lines: Vec<_> = frame.lines().collect();
+            assert_eq!(lines.len(), 3);
+            assert!(lines.iter().all(|line| line.len() == 4));
+        }
+    }
+
+    #[test]
+    fn test_without_generation_rotation() {
+        let solutions = |config: Config| {
+            let mut world = World::new(config).unwrap();
+            let mut solutions = Vec::new();
+            world.search_with(None, |world| solutions.push(world.to_frames_ascii()));
+            solutions
+        };
+
+        let config = Config::new("B3/S23", 5, 5, 2);
+        let with_rotation = solutions(config.clone());
+        let without_rotation = solutions(config.without_generation_rotation());
+
+        assert!(without_rotation.len() >= with_rotation.len());
+
+        // Every solution found without the optimization is a rotation of the generations
+        // of a solution found with it, and vice versa.
+        for frames in &with_rotation {
+            assert!(without_rotation.contains(frames));
+        }
+        for frames in &without_rotation {
+            assert!((0..frames.len()).any(|t| {
+                let mut rotated = frames.clone();
+                rotated.rotate_left(t);
+                with_rotation.contains(&rotated)
+            }));
+        }
+    }
+
+    #[test]
+    fn test_rule() {
+        let world = World::new(Config::new("B36/S23", 4, 4, 1)).unwrap();
+        assert_eq!(world.rule().birth, [3, 6]);
+        assert_eq!(world.rule().survival, [2, 3]);
+        assert_eq!(world.rule().neighborhood_size(), 8);
+        assert_eq!(world.rule_table().neighborhood_size, 8);
+    }
+
+    #[test]
+    fn test_memory_limit() {
+        let config = Config::new("B3/S23", 100_000, 100_000, 1);
+        assert!(matches!(
+            World::new(config),
+            Err(ConfigError::WorldTooLarge { .. })
+        ));
+
+        let config = Config::new("B3/S23", 16, 16, 1);
+        assert!(World::new_with_limit(config.clone(), None).is_ok());
+        let world = World::new_with_limit(config.clone(), 1 << 20).unwrap();
+        assert_eq!(world.memory_limit, Some(1 << 20));
+        assert!(matches!(
+            World::new_with_limit(config, 1024),
+            Err(ConfigError::WorldTooLarge { bytes }) if bytes > 1024
+        ));
+    }
+
+    #[test]
+    fn test_get_cell_state_exact() {
+        let config = Config::new("B3/S23", 5, 5, 4).with_translations(1, 1);
+        let mut world = World::new(config).unwrap();
+        assert_eq!(world.search(None), Status::Solved);
+
+        for t in 0..4 {
+            for y in 0..5 {
+                for x in 0..5 {
+                    assert_eq!(
+                        world.get_cell_state_exact((x, y, t)),
+                        world.get_cell_state((x, y, t))
+                    );
+                }
+            }
+        }
+
+        assert_eq!(world.get_cell_state_exact((0, 0, -1)), None);
+        assert_eq!(world.get_cell_state_exact((0, 0, 4)), None);
+        assert_eq!(world.get_cell_state_exact((-10, 0, 0)), None);
+        assert_eq!(world.get_cell_state((-10, 0, 0)), Some(CellState::Dead));
+    }
+
+    #[test]
+    fn test_rle_center_symmetric() {
+        let cells: Vec<_> = (0..4)
+            .flat_map(|y| (0..4).map(move |x| (x, y)))
+            .map(|(x, y)| {
+                let state = if (1..3).contains(&x) && (1..3).contains(&y) {
+                    CellState::Alive
+                } else {
+                    CellState::Dead
+                };
+                ((x, y, 0), state)
+            })
+            .collect();
+
+        let config = Config::new("B3/S23", 4, 4, 1);
+        let world = World::seed_with_symmetry(config.clone(), &cells).unwrap();
+        assert_eq!(world.rle(0, true), "x = 4, y = 4, rule = B3/S23\nb2o$b2o!");
+
+        let config = config.with_symmetry(Symmetry::D4X);
+        let world = World::seed_with_symmetry(config, &cells).unwrap();
+        assert_eq!(world.rle(0, true), "x = 4, y = 4, rule = B3/S23\n$b2o$b2o!");
+    }
+
+    #[test]
+    fn test_rle_with_position() {
+        let cells: Vec<_> = (0..4)
+            .flat_map(|y| (0..4).map(move |x| (x, y)))
+            .map(|(x, y)| {
+                let state = if (1..3).contains(&x) && (1..3).contains(&y) {
+                    CellState::Alive
+                } else {
+                    CellState::Dead
+                };
+                ((x, y, 0), state)
+            })
+            .collect();
+
+        let config = Config::new("B3/S23", 4, 4, 1);
+        let world = World::seed_with_symmetry(config, &cells).unwrap();
+
+        // The leading empty row is omitted, so the position moves down by one row.
+        assert_eq!(
+            world.rle_with_position(0, (10, -5), true),
+            "#CXRLE Pos=10,-4\nx = 4, y = 4, rule = B3/S23\nb2o$b2o!"
+        );
+        assert_eq!(
+            world.rle_with_position(0, (10, -5), false),
+            format!("#CXRLE Pos=10,-5\n{}", world.rle(0, false))
+        );
+    }
+
+    #[test]
+    fn test_still_life() {
+        let count = |w, h| {
+            let mut world = World::new(Config::still_life("B3/S23", w, h)).unwrap();
+            let mut count = 0;
+            world.search_with(None, |_| count += 1);
+            count
+        };
+
+        // Block.
+        assert_eq!(count(2, 2), 1);
+        // Tub, 4 orientations of boat, and 2 orientations of ship.
+        assert_eq!(count(3, 3), 7);
+        // Beehive, and 2 orientations of snake.
+        assert_eq!(count(3, 4), 3);
+    }
+
+    #[test]
+    fn test_search_with() {
+        let config = Config::new("B3/S23", 4, 4, 2);
+
+        let mut world = World::new(config.clone()).unwrap();
+        let mut solutions = Vec::new();
+        while world.search(None) == Status::Solved {
+            solutions.push(world.rle(0, true));
+        }
+
+        let mut world = World::new(config).unwrap();
+        let mut solutions_with = Vec::new();
+        let status = world.search_with(None, |world| solutions_with.push(world.rle(0, true)));
+
+        assert_eq!(status, Status::NoSolution);
+        assert!(!solutions.is_empty());
+        assert_eq!(solutions, solutions_with);
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_miri_serde() {
+        let config = Config::new("B3/S23", 3, 3, 2);
+        let mut world = World::new(config).unwrap();
+
+        let serde = world.to_serde();
+        let mut world2 = World::try_from(serde).unwrap();
+
+        world.search(None);
+        world2.search(None);
+        assert_eq!(world.status(), world2.status());
+        assert_eq!(world.rle(0, true), world2.rle(0, true));
+        assert_eq!(world.peak_population(), world2.peak_population());
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_export_find() {
+        let config = Config::new("B3/S23", 5, 5, 4).with_translations(1, 1);
+        let mut world = World::new(config).unwrap();
+        assert_eq!(world.search(None), Status::Solved);
+
+        let find = world.export_find().unwrap();
+        assert!(find.contains("\"velocity\": [\n    1,\n    1,\n    4\n  ]"));
+
+        let rebuilt = World::from_find(&find).unwrap();
+        assert_eq!(rebuilt.status(), Status::Solved);
+        for t in 0..4 {
+            assert_eq!(rebuilt.rle(t, true), world.rle(t, true));
+        }
+
+        // A blinker is not a glider.
+        let mut broken: serde_json::Value = serde_json::from_str(&find).unwrap();
+        broken["rle"] =
+            "x = 5, y = 5, rule = B3/S23\n.....$\n..o..$\n..o..$\n..o..$\n.....!\n".into();
+        assert!(matches!(
+            World::from_find(&broken.to_string()),
+            Err(FindError::NotASolution)
+        ));
+        assert!(matches!(World::from_find("{}"), Err(FindError::Json(_))));
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_checkpoint() {
+        let config = Config::new("B3/S23", 4, 4, 2);
+
+        let mut world = World::new(config.clone()).unwrap();
+        let mut solutions = Vec::new();
+        world.search_with(None, |world| solutions.push(world.rle(0, true)));
+
+        for steps in [1, 10, 100, 1000] {
+            let mut world = World::new(config.clone()).unwrap();
+            let mut resumed = Vec::new();
+            world.search_with(steps, |world| resumed.push(world.rle(0, true)));
+
+            let checkpoint = world.checkpoint().unwrap();
+            drop(world);
+
+            let mut world = World::restore(&checkpoint).unwrap();
+            let status = world.search_with(None, |world| resumed.push(world.rle(0, true)));
+
+            assert_eq!(status, Status::NoSolution);
+            assert_eq!(solutions, resumed);
+        }
     }
 }
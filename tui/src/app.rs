@@ -1,18 +1,19 @@
-use crate::{
-    args::{LoadArgs, NewArgs},
-    event::TermEvent,
-};
+use crate::{args::NewArgs, event::TermEvent};
 use color_eyre::Result;
 use crossterm::event::KeyCode;
 use factoriosrc_lib::{Status, World};
 use serde::{Deserialize, Serialize};
 use std::{
-    path::PathBuf,
+    path::{Path, PathBuf},
     time::{Duration, Instant},
 };
 
 const DEFAULT_STEP: usize = 100_000;
 
+/// The minimal interval between two updates of the displayed search speed,
+/// so that it does not flicker.
+const STATS_INTERVAL: Duration = Duration::from_millis(500);
+
 /// Application modes.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum Mode {
@@ -58,6 +59,12 @@ pub struct App {
     /// A path to save the application state.
     #[serde(skip)]
     pub save: Option<PathBuf>,
+    /// The time and the number of steps when the search speed was last updated.
+    #[serde(skip)]
+    pub speed_sample: Option<(Instant, u64)>,
+    /// The displayed search speed, in steps per second.
+    #[serde(skip)]
+    pub steps_per_sec: Option<f64>,
 }
 
 impl App {
@@ -75,6 +82,8 @@ impl App {
         let increase_world_size = args.increase_world_size;
         let no_stop = args.no_stop;
         let save = args.save;
+        let speed_sample = None;
+        let steps_per_sec = None;
 
         Ok(Self {
             world,
@@ -89,15 +98,17 @@ impl App {
             increase_world_size,
             no_stop,
             save,
+            speed_sample,
+            steps_per_sec,
         })
     }
 
-    /// Load the [`App`] from the path given in the command line arguments.
-    pub fn load(args: LoadArgs) -> Result<Self> {
-        let path = args.load;
+    /// Load the [`App`] from a path given in the command line arguments,
+    /// and set the path to save it.
+    pub fn load(path: &Path, save: Option<PathBuf>) -> Result<Self> {
         let json = std::fs::read_to_string(path)?;
         let mut app: Self = serde_json::from_str(&json)?;
-        app.save = args.save;
+        app.save = save;
         Ok(app)
     }
 
@@ -134,6 +145,7 @@ impl App {
     fn start(&mut self) {
         if self.mode == Mode::Paused {
             self.start = Some(Instant::now());
+            self.speed_sample = Some((Instant::now(), self.world.stats().steps));
             self.mode = Mode::Running;
         }
     }
@@ -142,24 +154,59 @@ impl App {
     fn pause(&mut self) {
         if self.mode == Mode::Running {
             self.elapsed += self.start.take().unwrap().elapsed();
+            self.speed_sample = None;
             self.mode = Mode::Paused;
         }
     }
 
+    /// Update the displayed search speed.
+    ///
+    /// The speed is only updated once every [`STATS_INTERVAL`], and is averaged with
+    /// the previous value, so that it does not jump around between two refreshes.
+    fn update_speed(&mut self) {
+        let Some((time, steps)) = self.speed_sample else {
+            return;
+        };
+
+        let elapsed = time.elapsed();
+        if elapsed < STATS_INTERVAL {
+            return;
+        }
+
+        let current_steps = self.world.stats().steps;
+        let speed = (current_steps - steps) as f64 / elapsed.as_secs_f64();
+        self.steps_per_sec = Some(
+            self.steps_per_sec
+                .map_or(speed, |previous| (previous + speed) / 2.0),
+        );
+        self.speed_sample = Some((Instant::now(), current_steps));
+    }
+
     /// Run the search for the given number of steps.
-    pub fn step(&mut self) {
+    ///
+    /// Return an error if the world cannot be grown or relaxed after the search fails,
+    /// e.g., because the new world would be too large.
+    pub fn step(&mut self) -> Result<()> {
         let mut status = self.world.search(self.step);
+        self.update_speed();
         if status == Status::Solved {
             self.solution = Some(self.world.rle(self.generation, true));
             self.solution_count += 1;
         }
-        if status == Status::NoSolution && self.increase_world_size {
-            self.world.increase_world_size();
+        if status == Status::NoSolution
+            && (self.increase_world_size || self.world.config().relax_on_no_solution.is_some())
+            && self.world.grow_or_relax()?
+        {
             status = self.world.status();
+            // The new world starts counting steps from zero.
+            if self.speed_sample.is_some() {
+                self.speed_sample = Some((Instant::now(), 0));
+            }
         }
         if status != Status::Running && !self.no_stop || status == Status::NoSolution {
             self.pause();
         }
+        Ok(())
     }
 
     /// Print the last found solution in RLE format.
@@ -1,4 +1,4 @@
-use clap::{error::ErrorKind, Args, CommandFactory, Parser, Subcommand};
+use clap::{error::ErrorKind, Args, CommandFactory, Parser, Subcommand, ValueEnum};
 use factoriosrc_lib::Config;
 use std::path::PathBuf;
 
@@ -14,6 +14,28 @@ pub struct Cli {
     /// or save the state of the search i
     #[arg(long)]
     pub no_tui: bool,
+
+    /// The format of the solutions when the TUI interface is disabled.
+    #[arg(long, value_enum, default_value_t)]
+    pub format: OutputFormat,
+}
+
+/// The format of the solutions when the TUI interface is disabled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum OutputFormat {
+    /// The pattern in RLE format.
+    ///
+    /// Without an output directory, the current partial result is also printed
+    /// after every `step` steps.
+    #[default]
+    Rle,
+
+    /// A JSON object for each solution, with the fields `rle`, `population`, `period`,
+    /// and `velocity`, which is `[dx, dy, period]`, or `null` if the pattern does not move.
+    ///
+    /// Without an output directory, the solutions are printed as a JSON array,
+    /// and the progress is printed to the standard error.
+    Json,
 }
 
 /// Either start a new search or load a saved search.
@@ -21,9 +43,9 @@ pub struct Cli {
 pub enum Command {
     /// Start a new search.
     #[command(arg_required_else_help = true)]
-    New(NewArgs),
+    New(Box<NewArgs>),
 
-    /// Load a saved search.
+    /// Load one or more saved searches.
     Load(LoadArgs),
 }
 
@@ -54,6 +76,9 @@ pub struct NewArgs {
     ///
     /// When the world size is increased, the search will be restarted, and the current search
     /// status will be lost.
+    ///
+    /// If [`relax_on_no_solution`](factoriosrc_lib::Config::relax_on_no_solution) is set,
+    /// the configuration is relaxed as it says instead, even if this is not enabled.
     #[arg(long)]
     pub increase_world_size: bool,
 
@@ -70,21 +95,78 @@ pub struct NewArgs {
     /// The state will be saved when quitting the application.
     #[arg(long)]
     pub save: Option<PathBuf>,
+
+    /// A directory to save the found solutions.
+    ///
+    /// Only used when the TUI interface is disabled. Each solution is saved in the chosen format
+    /// to `solution_{n}.rle` or `solution_{n}.json` in this directory,
+    /// where `n` is the index of the solution,
+    /// and only the progress is printed to the standard error.
+    ///
+    /// If not specified, the current partial result is printed to the standard output.
+    #[arg(long)]
+    pub output_dir: Option<PathBuf>,
+
+    /// Only print the search plan derived from the configuration, without searching.
+    ///
+    /// The plan includes the resolved search order, the world size, the number of front cells,
+    /// the neighborhood offsets, and the estimated memory usage.
+    #[arg(long)]
+    pub dry_run: bool,
 }
 
-/// Load a saved search.
+/// Load one or more saved searches.
 #[derive(Debug, Args)]
 pub struct LoadArgs {
-    /// A path to load the state of the search.
-    pub load: PathBuf,
+    /// Paths to load the states of the searches.
+    ///
+    /// A directory stands for all the files in it, in alphabetical order.
+    ///
+    /// If more than one search is given, the TUI interface must be disabled, and the searches
+    /// are run one after another, each until it stops, and its result is printed.
+    #[arg(required = true)]
+    pub load: Vec<PathBuf>,
 
     /// A path to save the state of the search.
     ///
     /// If not specified, it will default to the path of the loaded state.
+    /// Only allowed when a single search is loaded.
     ///
     /// The state will be saved when quitting the application.
     #[arg(long)]
     pub save: Option<PathBuf>,
+
+    /// A directory to save the found solutions, as with the `--output-dir` option
+    /// of the `new` command.
+    ///
+    /// When more than one search is loaded, the solutions of each search are saved in
+    /// a subdirectory named after the file stem of its save file.
+    #[arg(long)]
+    pub output_dir: Option<PathBuf>,
+}
+
+impl LoadArgs {
+    /// Replace each directory in [`load`](LoadArgs::load) by the files in it,
+    /// in alphabetical order.
+    fn expand_dirs(&mut self) -> std::io::Result<()> {
+        let mut paths = Vec::with_capacity(self.load.len());
+
+        for path in self.load.drain(..) {
+            if path.is_dir() {
+                let mut files = std::fs::read_dir(&path)?
+                    .map(|entry| entry.map(|entry| entry.path()))
+                    .collect::<std::io::Result<Vec<_>>>()?;
+                files.retain(|file| file.is_file());
+                files.sort();
+                paths.extend(files);
+            } else {
+                paths.push(path);
+            }
+        }
+
+        self.load = paths;
+        Ok(())
+    }
 }
 
 impl Cli {
@@ -100,12 +182,51 @@ impl Cli {
                         .exit();
                 }
 
+                // Only warn about a search order that the user specified,
+                // before `check` chooses one automatically.
+                if let Some(suggested) = args.config.suggested_search_order() {
+                    eprintln!(
+                        "Warning: this search order disables the front reduction, \
+                         so the search may be slow. Consider {suggested} instead."
+                    );
+                }
+
                 if let Err(e) = args.config.check() {
                     Self::command().error(ErrorKind::ValueValidation, e).exit();
                 }
             }
-            Command::Load(args) => {
-                args.save.get_or_insert(args.load.clone());
+            Command::Load(load_args) => {
+                if let Err(e) = load_args.expand_dirs() {
+                    Self::command().error(ErrorKind::Io, e).exit();
+                }
+
+                match load_args.load.len() {
+                    0 => Self::command()
+                        .error(ErrorKind::ValueValidation, "no saved search to load")
+                        .exit(),
+                    1 => {
+                        load_args.save.get_or_insert(load_args.load[0].clone());
+                    }
+                    _ => {
+                        if load_args.save.is_some() {
+                            Self::command()
+                                .error(
+                                    ErrorKind::ArgumentConflict,
+                                    "--save is only allowed when a single search is loaded",
+                                )
+                                .exit();
+                        }
+
+                        if !args.no_tui {
+                            Self::command()
+                                .error(
+                                    ErrorKind::ArgumentConflict,
+                                    "loading more than one search requires --no-tui",
+                                )
+                                .exit();
+                        }
+                    }
+                }
             }
         }
 
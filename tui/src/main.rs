@@ -6,27 +6,190 @@ mod ui;
 
 use crate::{
     app::App,
-    args::{Cli, Command},
+    args::{Cli, Command, OutputFormat},
     tui::Tui,
 };
 use color_eyre::Result;
 use crossterm::tty::IsTty;
 use factoriosrc_lib::{Status, World};
-use std::io::stdout;
+use serde_json::json;
+use std::{io::stdout, path::Path};
+
+/// Prints the solutions to the standard output in the chosen format.
+#[derive(Debug)]
+struct Printer {
+    /// The output format.
+    format: OutputFormat,
+    /// The number of solutions printed so far.
+    count: usize,
+}
+
+impl Printer {
+    /// Create a printer, and print the start of the output.
+    fn new(format: OutputFormat) -> Self {
+        if format == OutputFormat::Json {
+            print!("[");
+        }
+        Self { format, count: 0 }
+    }
+
+    /// Print a solution, or the current partial result if the search is not solved.
+    ///
+    /// In JSON format, only the solutions are printed,
+    /// and the status is printed to the standard error otherwise.
+    fn print(&mut self, world: &World) {
+        match self.format {
+            OutputFormat::Rle => println!("{}", world.rle(0, true)),
+            OutputFormat::Json => {
+                if world.status() == Status::Solved {
+                    if self.count > 0 {
+                        print!(",");
+                    }
+                    print!("\n  {}", solution_json(world));
+                    self.count += 1;
+                } else {
+                    eprintln!("Status: {}", world.status());
+                }
+            }
+        }
+    }
+}
+
+impl Drop for Printer {
+    /// Print the end of the output.
+    ///
+    /// This is done on drop, so that the JSON array is closed
+    /// even if the search stops early because of an error.
+    fn drop(&mut self) {
+        if self.format == OutputFormat::Json {
+            println!("{}]", if self.count > 0 { "\n" } else { "" });
+        }
+    }
+}
+
+/// The extension of the files to save the solutions in a format.
+const fn extension(format: OutputFormat) -> &'static str {
+    match format {
+        OutputFormat::Rle => "rle",
+        OutputFormat::Json => "json",
+    }
+}
+
+/// A solution as a JSON object, with its RLE and some metadata.
+fn solution_json(world: &World) -> serde_json::Value {
+    json!({
+        "rle": world.rle(0, true),
+        "population": world.population(0),
+        "period": world.cached_true_period(),
+        "velocity": world.velocity(),
+    })
+}
 
 /// Run the program without the TUI interface.
 fn run_no_tui(args: Cli) -> Result<()> {
-    let (mut world, step) = match args.command {
-        Command::New(args) => (World::new(args.config)?, args.step),
+    let mut printer = Printer::new(args.format);
+
+    match args.command {
+        Command::New(args) => run_search(
+            World::new(args.config)?,
+            args.step,
+            args.increase_world_size,
+            args.no_stop,
+            args.output_dir.as_deref(),
+            0,
+            &mut printer,
+        )?,
         Command::Load(args) => {
-            let app = App::load(args)?;
-            (app.world, Some(app.step))
+            let batch = args.load.len() > 1;
+
+            for path in &args.load {
+                if batch {
+                    eprintln!("Loading {}", path.display());
+                }
+
+                let app = App::load(path, None)?;
+
+                // Keep the solutions of different searches apart.
+                let output_dir = args.output_dir.as_ref().map(|output_dir| {
+                    if batch {
+                        output_dir.join(path.file_stem().unwrap_or(path.as_os_str()))
+                    } else {
+                        output_dir.clone()
+                    }
+                });
+
+                run_search(
+                    app.world,
+                    Some(app.step),
+                    app.increase_world_size,
+                    app.no_stop,
+                    output_dir.as_deref(),
+                    app.solution_count,
+                    &mut printer,
+                )?;
+            }
         }
-    };
+    }
 
-    while matches!(world.status(), Status::NotStarted | Status::Running) {
-        world.search(step);
-        println!("{}", world.rle(0, true));
+    Ok(())
+}
+
+/// Run a search without the TUI interface, until it stops.
+///
+/// See [`NewArgs::output_dir`](crate::args::NewArgs::output_dir) and [`OutputFormat`]
+/// for how the results are printed.
+///
+/// When no more solutions exist, the world is grown or relaxed as in the TUI,
+/// if [`NewArgs::increase_world_size`](crate::args::NewArgs::increase_world_size) is enabled
+/// or the configuration sets [`relax_on_no_solution`](factoriosrc_lib::Config::relax_on_no_solution).
+fn run_search(
+    mut world: World,
+    step: Option<usize>,
+    increase_world_size: bool,
+    no_stop: bool,
+    output_dir: Option<&Path>,
+    mut solution_count: usize,
+    printer: &mut Printer,
+) -> Result<()> {
+    if let Some(output_dir) = output_dir {
+        std::fs::create_dir_all(output_dir)?;
+    }
+
+    loop {
+        let outcome = world.search_outcome(step);
+        let status = outcome.status;
+
+        if let Some(output_dir) = output_dir {
+            if status == Status::Solved {
+                solution_count += 1;
+                let path = output_dir.join(format!(
+                    "solution_{solution_count}.{}",
+                    extension(printer.format)
+                ));
+                let content = match printer.format {
+                    OutputFormat::Rle => world.rle(0, true),
+                    OutputFormat::Json => serde_json::to_string_pretty(&solution_json(&world))?,
+                };
+                std::fs::write(&path, content)?;
+                eprintln!("Solution {solution_count} saved to {}", path.display());
+            } else {
+                eprintln!("Status: {status}");
+            }
+        } else {
+            printer.print(&world);
+        }
+
+        if status == Status::NoSolution
+            && (increase_world_size || world.config().relax_on_no_solution.is_some())
+            && world.grow_or_relax()?
+        {
+            eprintln!("Relaxing the configuration.");
+            continue;
+        }
+
+        if !(outcome.exhausted_budget || status == Status::Solved && no_stop) {
+            break;
+        }
     }
 
     Ok(())
@@ -35,6 +198,13 @@ fn run_no_tui(args: Cli) -> Result<()> {
 fn main() -> Result<()> {
     let args = Cli::parse_and_validate();
 
+    if let Command::New(args) = &args.command {
+        if args.dry_run {
+            println!("{}", World::new(args.config.clone())?.plan());
+            return Ok(());
+        }
+    }
+
     let stdout = stdout();
 
     if args.no_tui || !stdout.is_tty() {
@@ -28,8 +28,8 @@ impl Tui {
         let terminal = Terminal::new(backend)?;
 
         let app = match args.command {
-            Command::New(args) => App::new(args)?,
-            Command::Load(args) => App::load(args)?,
+            Command::New(args) => App::new(*args)?,
+            Command::Load(args) => App::load(&args.load[0], args.save)?,
         };
 
         let event_handler = EventHandler::new();
@@ -83,7 +83,7 @@ impl Tui {
         while !self.app.should_quit {
             // If the application is running, do not block on the event handler.
             if self.app.mode == Mode::Running {
-                self.app.step();
+                self.app.step()?;
                 if let Some(event) = self.event_handler.try_recv()? {
                     self.app.update(event);
                 }
@@ -103,9 +103,44 @@ impl App {
     }
 
     /// Render the main area.
+    ///
+    /// This includes the current generation in the RLE format, and the statistics pane on the right.
     fn render_main(&self, frame: &mut Frame, area: Rect) {
+        let [rle_area, stats_area] =
+            Layout::horizontal([Constraint::Min(0), Constraint::Length(24)]).areas(area);
+
         let rle = Rle::new(self);
-        frame.render_widget(rle, area);
+        frame.render_widget(rle, rle_area);
+
+        self.render_stats(frame, stats_area);
+    }
+
+    /// Render the statistics pane.
+    ///
+    /// This includes the search speed, the number of steps and backtracks, the current depth of
+    /// the stack, and the population of the current generation.
+    fn render_stats(&self, frame: &mut Frame, area: Rect) {
+        let stats = self.world.stats();
+
+        let speed = self
+            .steps_per_sec
+            .filter(|_| self.mode == Mode::Running)
+            .map_or_else(|| "-".to_string(), |speed| format!("{speed:.0}"));
+
+        let text = format!(
+            "Steps/s:    {speed}\n\
+             Steps:      {}\n\
+             Backtracks: {}\n\
+             Depth:      {}\n\
+             Population: {}",
+            stats.steps,
+            stats.backtracks,
+            stats.stack_depth,
+            self.world.population(self.generation),
+        );
+
+        let paragraph = Paragraph::new(text).block(Block::bordered().title("Stats"));
+        frame.render_widget(paragraph, area);
     }
 
     /// Render a popup window with some text.
@@ -209,23 +244,15 @@ impl Widget for Rle<'_> {
                 for x in 0..w.min(area.width) {
                     let buf_x = area.x + x;
                     let state = self.world.get_cell_state((x as i32, y as i32, self.t));
-                    match state {
-                        Some(CellState::Alive) => buf
-                            .cell_mut((buf_x, buf_y))
-                            .unwrap()
-                            .set_char('o')
-                            .set_style(Style::new().green()),
-                        Some(CellState::Dead) => buf
-                            .cell_mut((buf_x, buf_y))
-                            .unwrap()
-                            .set_char('.')
-                            .set_style(Style::new().red()),
-                        None => buf
-                            .cell_mut((buf_x, buf_y))
-                            .unwrap()
-                            .set_char('?')
-                            .set_style(Style::new().blue()),
+                    let style = match state {
+                        Some(CellState::Alive) => Style::new().green(),
+                        Some(CellState::Dead) => Style::new().red(),
+                        None => Style::new().blue(),
                     };
+                    buf.cell_mut((buf_x, buf_y))
+                        .unwrap()
+                        .set_char(CellState::glyph_opt(state))
+                        .set_style(style);
                 }
                 if area.width > w + 1 {
                     let buf_x: u16 = area.x + w;